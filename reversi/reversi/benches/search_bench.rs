@@ -1,6 +1,19 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use reversi::SparseVector;
 
 fn benchmark_test(_c: &mut Criterion) {}
 
-criterion_group!(benches, benchmark_test,);
+fn benchmark_sparse_dot(c: &mut Criterion) {
+    // パターン評価の特徴量に近い規模（数十個の非ゼロ要素、数十万次元）
+    let length = 200_000;
+    let weights: Vec<f32> = (0..length).map(|i| (i % 97) as f32 * 0.01).collect();
+    let elements: Vec<(usize, f32)> = (0..46).map(|k| (k * 4099 % length, 1.0)).collect();
+    let feature = SparseVector::from(&elements, length).unwrap();
+
+    c.bench_function("sparse_dot", |b| {
+        b.iter(|| black_box(&feature).dot(black_box(&weights)).unwrap())
+    });
+}
+
+criterion_group!(benches, benchmark_test, benchmark_sparse_dot);
 criterion_main!(benches);
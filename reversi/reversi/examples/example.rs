@@ -61,7 +61,7 @@ fn get_move_from_stdin(board: &BitBoard, color: Color) -> Option<Position> {
 
         match parse_position(&input) {
             Some(pos) => {
-                if board.get_valid_moves(color).contains(&pos) {
+                if board.is_legal(color, &pos) {
                     return Some(pos);
                 } else {
                     println!("Invalid move(): not a valid position. Try again.");
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::{bit_board::BitBoard, board::BOARD_SIZE, Color, Move, Position};
 
 mod evaluator;
@@ -18,7 +20,7 @@ pub struct SearchResult {
 
 pub enum Searcher {
     TestNegaalpha(Negaalpha<TestEvaluator>),
-    TempuraNegaalpha(Negaalpha<TempuraEvaluator>),
+    TempuraNegaalpha(Negaalpha<Arc<TempuraEvaluator>>),
 }
 
 impl Searcher {
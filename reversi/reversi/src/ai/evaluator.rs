@@ -10,12 +10,21 @@ pub use simple_evaluator::SimpleEvaluator;
 pub use tempura_evaluator::TempuraEvaluator;
 pub use test_evaluator::TestEvaluator;
 
+use std::sync::Arc;
+
 use crate::{bit_board::BitBoard, Color};
 
 pub trait Evaluator {
     fn evaluate(&self, board: &BitBoard, color: Color) -> i32;
 }
 
+/// 読み込んだモデルを複数の探索で共有できるよう、`Arc` 越しにも評価できるようにする
+impl<E: Evaluator + ?Sized> Evaluator for Arc<E> {
+    fn evaluate(&self, board: &BitBoard, color: Color) -> i32 {
+        (**self).evaluate(board, color)
+    }
+}
+
 pub fn add_noise(value: i32, epsilon: f64, rng: &mut impl rand::Rng) -> i32 {
     use rand_distr::Distribution;
     let normal = rand_distr::Normal::new(0.0, epsilon).unwrap();
@@ -13,6 +13,8 @@ pub struct TempuraEvaluator {
     pub test_evaluator: TestEvaluator,
     pub patterns: Vec<Pattern>,
     pub model: Model,
+    /// パターンの 4 つの回転で重みを共有するかどうか
+    pub shared_rotations: bool,
 }
 
 impl Default for TempuraEvaluator {
@@ -26,21 +28,45 @@ impl Default for TempuraEvaluator {
             patterns,
             model,
             test_evaluator,
+            shared_rotations: false,
         }
     }
 }
 
 impl TempuraEvaluator {
+    /// モデルを読み込む
+    ///
+    /// パターンの定義が変わって特徴量の次元が合わないモデルはエラーになる。
     pub fn load<P: AsRef<Path>>(file_path: P) -> ResultBoxErr<Self> {
         let model: Model = Model::load_model(file_path)?;
         let patterns = generate_patterns();
         let test_evaluator = TestEvaluator::default();
 
-        Ok(Self {
+        let evaluator = Self {
             patterns,
             model,
             test_evaluator,
-        })
+            shared_rotations: false,
+        };
+        let model_feature_size = evaluator.model.params.first().map_or(0, |p| p.len());
+        if model_feature_size != evaluator.feature_size() {
+            return Err(format!(
+                "モデルの特徴量の次元が現在のパターンと一致しません (モデル: {}, パターン: {})。モデルを学習し直してください",
+                model_feature_size,
+                evaluator.feature_size()
+            )
+            .into());
+        }
+
+        Ok(evaluator)
+    }
+
+    /// 4 つの回転で重みを共有する特徴量を使うかどうかを設定する
+    ///
+    /// 学習時と推論時で同じ設定にする必要がある。
+    pub fn with_shared_rotations(mut self, shared_rotations: bool) -> Self {
+        self.shared_rotations = shared_rotations;
+        self
     }
 
     pub fn patterns(&self) -> &Vec<Pattern> {
@@ -51,7 +77,12 @@ impl TempuraEvaluator {
         self.patterns
             .iter()
             .fold(SparseVector::default(), |acc, pattern| {
-                acc.concat(&pattern.feature(board)).unwrap_or_default()
+                let feature = if self.shared_rotations {
+                    pattern.shared_feature(board)
+                } else {
+                    pattern.feature(board)
+                };
+                acc.concat(&feature).unwrap_or_default()
             })
     }
 
@@ -202,16 +233,14 @@ const CORNER_D: [Position; 10] = [
     Position::A5,
 ];
 
-const CORNER_E: [Position; 10] = [
+const CORNER_E: [Position; 8] = [
     Position::A1,
     Position::B1,
     Position::A2,
     Position::B2,
     Position::C2,
     Position::D2,
-    Position::B2,
     Position::C3,
-    Position::D2,
     Position::D4,
 ];
 
@@ -268,22 +297,77 @@ const EDGE_D: [Position; 10] = [
 ];
 
 fn generate_patterns() -> Vec<Pattern> {
-    vec![
-        Pattern::from_positions(0, &LINE_A),
-        Pattern::from_positions(1, &LINE_B),
-        Pattern::from_positions(2, &LINE_C),
-        Pattern::from_positions(4, &DIAGONAL_A),
-        Pattern::from_positions(5, &DIAGONAL_B),
-        Pattern::from_positions(6, &DIAGONAL_C),
-        Pattern::from_positions(7, &DIAGONAL_D),
-        Pattern::from_positions(3, &CORNER_A),
-        Pattern::from_positions(9, &CORNER_B),
-        Pattern::from_positions(11, &CORNER_C),
-        Pattern::from_positions(13, &CORNER_D),
-        Pattern::from_positions(14, &CORNER_E),
-        Pattern::from_positions(8, &EDGE_A),
-        Pattern::from_positions(10, &EDGE_B),
-        Pattern::from_positions(12, &EDGE_C),
-        Pattern::from_positions(15, &EDGE_D),
+    [
+        (0, &LINE_A[..]),
+        (1, &LINE_B[..]),
+        (2, &LINE_C[..]),
+        (4, &DIAGONAL_A[..]),
+        (5, &DIAGONAL_B[..]),
+        (6, &DIAGONAL_C[..]),
+        (7, &DIAGONAL_D[..]),
+        (3, &CORNER_A[..]),
+        (9, &CORNER_B[..]),
+        (11, &CORNER_C[..]),
+        (13, &CORNER_D[..]),
+        (14, &CORNER_E[..]),
+        (8, &EDGE_A[..]),
+        (10, &EDGE_B[..]),
+        (12, &EDGE_C[..]),
+        (15, &EDGE_D[..]),
     ]
+    .into_iter()
+    .map(|(id, positions)| {
+        Pattern::from_positions(id, positions).expect("定義済みのパターンは正しい座標で構成される")
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::pattern::rotate_board_90;
+
+    #[test]
+    fn test_shared_rotations_output_is_rotation_invariant() {
+        let evaluator = TempuraEvaluator::default().with_shared_rotations(true);
+
+        let board = BitBoard {
+            black: 0x0000_0010_3818_0c07,
+            white: 0x0000_2008_0420_1000,
+        };
+        let rotated = rotate_board_90(&board);
+
+        let phase = 30;
+        let inputs = [
+            ModelInput {
+                phase,
+                feature: evaluator.feature(&board),
+            },
+            ModelInput {
+                phase,
+                feature: evaluator.feature(&rotated),
+            },
+        ];
+        let outputs = evaluator.model.forward(&inputs);
+        assert_eq!(outputs[0], outputs[1]);
+    }
+
+    #[test]
+    fn test_load_rejects_model_with_different_feature_size() -> ResultBoxErr<()> {
+        let evaluator = TempuraEvaluator::default();
+        let path = std::env::temp_dir().join(format!(
+            "reversi_tempura_feature_size_test_{}.bin",
+            std::process::id()
+        ));
+
+        Model::save_model(&evaluator.model, &path)?;
+        let loaded = TempuraEvaluator::load(&path);
+        assert!(loaded.is_ok());
+
+        Model::save_model(&Model::new(evaluator.feature_size() + 1), &path)?;
+        let loaded = TempuraEvaluator::load(&path);
+        std::fs::remove_file(&path)?;
+        assert!(loaded.is_err());
+        Ok(())
+    }
 }
@@ -3,7 +3,7 @@ use std::{collections::HashMap, fs::File, io::Read};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
-use crate::{bit_board::BitBoard, Position, SparseVector};
+use crate::{bit_board::BitBoard, Position, SparseVector, BOARD_SIZE};
 
 pub const PATTERN_ROTATION_0: usize = 0;
 pub const PATTERN_ROTATION_90: usize = 1;
@@ -14,22 +14,42 @@ pub const PATTERN_ROTATION_270: usize = 3;
 pub struct Pattern {
     pub id: usize,
     pub masks: [u64; 4],
+    /// 回転ごとのマスのビット位置
+    ///
+    /// 回転なしのマスをビット位置の昇順に並べ、各回転ではその順番を保ったまま回転させる。
+    /// この順番で状態を数えると、回転した盤面どうしが同じ状態インデックスになる。
+    pub cells: [Vec<u8>; 4],
 }
 
 impl Pattern {
-    pub fn from_positions(id: usize, positions: &[Position]) -> Self {
+    /// 座標列からパターンを生成する
+    ///
+    /// 盤外の座標や重複した座標が含まれる場合はエラーを返す
+    pub fn from_positions(id: usize, positions: &[Position]) -> Result<Self, String> {
+        for (i, pos) in positions.iter().enumerate() {
+            if pos.x as usize >= BOARD_SIZE || pos.y as usize >= BOARD_SIZE {
+                return Err(format!("パターン {} の座標 {:?} が盤外です", id, pos));
+            }
+            if positions[..i].contains(pos) {
+                return Err(format!("パターン {} の座標 {:?} が重複しています", id, pos));
+            }
+        }
+
         let mut masks = [0u64; 4];
+        let mut cells: [Vec<u8>; 4] = Default::default();
         let mut positions = positions.to_vec();
+        positions.sort_by_key(|p| p.to_index());
 
-        masks.iter_mut().for_each(|mask| {
+        masks.iter_mut().zip(&mut cells).for_each(|(mask, cells)| {
             for pos in &positions {
                 let bit_index = pos.to_index();
                 *mask |= 1 << bit_index;
+                cells.push(bit_index as u8);
             }
             positions.iter_mut().for_each(|p| p.rotate_90());
         });
 
-        Self { id, masks }
+        Ok(Self { id, masks, cells })
     }
 
     pub fn state_count(&self) -> usize {
@@ -75,10 +95,43 @@ impl Pattern {
         indices
     }
 
+    /// 回転ごとの状態インデックスを、回転なしのマスの順番で数えて返す
+    ///
+    /// 回転した盤面どうしが同じインデックスになるため、4 つの回転で重みを共有できる。
+    pub fn shared_state_indices(&self, board: &BitBoard) -> [usize; 4] {
+        let mut indices = [0usize; 4];
+        indices
+            .iter_mut()
+            .zip(&self.cells)
+            .for_each(|(index, cells)| {
+                *index = cells.iter().rev().fold(0, |acc, &bit_index| {
+                    let bit = 1u64 << bit_index;
+                    let val = if (board.black & bit) != 0 {
+                        1
+                    } else if (board.white & bit) != 0 {
+                        2
+                    } else {
+                        0
+                    };
+                    acc * 3 + val
+                });
+            });
+        indices
+    }
+
     pub fn feature(&self, board: &BitBoard) -> SparseVector {
+        self.feature_from_indices(self.state_indices(board))
+    }
+
+    /// 4 つの回転で重みを共有する特徴量を返す
+    pub fn shared_feature(&self, board: &BitBoard) -> SparseVector {
+        self.feature_from_indices(self.shared_state_indices(board))
+    }
+
+    fn feature_from_indices(&self, state_indices: [usize; 4]) -> SparseVector {
         let mut index_count: HashMap<usize, f32> = HashMap::new();
 
-        for index in self.state_indices(board) {
+        for index in state_indices {
             *index_count.entry(index).or_insert(0.0) += 1.0;
         }
 
@@ -94,6 +147,22 @@ impl Pattern {
     }
 }
 
+/// 盤面を 90 度回転させる
+#[cfg(test)]
+pub(crate) fn rotate_board_90(board: &BitBoard) -> BitBoard {
+    let rotate = |bits: u64| {
+        (0..64)
+            .filter(|&i| bits & (1 << i) != 0)
+            .fold(0u64, |acc, i| {
+                acc | (1 << Position::from_index(i).rotated_90().to_index())
+            })
+    };
+    BitBoard {
+        black: rotate(board.black),
+        white: rotate(board.white),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,7 +174,7 @@ mod tests {
             Position { x: 1, y: 0 },
             Position { x: 0, y: 1 },
         ];
-        let pattern = Pattern::from_positions(1, &positions);
+        let pattern = Pattern::from_positions(1, &positions).unwrap();
 
         let expected_masks = [
             0b0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001_0000_0011, // 回転なし
@@ -130,7 +199,7 @@ mod tests {
             Position { x: 1, y: 0 },
             Position { x: 0, y: 1 },
         ];
-        let pattern = Pattern::from_positions(1, &positions);
+        let pattern = Pattern::from_positions(1, &positions).unwrap();
         assert_eq!(pattern.state_count(), 3 * 3 * 3);
     }
 
@@ -141,7 +210,7 @@ mod tests {
             Position { x: 1, y: 0 },
             Position { x: 0, y: 1 },
         ];
-        let pattern = Pattern::from_positions(1, &positions);
+        let pattern = Pattern::from_positions(1, &positions).unwrap();
 
         let board = BitBoard {
             black: 0b0000_0001_0000_0010,
@@ -165,7 +234,7 @@ mod tests {
             Position { x: 1, y: 0 },
             Position { x: 0, y: 1 },
         ];
-        let pattern = Pattern::from_positions(1, &positions);
+        let pattern = Pattern::from_positions(1, &positions).unwrap();
 
         let board = BitBoard {
             black: 0b0000_0001_0000_0010,
@@ -185,4 +254,53 @@ mod tests {
         assert_eq!(feature.indices()[1], 14);
         assert_eq!(feature.values()[1], 1.0);
     }
+
+    #[test]
+    fn test_from_positions_corner_3x3() {
+        let positions: Vec<Position> = (0..3)
+            .flat_map(|y| (0..3).map(move |x| Position::new(x, y)))
+            .collect();
+        let pattern = Pattern::from_positions(0, &positions).unwrap();
+
+        assert_eq!(pattern.masks[PATTERN_ROTATION_0], 0x0000_0000_0007_0707);
+        assert!(pattern.masks.iter().all(|mask| mask.count_ones() == 9));
+        assert_eq!(pattern.state_count(), 3usize.pow(9));
+    }
+
+    #[test]
+    fn test_from_positions_rejects_invalid_positions() {
+        let duplicated = [Position::A1, Position::B1, Position::A1];
+        assert!(Pattern::from_positions(0, &duplicated).is_err());
+
+        let out_of_range = [Position::A1, Position { x: 8, y: 0 }];
+        assert!(Pattern::from_positions(0, &out_of_range).is_err());
+    }
+
+    #[test]
+    fn test_shared_feature_is_rotation_invariant() {
+        let pattern =
+            Pattern::from_positions(0, &[Position::B1, Position::A1, Position::A2]).unwrap();
+
+        // 回転なしでは従来のインデックスと一致する
+        let board = BitBoard {
+            black: (1 << Position::A1.to_index()) | (1 << Position::C3.to_index()),
+            white: 1 << Position::B1.to_index(),
+        };
+        assert_eq!(
+            pattern.shared_state_indices(&board)[PATTERN_ROTATION_0],
+            pattern.state_indices(&board)[PATTERN_ROTATION_0]
+        );
+
+        let rotated = rotate_board_90(&board);
+        let feature = pattern.shared_feature(&board);
+        let rotated_feature = pattern.shared_feature(&rotated);
+        assert_eq!(feature.indices(), rotated_feature.indices());
+        assert_eq!(feature.values(), rotated_feature.values());
+
+        // 従来の特徴量では回転によってインデックスが変わる
+        assert_ne!(
+            pattern.feature(&board).indices(),
+            pattern.feature(&rotated).indices()
+        );
+    }
 }
@@ -183,23 +183,6 @@ impl Board for ArrayBoard {
         }
         valid_moves
     }
-
-    fn display(&self) {
-        println!("  A B C D E F G H");
-        for y in 0..BOARD_SIZE {
-            for x in 0..BOARD_SIZE {
-                print!(" ");
-                let index = x + y * BOARD_SIZE;
-                match self.discs[index] {
-                    EMPTY => print!("-"),
-                    BLACK => print!("B"),
-                    WHITE => print!("W"),
-                    _ => print!("?"),
-                };
-            }
-            println!();
-        }
-    }
 }
 
 #[cfg(test)]
@@ -254,4 +237,25 @@ mod tests {
 
         board.display();
     }
+
+    #[test]
+    fn test_render_matches_bit_board() {
+        let board = ArrayBoard::init_board();
+        let bit_board = crate::BitBoard::init_board();
+        assert_eq!(board.render(), bit_board.render());
+        assert_eq!(
+            board.render_with_moves(Color::White),
+            bit_board.render_with_moves(Color::White)
+        );
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let mut board = ArrayBoard::init_board();
+        assert!(board.make_move(Color::Black, &Position::F5));
+
+        let bytes = board.to_bytes();
+        assert_eq!(ArrayBoard::from_bytes(bytes), board);
+        assert_eq!(ArrayBoard::from_bytes([0; 16]), ArrayBoard::default());
+    }
 }
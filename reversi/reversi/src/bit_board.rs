@@ -1,5 +1,7 @@
+use std::sync::OnceLock;
+
 use crate::{
-    board::{Board, BOARD_SIZE},
+    board::{split_bytes, Board, BOARD_SIZE},
     CellState, Color, Direction, Position,
 };
 
@@ -70,10 +72,117 @@ fn get_valid_moves_bits(player_bits: u64, opponent_bits: u64) -> u64 {
     valid_moves
 }
 
+// 横方向 (東西) を除いた方向
+const NON_HORIZONTAL_DIRECTIONS: [Direction; 6] = [
+    Direction::South,
+    Direction::North,
+    Direction::SouthEast,
+    Direction::SouthWest,
+    Direction::NorthEast,
+    Direction::NorthWest,
+];
+
+// 1 行 8 マスの状態数 (空き・自石・相手石の 3 通り)
+const LINE_OCCUPANCY_COUNT: usize = 6561;
+
+// 1 行 8 マスの 2 進表現を、各桁が 0 か 1 の 3 進表現に変換する表
+fn ternary_digits() -> &'static [u16; 256] {
+    static TABLE: OnceLock<[u16; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0u16; 256];
+        for (bits, value) in table.iter_mut().enumerate() {
+            let mut weight = 1;
+            for x in 0..BOARD_SIZE {
+                if bits & (1 << x) != 0 {
+                    *value += weight;
+                }
+                weight *= 3;
+            }
+        }
+        table
+    })
+}
+
+// 1 行の状態を 3 進数のインデックスに変換する (空き 0、自石 1、相手石 2)
+fn line_occupancy(player_line: u8, opponent_line: u8) -> usize {
+    let digits = ternary_digits();
+    digits[player_line as usize] as usize + 2 * digits[opponent_line as usize] as usize
+}
+
+// 1 行の中で x に打ったときに横方向で裏返る石 (表を作るための一般的な計算)
+fn get_line_flips(player_line: u8, opponent_line: u8, x: usize) -> u8 {
+    let mut flips = 0u8;
+    for step in [1i32, -1] {
+        let mut tmp_flips = 0u8;
+        let mut cx = x as i32 + step;
+        while (0..BOARD_SIZE as i32).contains(&cx) && opponent_line & (1 << cx) != 0 {
+            tmp_flips |= 1 << cx;
+            cx += step;
+        }
+        if (0..BOARD_SIZE as i32).contains(&cx) && player_line & (1 << cx) != 0 {
+            flips |= tmp_flips;
+        }
+    }
+    flips
+}
+
+// 辺の横方向の裏返りを (行の状態, 打つ列) ごとに事前計算した表
+fn edge_flip_table() -> &'static [u8] {
+    static TABLE: OnceLock<Vec<u8>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = vec![0u8; LINE_OCCUPANCY_COUNT * BOARD_SIZE];
+        for player_line in 0..=u8::MAX {
+            for opponent_line in 0..=u8::MAX {
+                if player_line & opponent_line != 0 {
+                    continue;
+                }
+                let occupancy = line_occupancy(player_line, opponent_line);
+                for x in 0..BOARD_SIZE {
+                    if (player_line | opponent_line) & (1 << x) == 0 {
+                        table[occupancy * BOARD_SIZE + x] =
+                            get_line_flips(player_line, opponent_line, x);
+                    }
+                }
+            }
+        }
+        table
+    })
+}
+
+// 最初と最後の行に打つ手の裏返り (横方向は表から引き、残りの方向だけ計算する)
+fn get_edge_flips_bits(move_bit: u64, player_bits: u64, opponent_bits: u64) -> u64 {
+    let index = move_bit.trailing_zeros() as usize;
+    let shift = index / BOARD_SIZE * BOARD_SIZE;
+    let occupancy = line_occupancy((player_bits >> shift) as u8, (opponent_bits >> shift) as u8);
+    let horizontal = edge_flip_table()[occupancy * BOARD_SIZE + index % BOARD_SIZE];
+    ((horizontal as u64) << shift)
+        | get_flips_bits_in_directions(
+            move_bit,
+            player_bits,
+            opponent_bits,
+            &NON_HORIZONTAL_DIRECTIONS,
+        )
+}
+
 fn get_flips_bits(move_bit: u64, player_bits: u64, opponent_bits: u64) -> u64 {
+    // 辺の表は edge_flip_table フィーチャーを有効にしたときだけ使う
+    const EDGE_RANKS: u64 = 0xff00_0000_0000_00ff;
+    if cfg!(feature = "edge_flip_table") && move_bit & EDGE_RANKS != 0 {
+        return get_edge_flips_bits(move_bit, player_bits, opponent_bits);
+    }
+
+    get_flips_bits_in_directions(move_bit, player_bits, opponent_bits, &Direction::DIRECTIONS)
+}
+
+fn get_flips_bits_in_directions(
+    move_bit: u64,
+    player_bits: u64,
+    opponent_bits: u64,
+    directions: &[Direction],
+) -> u64 {
     let mut flips = 0u64;
 
-    for dir in Direction::DIRECTIONS {
+    for &dir in directions {
         let (shift_amount, mask) = get_shift_and_mask_for_flips(dir);
         let mut tmp_flips = 0;
         let mut tmp = shift_bits(move_bit, shift_amount) & mask;
@@ -89,6 +198,18 @@ fn get_flips_bits(move_bit: u64, player_bits: u64, opponent_bits: u64) -> u64 {
     flips
 }
 
+// 立っているビットの位置を下位ビットから順に返す
+fn positions_from_bits(mut bits: u64) -> Vec<Position> {
+    let mut positions = Vec::new();
+    while bits != 0 {
+        let lsb = bits & (!bits + 1); // 最下位の1ビットを取得
+        let index = lsb.trailing_zeros() as usize;
+        positions.push(Position::from_index(index));
+        bits &= bits - 1; // 最下位の1ビットをクリア
+    }
+    positions
+}
+
 impl BitBoard {
     pub fn new() -> Self {
         Self::default()
@@ -100,6 +221,52 @@ impl BitBoard {
         board
     }
 
+    /// 石を置き、裏返った石の位置を返す（GUI のアニメーション用）
+    ///
+    /// 合法手でなければ盤面を変えずに `None` を返す。
+    pub fn make_move_checked(&mut self, color: Color, pos: &Position) -> Option<Vec<Position>> {
+        self.apply_move_bits(color, pos).map(positions_from_bits)
+    }
+
+    // 石を置き、裏返った石のビットを返す（合法手でなければ盤面を変えずに None）
+    fn apply_move_bits(&mut self, color: Color, pos: &Position) -> Option<u64> {
+        let move_bit = 1u64 << pos.to_index();
+
+        let (player_bits, opponent_bits) = match color {
+            Color::Black => (&mut self.black, &mut self.white),
+            Color::White => (&mut self.white, &mut self.black),
+        };
+        let valid_moves = get_valid_moves_bits(*player_bits, *opponent_bits);
+
+        if valid_moves & move_bit == 0 {
+            // Invalid move
+            return None;
+        }
+
+        let flips = get_flips_bits(move_bit, *player_bits, *opponent_bits);
+
+        *player_bits |= move_bit | flips;
+        *opponent_bits &= !flips;
+
+        Some(flips)
+    }
+
+    /// 合法手一覧を生成せずに、そのマスが合法手かどうかを判定する
+    ///
+    /// 空きマスで、かつ 1 つ以上の石を裏返せるときに合法手となる。
+    pub fn is_legal(&self, color: Color, pos: &Position) -> bool {
+        let move_bit = 1u64 << pos.to_index();
+        if (self.black | self.white) & move_bit != 0 {
+            return false;
+        }
+
+        let (player_bits, opponent_bits) = match color {
+            Color::Black => (self.black, self.white),
+            Color::White => (self.white, self.black),
+        };
+        get_flips_bits(move_bit, player_bits, opponent_bits) != 0
+    }
+
     pub fn from_board(board: &(dyn Board + Send)) -> Self {
         let mut bit_board = Self::new();
         for x in 0..BOARD_SIZE {
@@ -114,7 +281,40 @@ impl BitBoard {
     }
 }
 
+// temp_reversi_core::Bitboard と同じビット配置 (y * 8 + x) なので、マスクをそのまま変換できる
+impl From<&temp_reversi_core::Bitboard> for BitBoard {
+    fn from(board: &temp_reversi_core::Bitboard) -> Self {
+        let (black, white) = board.bits();
+        Self { black, white }
+    }
+}
+
+impl TryFrom<&BitBoard> for temp_reversi_core::Bitboard {
+    type Error = String;
+
+    // 黒と白が同じマスにある盤面は変換できない
+    fn try_from(board: &BitBoard) -> Result<Self, Self::Error> {
+        let overlap = board.black & board.white;
+        if overlap != 0 {
+            return Err(format!("黒と白の石が重なっています: {:#018x}", overlap));
+        }
+        Ok(temp_reversi_core::Bitboard::new(board.black, board.white))
+    }
+}
+
 impl Board for BitBoard {
+    fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.black.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.white.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: [u8; 16]) -> Self {
+        let (black, white) = split_bytes(bytes);
+        Self { black, white }
+    }
+
     fn cell_states(&self) -> [CellState; BOARD_SIZE * BOARD_SIZE] {
         let mut cells: [CellState; BOARD_SIZE * BOARD_SIZE] =
             [CellState::Empty; BOARD_SIZE * BOARD_SIZE];
@@ -167,65 +367,227 @@ impl Board for BitBoard {
     }
 
     fn make_move(&mut self, color: Color, pos: &Position) -> bool {
-        let move_bit = 1u64 << pos.to_index();
+        self.apply_move_bits(color, pos).is_some()
+    }
 
+    fn get_valid_moves(&self, color: Color) -> Vec<Position> {
         let (player_bits, opponent_bits) = match color {
-            Color::Black => (&mut self.black, &mut self.white),
-            Color::White => (&mut self.white, &mut self.black),
+            Color::Black => (self.black, self.white),
+            Color::White => (self.white, self.black),
         };
-        let valid_moves = get_valid_moves_bits(*player_bits, *opponent_bits);
+        positions_from_bits(get_valid_moves_bits(player_bits, opponent_bits))
+    }
+}
 
-        if valid_moves & move_bit == 0 {
-            // Invalid move
-            return false;
-        }
+#[cfg(test)]
+mod tests {
 
-        let flips = get_flips_bits(move_bit, *player_bits, *opponent_bits);
+    use super::*;
+    use crate::{array_board::ArrayBoard, Color, Position};
 
-        *player_bits |= move_bit | flips;
-        *opponent_bits &= !flips;
+    #[test]
+    fn test_temp_reversi_core_conversion_round_trip() {
+        let boards = [
+            BitBoard::init_board(),
+            BitBoard {
+                black: 0x8100000000000081,
+                white: 0x0042000000004200,
+            },
+            BitBoard {
+                black: 0x00000000000000ff,
+                white: 0xff00000000000000,
+            },
+            BitBoard {
+                black: 0x5555555555555555,
+                white: 0xaaaaaaaaaaaaaaaa,
+            },
+        ];
 
-        true
+        for board in boards {
+            let core = temp_reversi_core::Bitboard::try_from(&board).unwrap();
+            assert_eq!(BitBoard::from(&core), board);
+
+            // 座標ごとに石の色が一致することを確認
+            let (black, white) = core.bits();
+            for x in 0..BOARD_SIZE {
+                for y in 0..BOARD_SIZE {
+                    let pos = Position::new(x, y);
+                    let bit = temp_reversi_core::Position::from_u8(pos.to_index() as u8)
+                        .unwrap()
+                        .to_bit();
+                    let expected = match board.get_cell_state(&pos) {
+                        CellState::Disc(Color::Black) => (true, false),
+                        CellState::Disc(Color::White) => (false, true),
+                        CellState::Empty => (false, false),
+                    };
+                    assert_eq!((black & bit != 0, white & bit != 0), expected);
+                }
+            }
+        }
     }
 
-    fn get_valid_moves(&self, color: Color) -> Vec<Position> {
-        let (player_bits, opponent_bits) = match color {
-            Color::Black => (self.black, self.white),
-            Color::White => (self.white, self.black),
-        };
-        let mut bits = get_valid_moves_bits(player_bits, opponent_bits);
-        let mut moves = Vec::new();
-        while bits != 0 {
-            let lsb = bits & (!bits + 1); // 最下位の1ビットを取得
-            let index = lsb.trailing_zeros() as usize;
-            moves.push(Position::from_index(index));
-            bits &= bits - 1; // 最下位の1ビットをクリア
-        }
-        moves
-    }
-
-    fn display(&self) {
-        println!("  A B C D E F G H");
-        for y in 0..BOARD_SIZE as u8 {
-            print!("{}", y + 1);
-            for x in 0..BOARD_SIZE as u8 {
-                print!(" ");
-                match self.get_cell_state(&Position { x, y }) {
-                    CellState::Disc(Color::Black) => print!("B"), // 黒の駒
-                    CellState::Disc(Color::White) => print!("W"), // 白の駒
-                    CellState::Empty => print!("-"),              // 駒なし
+    #[test]
+    fn test_make_move_checked() {
+        let mut board = BitBoard::init_board();
+
+        // 初手 F5 は E5 の白石だけを裏返す
+        let flipped = board.make_move_checked(Color::Black, &Position::F5);
+        assert_eq!(flipped, Some(vec![Position::E5]));
+        assert_eq!(
+            board.get_cell_state(&Position::E5),
+            CellState::Disc(Color::Black)
+        );
+        assert_eq!(board.black_count(), 4);
+        assert_eq!(board.white_count(), 1);
+
+        // 非合法手では盤面が変わらない
+        let before = board.clone();
+        assert_eq!(board.make_move_checked(Color::White, &Position::A1), None);
+        assert_eq!(board, before);
+    }
+
+    #[test]
+    fn test_edge_flip_table_matches_general_flips() {
+        // 辺の行以外は、空きの場合と縦・斜めにも裏返る石がある場合を試す
+        let backgrounds = [(0u64, 0u64), (0x0000ff0000ff0000, 0x00ff00000000ff00)];
+
+        for y in [0, BOARD_SIZE - 1] {
+            let shift = y * BOARD_SIZE;
+            for occupancy in 0..LINE_OCCUPANCY_COUNT {
+                // 3 進数のインデックスから行の状態を復元する
+                let (mut player_line, mut opponent_line) = (0u64, 0u64);
+                let mut state = occupancy;
+                for x in 0..BOARD_SIZE {
+                    match state % 3 {
+                        1 => player_line |= 1 << x,
+                        2 => opponent_line |= 1 << x,
+                        _ => {}
+                    }
+                    state /= 3;
+                }
+
+                for (background_player, background_opponent) in backgrounds {
+                    let player_bits = background_player | (player_line << shift);
+                    let opponent_bits = background_opponent | (opponent_line << shift);
+                    for x in 0..BOARD_SIZE {
+                        let move_bit = 1u64 << (shift + x);
+                        if (player_bits | opponent_bits) & move_bit != 0 {
+                            continue;
+                        }
+                        assert_eq!(
+                            get_edge_flips_bits(move_bit, player_bits, opponent_bits),
+                            get_flips_bits_in_directions(
+                                move_bit,
+                                player_bits,
+                                opponent_bits,
+                                &Direction::DIRECTIONS
+                            )
+                        );
+                    }
                 }
             }
-            println!();
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    fn test_is_legal() {
+        let board = BitBoard::init_board();
+
+        // 初手の合法手
+        assert!(board.is_legal(Color::Black, &Position::F5));
+        assert!(board.is_legal(Color::White, &Position::D6));
+        // 石のあるマス
+        assert!(!board.is_legal(Color::Black, &Position::D4));
+        assert!(!board.is_legal(Color::Black, &Position::E4));
+        // 裏返せる石のない空きマス
+        assert!(!board.is_legal(Color::Black, &Position::A1));
+        assert!(!board.is_legal(Color::Black, &Position::F4));
+
+        // 全マスで合法手一覧と一致する
+        for color in [Color::Black, Color::White] {
+            let valid_moves = board.get_valid_moves(color);
+            for index in 0..BOARD_SIZE * BOARD_SIZE {
+                let pos = Position::from_index(index);
+                assert_eq!(board.is_legal(color, &pos), valid_moves.contains(&pos));
+            }
+        }
+    }
 
-    use super::*;
-    use crate::{Color, Position};
+    #[test]
+    fn test_render() {
+        let board = BitBoard::init_board();
+        let expected = "  A B C D E F G H\n\
+                        1 - - - - - - - -\n\
+                        2 - - - - - - - -\n\
+                        3 - - - - - - - -\n\
+                        4 - - - W B - - -\n\
+                        5 - - - B W - - -\n\
+                        6 - - - - - - - -\n\
+                        7 - - - - - - - -\n\
+                        8 - - - - - - - -\n";
+        assert_eq!(board.render(), expected);
+
+        let expected = "  A B C D E F G H\n\
+                        1 - - - - - - - -\n\
+                        2 - - - - - - - -\n\
+                        3 - - - * - - - -\n\
+                        4 - - * W B - - -\n\
+                        5 - - - B W * - -\n\
+                        6 - - - - * - - -\n\
+                        7 - - - - - - - -\n\
+                        8 - - - - - - - -\n";
+        assert_eq!(board.render_with_moves(Color::Black), expected);
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let boards = [
+            BitBoard::new(),
+            BitBoard::init_board(),
+            BitBoard {
+                black: 0x8100000000000081,
+                white: 0x0042000000004200,
+            },
+        ];
+
+        for board in boards {
+            let bytes = board.to_bytes();
+            assert_eq!(BitBoard::from_bytes(bytes), board);
+
+            // ArrayBoard と同じ並びになる
+            let mut array_board = ArrayBoard::default();
+            array_board.set_board_state(&board.board_state());
+            assert_eq!(array_board.to_bytes(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_resolves_overlap_to_black() {
+        let board = BitBoard {
+            black: 0x0000_0000_0000_0003,
+            white: 0x0000_0000_0000_0006,
+        };
+        let bytes = board.to_bytes();
+
+        let expected = BitBoard {
+            black: 0x0000_0000_0000_0003,
+            white: 0x0000_0000_0000_0004,
+        };
+        assert_eq!(BitBoard::from_bytes(bytes), expected);
+
+        // ArrayBoard も同じ盤面になる
+        let array_board = ArrayBoard::from_bytes(bytes);
+        assert_eq!(array_board.to_bytes(), expected.to_bytes());
+    }
+
+    #[test]
+    fn test_temp_reversi_core_conversion_rejects_overlap() {
+        let board = BitBoard {
+            black: 0x1,
+            white: 0x1,
+        };
+        assert!(temp_reversi_core::Bitboard::try_from(&board).is_err());
+    }
 
     #[test]
     fn test_get_valid_moves_all_directions() {
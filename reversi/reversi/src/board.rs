@@ -2,6 +2,19 @@ use crate::{BoardState, CellState, Color, Position};
 
 pub const BOARD_SIZE: usize = 8;
 
+/// `Board::to_bytes` の 16 バイトを黒と白のビットマスクに分ける
+///
+/// 黒と白が重なるマスは黒とし、白のビットを落とす。
+pub(crate) fn split_bytes(bytes: [u8; 16]) -> (u64, u64) {
+    let mut black = [0u8; 8];
+    let mut white = [0u8; 8];
+    black.copy_from_slice(&bytes[..8]);
+    white.copy_from_slice(&bytes[8..]);
+    let black = u64::from_le_bytes(black);
+    let white = u64::from_le_bytes(white);
+    (black, white & !black)
+}
+
 pub trait CloneAsBoard {
     fn clone_as_board(&self) -> Box<dyn Board + Send>;
 }
@@ -52,6 +65,50 @@ pub trait Board: CloneAsBoard + std::fmt::Debug {
         }
     }
 
+    /// 盤面を 16 バイトに詰める
+    ///
+    /// 前半 8 バイトが黒、後半 8 バイトが白のビットマスク (y * 8 + x) で、それぞれリトルエンディアン。
+    fn to_bytes(&self) -> [u8; 16] {
+        let mut black = 0u64;
+        let mut white = 0u64;
+        for (index, cell) in self.cell_states().iter().enumerate() {
+            match cell {
+                CellState::Disc(Color::Black) => black |= 1 << index,
+                CellState::Disc(Color::White) => white |= 1 << index,
+                CellState::Empty => {}
+            }
+        }
+
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&black.to_le_bytes());
+        bytes[8..].copy_from_slice(&white.to_le_bytes());
+        bytes
+    }
+
+    /// `to_bytes` で詰めた盤面を復元する
+    ///
+    /// 黒と白のビットが重なるマスは黒として扱う。
+    fn from_bytes(bytes: [u8; 16]) -> Self
+    where
+        Self: Sized + Default,
+    {
+        let (black, white) = split_bytes(bytes);
+        let mut board = Self::default();
+        board.clear();
+        for index in 0..BOARD_SIZE * BOARD_SIZE {
+            let bit = 1u64 << index;
+            let cell = if black & bit != 0 {
+                CellState::Disc(Color::Black)
+            } else if white & bit != 0 {
+                CellState::Disc(Color::White)
+            } else {
+                CellState::Empty
+            };
+            board.set_cell_state(&Position::from_index(index), cell);
+        }
+        board
+    }
+
     fn cell_states(&self) -> [CellState; BOARD_SIZE * BOARD_SIZE];
     fn get_cell_state(&self, pos: &Position) -> CellState;
     fn set_cell_state(&mut self, pos: &Position, cell: CellState);
@@ -74,5 +131,37 @@ pub trait Board: CloneAsBoard + std::fmt::Debug {
 
     fn get_valid_moves(&self, color: Color) -> Vec<Position>;
 
-    fn display(&self);
+    /// 座標付きの盤面を文字列にする（黒は B、白は W、空きは -）
+    fn render(&self) -> String {
+        render_cells(&self.cell_states(), &[])
+    }
+
+    /// `render` に加えて、`color` の合法手を * で示す
+    fn render_with_moves(&self, color: Color) -> String {
+        render_cells(&self.cell_states(), &self.get_valid_moves(color))
+    }
+
+    fn display(&self) {
+        print!("{}", self.render());
+    }
+}
+
+fn render_cells(cells: &[CellState; BOARD_SIZE * BOARD_SIZE], marked: &[Position]) -> String {
+    let mut text = String::from("  A B C D E F G H\n");
+    for y in 0..BOARD_SIZE {
+        text.push_str(&(y + 1).to_string());
+        for x in 0..BOARD_SIZE {
+            let index = y * BOARD_SIZE + x;
+            let mark = match cells[index] {
+                CellState::Disc(Color::Black) => 'B',
+                CellState::Disc(Color::White) => 'W',
+                CellState::Empty if marked.contains(&Position::from_index(index)) => '*',
+                CellState::Empty => '-',
+            };
+            text.push(' ');
+            text.push(mark);
+        }
+        text.push('\n');
+    }
+    text
 }
@@ -16,6 +16,12 @@ pub struct TrainingConfig {
     pub batch_size: usize,
     pub early_stopping: EarlyStoppingConfig,
     pub models_file: String,
+    /// エポックごとにモデルを書き出すかどうか（`models_file` に加えて保存する）
+    #[serde(default)]
+    pub export_each_epoch: bool,
+    /// パターンの 4 つの回転で重みを共有する特徴量で学習するかどうか
+    #[serde(default)]
+    pub shared_rotations: bool,
 }
 
 impl Default for TrainingConfig {
@@ -30,6 +36,8 @@ impl Default for TrainingConfig {
                 min_delta: 0.001,
             },
             models_file: "models.bin".to_string(),
+            export_each_epoch: false,
+            shared_rotations: false,
         }
     }
 }
@@ -40,6 +48,28 @@ pub struct GenDataConfig {
     pub num_games_for_valid: usize,
     pub train_file: String,
     pub valid_file: String,
+    /// 自己対局に使う学習済みモデル（未指定なら TestEvaluator で対局する）
+    #[serde(default)]
+    pub model_file: Option<String>,
+    /// `model_file` のモデルが 4 つの回転で重みを共有する特徴量で学習されたかどうか
+    ///
+    /// モデルを学習したときの `TrainingConfig::shared_rotations` と同じ値にする
+    #[serde(default)]
+    pub shared_rotations: bool,
+    /// 序盤のランダム手数の下限
+    #[serde(default = "default_min_random_moves")]
+    pub min_random_moves: usize,
+    /// 序盤のランダム手数の上限（この値を含む）。対局ごとに下限との間から選ばれる
+    #[serde(default = "default_max_random_moves")]
+    pub max_random_moves: usize,
+}
+
+fn default_min_random_moves() -> usize {
+    6
+}
+
+fn default_max_random_moves() -> usize {
+    9
 }
 
 impl Default for GenDataConfig {
@@ -49,6 +79,10 @@ impl Default for GenDataConfig {
             num_games_for_valid: 300,
             train_file: "train.bin".to_string(),
             valid_file: "valid.bin".to_string(),
+            model_file: None,
+            shared_rotations: false,
+            min_random_moves: default_min_random_moves(),
+            max_random_moves: default_max_random_moves(),
         }
     }
 }
@@ -115,6 +149,13 @@ impl Config {
         Path::new(&self.base_path).join(&self.gen_data.valid_file)
     }
 
+    pub fn gen_data_model_path(&self) -> Option<PathBuf> {
+        self.gen_data
+            .model_file
+            .as_ref()
+            .map(|file| Path::new(&self.base_path).join(file))
+    }
+
     pub fn validate(&self) -> Result<(), String> {
         if self.training.epochs == 0 {
             return Err("エポック数は0より大きくなければなりません。".to_string());
@@ -128,6 +169,9 @@ impl Config {
         if self.gen_data.num_games_for_valid == 0 {
             return Err("対局数は0より大きくなければなりません。".to_string());
         }
+        if self.gen_data.min_random_moves > self.gen_data.max_random_moves {
+            return Err("ランダム手数の下限が上限を超えています。".to_string());
+        }
         if !Path::new(&self.base_path).exists() {
             return Err(format!("base_path が存在しません: {}", self.base_path));
         }
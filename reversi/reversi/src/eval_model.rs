@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{path::Path, sync::Arc};
 
 use crate::{
     Ai, BitBoard, Config, Game, Negaalpha, ResultBoxErr, Searcher, TempuraEvaluator, TestEvaluator,
@@ -12,7 +12,7 @@ pub fn eval_model<P: AsRef<Path>>(config: P) -> ResultBoxErr<()> {
     let mut game = Game::initial();
 
     let mut ai = Ai {
-        searcher: Searcher::TempuraNegaalpha(Negaalpha::new(evaluator)),
+        searcher: Searcher::TempuraNegaalpha(Negaalpha::new(Arc::new(evaluator))),
         search_depth: 4,
     };
 
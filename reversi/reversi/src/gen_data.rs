@@ -8,39 +8,64 @@ use indicatif::ProgressBar;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use crate::{
-    ml::{self_play, GameRecord, SelfPlaySetting},
-    Config, ResultBoxErr,
+    ml::{self_play, EvaluatorType, GameRecord, SelfPlaySetting},
+    Config, GenDataConfig, ResultBoxErr,
 };
 
 pub fn gen_data(config: &str) -> ResultBoxErr<()> {
     let config = Config::from_file(config)?;
 
+    // モデルは対局ごとではなく、ここで一度だけ読み込む
+    let evaluator = match config.gen_data_model_path() {
+        Some(model_path) => {
+            EvaluatorType::load_tempura(model_path, config.gen_data.shared_rotations)?
+        }
+        None => EvaluatorType::Test,
+    };
+
     let output = config.gen_data_train_path();
     println!("Generating data for training...");
-    gen_data_impl(&output, config.gen_data.num_games_for_train)?;
+    gen_data_impl(
+        &output,
+        config.gen_data.num_games_for_train,
+        &config.gen_data,
+        &evaluator,
+    )?;
 
     let output = config.gen_data_valid_path();
     println!("Generating data for validation...");
-    gen_data_impl(&output, config.gen_data.num_games_for_valid)?;
+    gen_data_impl(
+        &output,
+        config.gen_data.num_games_for_valid,
+        &config.gen_data,
+        &evaluator,
+    )?;
 
     Ok(())
 }
 
-fn gen_data_impl(output: &PathBuf, num_games: usize) -> ResultBoxErr<()> {
+fn gen_data_impl(
+    output: &PathBuf,
+    num_games: usize,
+    config: &GenDataConfig,
+    evaluator: &EvaluatorType,
+) -> ResultBoxErr<()> {
     let pb = ProgressBar::new(num_games.try_into().unwrap());
     let records: Vec<GameRecord> = (0..num_games)
         .into_par_iter()
         .map(|_| {
             let setting = SelfPlaySetting {
-                max_random_moves: 10,
-                min_random_moves: 6,
+                max_random_moves: config.max_random_moves,
+                min_random_moves: config.min_random_moves,
+                evaluator: evaluator.clone(),
             };
-            let record = self_play(&setting);
+            // Box<dyn Error> はスレッド間で送れないので文字列にする
+            let record = self_play(&setting).map_err(|e| e.to_string());
             pb.inc(1);
 
             record
         })
-        .collect();
+        .collect::<Result<_, String>>()?;
 
     let path = Path::new(&output);
     if path.exists() {
@@ -246,5 +246,59 @@ impl std::fmt::Display for Position {
     }
 }
 
+impl std::str::FromStr for Position {
+    type Err = String;
+
+    /// Parses a position such as "A1" or "h8". The file letter is case-insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (Some(file), Some(rank), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(format!(
+                "Invalid position '{}': expected a file letter and a rank digit",
+                s
+            ));
+        };
+
+        let file = file.to_ascii_uppercase();
+        if !('A'..='H').contains(&file) {
+            return Err(format!("Invalid position '{}': file must be A-H", s));
+        }
+        if !('1'..='8').contains(&rank) {
+            return Err(format!("Invalid position '{}': rank must be 1-8", s));
+        }
+
+        Ok(Position {
+            x: file as u8 - b'A',
+            y: rank as u8 - b'1',
+        })
+    }
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_position_from_str_round_trip() {
+        for index in 0..BOARD_SIZE * BOARD_SIZE {
+            let pos = Position::from_index(index);
+            assert_eq!(Position::from_str(&pos.to_string()), Ok(pos));
+        }
+    }
+
+    #[test]
+    fn test_position_from_str() {
+        assert_eq!(Position::from_str("f5"), Ok(Position::F5));
+        assert_eq!(Position::from_str("H8"), Ok(Position::H8));
+
+        assert!(Position::from_str("").is_err());
+        assert!(Position::from_str("A").is_err());
+        assert!(Position::from_str("A10").is_err());
+        assert!(Position::from_str("I1").is_err());
+        assert!(Position::from_str("A0").is_err());
+        assert!(Position::from_str("A9").is_err());
+        assert!(Position::from_str("1A").is_err());
+    }
+}
@@ -4,7 +4,7 @@ use rand::{seq::SliceRandom, thread_rng};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    ml::GameRecord, BitBoard, Game, Position, ResultBoxErr, SparseVector, TempuraEvaluator,
+    ml::GameRecord, BitBoard, Color, Game, Position, ResultBoxErr, SparseVector, TempuraEvaluator,
 };
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -13,11 +13,40 @@ pub struct DataItem {
     pub target: f32,
 }
 
+/// 学習の目標値とする最終石差の視点
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScorePerspective {
+    /// 常に黒から見た石差（既存の学習はこちら）
+    #[default]
+    Black,
+    /// 各局面の手番の側から見た石差
+    SideToMove,
+}
+
+/// 棋譜の各局面を、黒から見た最終石差を目標値とするデータにする
 pub fn get_data_items_from_record(record: &GameRecord) -> Vec<DataItem> {
-    let evaluator = TempuraEvaluator::default();
+    get_data_items_from_record_with_perspective(record, ScorePerspective::Black)
+}
+
+/// 棋譜の各局面を、指定した視点の最終石差を目標値とするデータにする
+pub fn get_data_items_from_record_with_perspective(
+    record: &GameRecord,
+    perspective: ScorePerspective,
+) -> Vec<DataItem> {
+    get_data_items_from_record_with_evaluator(record, &TempuraEvaluator::default(), perspective)
+}
+
+/// 棋譜の各局面を、`evaluator` の特徴量と指定した視点の最終石差の組にする
+///
+/// 共有回転の特徴量で学習する場合は `with_shared_rotations(true)` の評価器を渡す
+pub fn get_data_items_from_record_with_evaluator(
+    record: &GameRecord,
+    evaluator: &TempuraEvaluator,
+    perspective: ScorePerspective,
+) -> Vec<DataItem> {
     let mut game = Game::initial();
     let mut data_items = vec![];
-    let target = record.black_score as f32 - record.white_score as f32;
+    let black_target = record.black_score as f32 - record.white_score as f32;
 
     for &mov in &record.moves {
         let player = game.current_player();
@@ -25,6 +54,10 @@ pub fn get_data_items_from_record(record: &GameRecord) -> Vec<DataItem> {
         let board = game.board();
         let bit_board = BitBoard::from_board(board);
         let feature = evaluator.feature(&bit_board);
+        let target = match (perspective, game.current_player()) {
+            (ScorePerspective::SideToMove, Color::White) => -black_target,
+            _ => black_target,
+        };
         data_items.push(DataItem { feature, target });
     }
 
@@ -66,6 +99,14 @@ pub struct Dataloader {
 }
 
 impl Dataloader {
+    pub fn new(records: Vec<GameRecord>, batch_size: usize) -> Self {
+        Self {
+            records,
+            batch_size,
+            current_index: 0,
+        }
+    }
+
     pub fn from_data_file<P: AsRef<Path>>(
         data_file_path: P,
         batch_size: usize,
@@ -162,4 +203,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_side_to_move_perspective() {
+        let record = GameRecord {
+            moves: [Position::F5, Position::D6, Position::C3]
+                .iter()
+                .map(|p| p.to_index() as u8)
+                .collect(),
+            black_score: 40,
+            white_score: 24,
+            ..Default::default()
+        };
+
+        let black = get_data_items_from_record_with_perspective(&record, ScorePerspective::Black);
+        let side_to_move =
+            get_data_items_from_record_with_perspective(&record, ScorePerspective::SideToMove);
+        assert_eq!(black.len(), 3);
+        assert!(black.iter().all(|item| item.target == 16.0));
+
+        // F5 の後は白番、D6 の後は黒番
+        assert_eq!(side_to_move[0].target, -black[0].target);
+        assert_eq!(side_to_move[1].target, black[1].target);
+        assert_eq!(side_to_move[2].target, -black[2].target);
+    }
 }
@@ -1,16 +1,22 @@
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
 use derive_builder::Builder;
 use indicatif::ProgressBar;
-use rayon::iter::{
-    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
-};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 
-use crate::{ResultBoxErr, SparseVector};
+use crate::{ResultBoxErr, SparseVector, TempuraEvaluator};
 
 use super::{
-    dataloader::Dataloader, get_data_items_from_record, loss_function::LossFunction,
-    lr_scheduler::LrScheduler, optimizer::Optimizer, transpose, DataItem, GameRecord, Model,
-    ModelInput,
+    dataloader::{get_data_items_from_record_with_evaluator, Dataloader, ScorePerspective},
+    loss_function::LossFunction,
+    lr_scheduler::LrScheduler,
+    optimizer::Optimizer,
+    DataItem, GameRecord, Model, ModelInput,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +25,57 @@ pub struct EarlyStoppingConfig {
     pub min_delta: f32,
 }
 
+/// チェックポイントの保存設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointConfig {
+    /// 保存先のファイルパス
+    pub path: String,
+    /// 何エポックごとに保存するか
+    pub interval: usize,
+}
+
+/// エポックごとに書き出すモデルのファイルパスを返す
+///
+/// `models.bin` の5エポック目なら `models.e05.bin` になる
+pub fn epoch_model_path<P: AsRef<Path>>(file_path: P, epoch: usize) -> PathBuf {
+    let file_path = file_path.as_ref();
+    let stem = file_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let file_name = match file_path.extension() {
+        Some(extension) => format!("{}.e{:02}.{}", stem, epoch, extension.to_string_lossy()),
+        None => format!("{}.e{:02}", stem, epoch),
+    };
+    file_path.with_file_name(file_name)
+}
+
+/// 学習を途中から再開するために保存する状態
+#[derive(Serialize, Deserialize)]
+struct Checkpoint<O, S> {
+    /// 完了したエポック数
+    epoch: usize,
+    model: Model,
+    optimizer: O,
+    lr_scheduler: Option<S>,
+    best_loss: f32,
+    patience_counter: usize,
+    best_model: Option<Model>,
+    last_loss: f32,
+}
+
+/// フェーズ別の損失を集計するときの1区間あたりのフェーズ数
+pub const PHASE_BUCKET_WIDTH: usize = 10;
+
+/// 検証データに対する評価結果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValidationMetrics {
+    /// 損失関数の値
+    pub loss: f32,
+    /// 平均絶対誤差（石差の単位）
+    pub mae: f32,
+}
+
 #[derive(Debug, Builder)]
 pub struct Learner<O, S, L>
 where
@@ -42,12 +99,38 @@ where
     #[builder(default = "None")]
     early_stopping: Option<EarlyStoppingConfig>,
 
+    /// 勾配のL2ノルムの上限（超えた場合は勾配を縮小する）
+    #[builder(default = "None")]
+    grad_clip_norm: Option<f32>,
+
+    /// 指定した場合、一定エポックごとにチェックポイントを保存する
+    #[builder(default = "None")]
+    checkpoint: Option<CheckpointConfig>,
+
+    /// パターンの 4 つの回転で重みを共有する特徴量で学習するかどうか
+    ///
+    /// 学習したモデルを使う `TempuraEvaluator::with_shared_rotations` と同じ設定にする
+    #[builder(default)]
+    shared_rotations: bool,
+
+    /// 指定した場合、エポックごとにモデルを `epoch_model_path` のパスへ書き出す
+    #[builder(default = "None")]
+    epoch_export_path: Option<PathBuf>,
+
+    /// 学習を開始するエポック（チェックポイントから再開した場合に0以外になる）
+    #[builder(default, setter(skip))]
+    start_epoch: usize,
+
     #[builder(default, setter(skip))]
     best_loss: f32,
 
     #[builder(default, setter(skip))]
     patience_counter: usize,
 
+    /// 検証損失が最も小さかった時点のモデル
+    #[builder(default, setter(skip))]
+    best_model: Option<Model>,
+
     #[builder(default, setter(skip))]
     pub last_loss: f32,
 }
@@ -59,18 +142,27 @@ where
     L: LossFunction,
 {
     pub fn fit(&mut self, progress_bar: &ProgressBar) -> ResultBoxErr<()> {
-        self.best_loss = f32::MAX;
+        // 再開時はチェックポイントから復元した状態を引き継ぐ
+        if self.start_epoch == 0 {
+            self.best_loss = f32::MAX;
+            self.best_model = None;
+            self.patience_counter = 0;
+        }
+        progress_bar.set_position(self.start_epoch as u64);
 
-        for _epoch in 0..self.num_epochs {
+        let evaluator = self.feature_evaluator();
+        for epoch in self.start_epoch..self.num_epochs {
             self.train_dataloader.reset()?;
 
             let mut losses = Vec::new();
             for batch in self.train_dataloader.iter_batches() {
                 let loss = train_single_batch(
                     &mut self.model,
+                    &evaluator,
                     &mut self.optimizer,
                     &self.loss_function,
                     batch,
+                    self.grad_clip_norm,
                 );
                 losses.push(loss);
             }
@@ -82,18 +174,15 @@ where
             self.last_loss = loss_avarage;
 
             if let Some(valid_loader) = &self.valid_dataloader {
-                let validation_loss = self.evaluate(valid_loader)?;
-
-                if let Some(early_stop_config) = &self.early_stopping {
-                    if validation_loss + early_stop_config.min_delta < self.best_loss {
-                        self.best_loss = validation_loss;
-                        self.patience_counter = 0;
-                    } else {
-                        self.patience_counter += 1;
-                        if self.patience_counter >= early_stop_config.patience {
-                            return Ok(());
-                        }
-                    }
+                let metrics = self.evaluate_metrics(valid_loader)?;
+                progress_bar.set_message(format!(
+                    "Loss:{loss_avarage:0.2}({dif:0.2}) Valid:{:0.2} MAE:{:0.2}",
+                    metrics.loss, metrics.mae
+                ));
+
+                if self.record_validation_loss(metrics.loss) {
+                    self.restore_best_model();
+                    return Ok(());
                 }
             }
 
@@ -101,51 +190,274 @@ where
             //     lr_scheduler.step(&mut self.optimizer);
             // }
 
+            if let Some(path) = &self.epoch_export_path {
+                Model::save_model(&self.model, epoch_model_path(path, epoch + 1))?;
+            }
+
+            if let Some(config) = &self.checkpoint {
+                if (epoch + 1) % config.interval == 0 {
+                    self.save_checkpoint(&config.path, epoch + 1)?;
+                }
+            }
+
             progress_bar.inc(1);
         }
 
         progress_bar.finish();
+        self.restore_best_model();
+
+        Ok(())
+    }
+
+    /// `epoch` エポック完了時点の学習状態をファイルに保存する
+    ///
+    /// モデルに加えてオプティマイザと学習率スケジューラの状態も保存するので、
+    /// `resume_from` で再開すると中断しなかった場合と同じように学習が続く
+    pub fn save_checkpoint<P: AsRef<Path>>(&self, file_path: P, epoch: usize) -> ResultBoxErr<()> {
+        let checkpoint = Checkpoint {
+            epoch,
+            model: self.model.clone(),
+            optimizer: self.optimizer.clone(),
+            lr_scheduler: self.lr_scheduler.clone(),
+            best_loss: self.best_loss,
+            patience_counter: self.patience_counter,
+            best_model: self.best_model.clone(),
+            last_loss: self.last_loss,
+        };
+
+        let mut file = File::create(file_path)?;
+        let serialized = bincode::serialize(&checkpoint)?;
+        file.write_all(&serialized)?;
+        file.flush()?;
+        Ok(())
+    }
 
+    /// チェックポイントから学習状態を復元する
+    ///
+    /// 次の `fit` は保存されたエポックの続きから学習する
+    pub fn resume_from<P: AsRef<Path>>(&mut self, file_path: P) -> ResultBoxErr<()> {
+        let mut file = File::open(file_path)?;
+        let mut buf = vec![];
+        file.read_to_end(&mut buf)?;
+        let checkpoint: Checkpoint<O, S> = bincode::deserialize(&buf)?;
+
+        self.start_epoch = checkpoint.epoch;
+        self.model = checkpoint.model;
+        self.optimizer = checkpoint.optimizer;
+        self.lr_scheduler = checkpoint.lr_scheduler;
+        self.best_loss = checkpoint.best_loss;
+        self.patience_counter = checkpoint.patience_counter;
+        self.best_model = checkpoint.best_model;
+        self.last_loss = checkpoint.last_loss;
         Ok(())
     }
 
+    /// 検証損失が最も小さかった時点のモデルを返す
+    pub fn best_model(&self) -> Option<&Model> {
+        self.best_model.as_ref()
+    }
+
+    /// 検証損失を記録し、改善していればモデルを保存する
+    ///
+    /// 早期終了すべき場合は `true` を返す
+    fn record_validation_loss(&mut self, validation_loss: f32) -> bool {
+        let min_delta = self
+            .early_stopping
+            .as_ref()
+            .map_or(0.0, |config| config.min_delta);
+
+        if validation_loss + min_delta < self.best_loss {
+            self.best_loss = validation_loss;
+            self.best_model = Some(self.model.clone());
+            self.patience_counter = 0;
+            return false;
+        }
+
+        match &self.early_stopping {
+            Some(config) => {
+                self.patience_counter += 1;
+                self.patience_counter >= config.patience
+            }
+            None => false,
+        }
+    }
+
+    /// 学習データの特徴量を作る評価器
+    fn feature_evaluator(&self) -> TempuraEvaluator {
+        TempuraEvaluator::default().with_shared_rotations(self.shared_rotations)
+    }
+
+    /// 保存しておいた最良のモデルに戻す
+    fn restore_best_model(&mut self) {
+        if let Some(best_model) = &self.best_model {
+            self.model = best_model.clone();
+        }
+    }
+
     pub fn evaluate(&self, dataloader: &Dataloader) -> ResultBoxErr<f32> {
+        Ok(self.evaluate_metrics(dataloader)?.loss)
+    }
+
+    /// 検証データの損失をフェーズの区間（`PHASE_BUCKET_WIDTH` 手ごと）別に計算する
+    ///
+    /// 序盤・中盤・終盤のどこで誤差が大きいかを確認するために使う
+    pub fn evaluate_by_phase(&self, dataloader: &Dataloader) -> ResultBoxErr<Vec<f32>> {
+        let evaluator = self.feature_evaluator();
+        let samples: Vec<(usize, f32, f32)> = dataloader
+            .iter_batches()
+            .flat_map(|batch| self.predict_batch(&evaluator, batch))
+            .collect();
+
+        Ok(phase_bucket_losses(
+            &self.loss_function,
+            &samples,
+            PHASE_BUCKET_WIDTH,
+        ))
+    }
+
+    /// 損失と平均絶対誤差を計算する
+    pub fn evaluate_metrics(&self, dataloader: &Dataloader) -> ResultBoxErr<ValidationMetrics> {
         let mut total_loss = 0.0;
+        let mut total_absolute_error = 0.0;
         let mut count = 0.0;
 
+        let evaluator = self.feature_evaluator();
         for batch in dataloader.iter_batches() {
-            let features: Vec<SparseVector> =
-                batch.iter().map(|item| item.feature.clone()).collect();
-            let targets: Vec<f32> = batch.iter().map(|item| item.target).collect();
-            let predictions = self.model.forward(&features);
+            let (predictions, targets): (Vec<f32>, Vec<f32>) = self
+                .predict_batch(&evaluator, batch)
+                .into_iter()
+                .map(|(_, prediction, target)| (prediction, target))
+                .unzip();
+            if predictions.is_empty() {
+                continue;
+            }
             let loss = self.loss_function.compute(&predictions, targets.as_slice());
 
             total_loss += loss.value * predictions.len() as f32;
+            total_absolute_error +=
+                mean_absolute_error(&predictions, &targets) * predictions.len() as f32;
             count += predictions.len() as f32;
         }
 
-        Ok(total_loss / count)
+        Ok(ValidationMetrics {
+            loss: total_loss / count,
+            mae: total_absolute_error / count,
+        })
+    }
+
+    /// 棋譜の各局面を予測し、(フェーズ, 予測値, 目標値) の組を返す
+    fn predict_batch(
+        &self,
+        evaluator: &TempuraEvaluator,
+        records: &[GameRecord],
+    ) -> Vec<(usize, f32, f32)> {
+        let items: Vec<(usize, DataItem)> = records
+            .iter()
+            .flat_map(|record| {
+                get_data_items_from_record_with_evaluator(
+                    record,
+                    evaluator,
+                    ScorePerspective::Black,
+                )
+                .into_iter()
+                .enumerate()
+            })
+            .collect();
+        let inputs: Vec<ModelInput> = items
+            .iter()
+            .map(|(phase, item)| ModelInput {
+                phase: *phase,
+                feature: item.feature.clone(),
+            })
+            .collect();
+        let predictions = self.model.forward(&inputs);
+
+        items
+            .iter()
+            .zip(predictions)
+            .map(|((phase, item), prediction)| (*phase, prediction, item.target))
+            .collect()
     }
 }
 
-fn compute_gradients(grad_outputs: &[f32], features: &[SparseVector]) -> SparseVector {
-    let mut grad_weights = grad_outputs
+/// フェーズ（手数）ごとの区間に分けて損失を計算する
+///
+/// `samples` は (フェーズ, 予測値, 目標値) の組。サンプルがない区間は NaN になる
+fn phase_bucket_losses<L: LossFunction>(
+    loss_function: &L,
+    samples: &[(usize, f32, f32)],
+    bucket_width: usize,
+) -> Vec<f32> {
+    let bucket_count = 60_usize.div_ceil(bucket_width);
+    let mut buckets: Vec<(Vec<f32>, Vec<f32>)> = vec![(vec![], vec![]); bucket_count];
+    for &(phase, prediction, target) in samples {
+        let bucket = (phase / bucket_width).min(bucket_count - 1);
+        buckets[bucket].0.push(prediction);
+        buckets[bucket].1.push(target);
+    }
+
+    buckets
+        .into_iter()
+        .map(|(predictions, targets)| {
+            if predictions.is_empty() {
+                f32::NAN
+            } else {
+                loss_function.compute(&predictions, &targets).value
+            }
+        })
+        .collect()
+}
+
+/// 予測値と目標値の平均絶対誤差
+fn mean_absolute_error(predictions: &[f32], targets: &[f32]) -> f32 {
+    if predictions.is_empty() {
+        return 0.0;
+    }
+
+    let total: f32 = predictions
         .iter()
-        .zip(features.iter())
-        .map(|(&grad_output, feature)| feature.clone() * grad_output)
-        .reduce(|g1, g2| g1 + g2)
-        .unwrap();
+        .zip(targets)
+        .map(|(p, t)| (p - t).abs())
+        .sum();
+    total / predictions.len() as f32
+}
+
+fn compute_gradients(grad_outputs: &[f32], features: &[SparseVector]) -> SparseVector {
+    let mut grad_weights = SparseVector::new(vec![], vec![], features[0].len()).unwrap();
+    let mut scaled = SparseVector::default();
+
+    for (&grad_output, feature) in grad_outputs.iter().zip(features.iter()) {
+        scaled.clone_from(feature);
+        scaled.scale_in_place(grad_output);
+        grad_weights += &scaled;
+    }
 
-    grad_weights = grad_weights / grad_outputs.len() as f32;
+    grad_weights.scale_in_place(1.0 / grad_outputs.len() as f32);
 
     grad_weights
 }
 
+/// 勾配のL2ノルムが `max_norm` を超えていれば `max_norm` になるよう縮小する
+fn clip_gradients(grads: SparseVector, max_norm: Option<f32>) -> SparseVector {
+    let Some(max_norm) = max_norm else {
+        return grads;
+    };
+
+    let norm = grads.values().iter().map(|g| g * g).sum::<f32>().sqrt();
+    if norm > max_norm {
+        grads * (max_norm / norm)
+    } else {
+        grads
+    }
+}
+
 fn train_single_batch<O, L>(
     model: &mut Model,
+    evaluator: &TempuraEvaluator,
     optimizer: &mut O,
     loss_function: &L,
     records: &[GameRecord],
+    grad_clip_norm: Option<f32>,
 ) -> f32
 where
     O: Optimizer,
@@ -153,53 +465,343 @@ where
 {
     let items_by_record: Vec<Vec<DataItem>> = records
         .par_iter()
-        .map(|record| get_data_items_from_record(record))
+        .map(|record| {
+            get_data_items_from_record_with_evaluator(record, evaluator, ScorePerspective::Black)
+        })
         .collect();
-    let items_by_phase = transpose(items_by_record);
 
-    items_by_phase
-        .into_iter()
-        .enumerate()
-        .for_each(|(phase, items)| {
-            let (features, targets): (Vec<SparseVector>, Vec<f32>) =
-                items.into_iter().map(|i| (i.feature, i.target)).unzip();
+    // 終局までの手数は棋譜ごとに異なるので、手数ごとに局面をまとめる
+    let mut items_by_phase: Vec<Vec<DataItem>> = vec![];
+    for items in items_by_record {
+        for (phase, item) in items.into_iter().enumerate() {
+            if items_by_phase.len() <= phase {
+                items_by_phase.resize_with(phase + 1, Vec::new);
+            }
+            items_by_phase[phase].push(item);
+        }
+    }
 
-            let inputs: Vec<ModelInput> = features
-                .iter()
-                .map(|f| ModelInput {
-                    phase,
-                    feature: f.clone(),
-                })
-                .collect();
-            let predictions: Vec<f32> = model.forward(&inputs);
-            let loss = loss_function.compute(&predictions, &targets);
-            let grads = compute_gradients(&loss.grad, &features);
-            optimizer.step(&mut model.params, &grads);
-        });
-
-    model
-        .params
-        .into_par_iter()
-        .zip(items_by_phase)
-        .map(|(param, items)| {});
-
-    let features: Vec<SparseVector> = datas.iter().map(|d| d.feature.clone()).collect();
-    let targets: Vec<f32> = datas.iter().map(|d| d.target).collect();
-
-    let predictions: Vec<f32> = model.forward(&features);
-    let loss = loss_function.compute(&predictions, &targets);
-    let grads = compute_gradients(&loss.grad, &features);
-    optimizer.step(&mut model.params, &grads);
-
-    loss.value
+    let mut total_loss = 0.0;
+    let mut count = 0;
+    for (phase, items) in items_by_phase.into_iter().enumerate() {
+        if items.is_empty() || phase >= model.params.len() {
+            continue;
+        }
+
+        let (features, targets): (Vec<SparseVector>, Vec<f32>) =
+            items.into_iter().map(|i| (i.feature, i.target)).unzip();
+        let inputs: Vec<ModelInput> = features
+            .iter()
+            .map(|f| ModelInput {
+                phase,
+                feature: f.clone(),
+            })
+            .collect();
+
+        let predictions: Vec<f32> = model.forward(&inputs);
+        let loss = loss_function.compute(&predictions, &targets);
+        let grads = clip_gradients(compute_gradients(&loss.grad, &features), grad_clip_norm);
+        optimizer.step(&mut model.params[phase], &grads);
+
+        total_loss += loss.value * targets.len() as f32;
+        count += targets.len();
+    }
+
+    if count == 0 {
+        return 0.0;
+    }
+    total_loss / count as f32
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        ml::{Adam, Mse},
-        TempuraEvaluator,
+        ml::{Adam, Mse, Sgd, StepLr},
+        Position, TempuraEvaluator,
     };
 
     use super::*;
+
+    #[test]
+    fn test_phase_bucket_losses() {
+        // 序盤（誤差1）と終盤（誤差3）のサンプル
+        let samples = [
+            (2, 1.0, 0.0),
+            (5, -1.0, 0.0),
+            (55, 3.0, 0.0),
+            (59, 0.0, 3.0),
+        ];
+
+        let losses = phase_bucket_losses(&Mse::new(), &samples, 10);
+        assert_eq!(losses.len(), 6);
+        assert!((losses[0] - 1.0).abs() < 1e-6);
+        assert!((losses[5] - 9.0).abs() < 1e-6);
+        assert!(losses[1..5].iter().all(|loss| loss.is_nan()));
+    }
+
+    #[test]
+    fn test_mean_absolute_error() {
+        let predictions = [2.0, -1.0, 10.0];
+        let targets = [0.0, 1.0, 4.0];
+        // (2 + 2 + 6) / 3
+        assert!((mean_absolute_error(&predictions, &targets) - 10.0 / 3.0).abs() < 1e-6);
+
+        assert_eq!(mean_absolute_error(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn test_clip_gradients() {
+        let features = vec![
+            SparseVector::from(&[(0, 1.0), (2, 1.0)], 4).unwrap(),
+            SparseVector::from(&[(1, 1.0), (2, 1.0)], 4).unwrap(),
+        ];
+        // 極端な目標値による大きな勾配
+        let grads = compute_gradients(&[1000.0, -500.0], &features);
+        let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!(norm(grads.values()) > 1.0);
+
+        let clipped = clip_gradients(grads.clone(), Some(1.0));
+        assert!((norm(clipped.values()) - 1.0).abs() < 1e-5);
+
+        // 適用される更新量も上限で抑えられる
+        let learning_rate = 0.5;
+        let mut optimizer = Sgd::new(learning_rate);
+        let mut params = vec![0.0; 4];
+        optimizer.step(&mut params, &clipped);
+        assert!(norm(&params) <= learning_rate * 1.0 + 1e-5);
+
+        // 上限未満または未設定なら変化しない
+        let unclipped = clip_gradients(grads.clone(), Some(1e6));
+        assert_eq!(unclipped.values(), grads.values());
+        let unclipped = clip_gradients(grads.clone(), None);
+        assert_eq!(unclipped.values(), grads.values());
+    }
+
+    fn checkpoint_test_learner() -> Learner<Adam, StepLr, Mse> {
+        LearnerBuilder::default()
+            .model(Model {
+                params: vec![vec![0.5; 4]],
+            })
+            .train_dataloader(Dataloader::new(vec![], 1))
+            .optimizer(Adam::new(0.01, 0.9, 0.999, 1e-8))
+            .num_epochs(10)
+            .loss_function(Mse::new())
+            .lr_scheduler(Some(StepLr::new(2, 0.5)))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_epoch_model_path() {
+        assert_eq!(
+            epoch_model_path("data/models.bin", 5),
+            PathBuf::from("data/models.e05.bin")
+        );
+        assert_eq!(epoch_model_path("models", 12), PathBuf::from("models.e12"));
+    }
+
+    #[test]
+    fn test_export_model_each_epoch() -> ResultBoxErr<()> {
+        let path = std::env::temp_dir().join(format!(
+            "reversi_learner_export_test_{}.bin",
+            std::process::id()
+        ));
+        let mut learner = checkpoint_test_learner();
+        learner.num_epochs = 3;
+        learner.epoch_export_path = Some(path.clone());
+
+        learner.fit(&ProgressBar::hidden())?;
+
+        for epoch in 1..=3 {
+            let epoch_path = epoch_model_path(&path, epoch);
+            let model = Model::load_model(&epoch_path)?;
+            assert_eq!(model.params.len(), learner.model.params.len());
+            std::fs::remove_file(epoch_path)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() -> ResultBoxErr<()> {
+        let grads = SparseVector::from(&[(0, 0.5), (2, -0.3)], 4)?;
+        let path = std::env::temp_dir().join(format!(
+            "reversi_learner_checkpoint_test_{}.bin",
+            std::process::id()
+        ));
+
+        // k エポック分学習してからチェックポイントを保存する
+        let mut learner = checkpoint_test_learner();
+        for _ in 0..3 {
+            learner.optimizer.step(&mut learner.model.params[0], &grads);
+            learner
+                .lr_scheduler
+                .as_mut()
+                .unwrap()
+                .step(&mut learner.optimizer);
+        }
+        learner.save_checkpoint(&path, 3)?;
+
+        let mut resumed = checkpoint_test_learner();
+        resumed.resume_from(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(resumed.start_epoch, 3);
+        assert_eq!(
+            resumed.optimizer.get_learning_rate(),
+            learner.optimizer.get_learning_rate()
+        );
+
+        // k+1 エポック目の更新結果が中断しなかった場合と一致する
+        for learner in [&mut learner, &mut resumed] {
+            learner.optimizer.step(&mut learner.model.params[0], &grads);
+            learner
+                .lr_scheduler
+                .as_mut()
+                .unwrap()
+                .step(&mut learner.optimizer);
+        }
+        assert_eq!(resumed.model.params, learner.model.params);
+        assert_eq!(
+            resumed.optimizer.get_learning_rate(),
+            learner.optimizer.get_learning_rate()
+        );
+
+        Ok(())
+    }
+
+    /// 1 つの棋譜で学習する学習器
+    ///
+    /// 棋譜が 1 つならシャッフルしても学習の順序は変わらないので、結果が再現する
+    fn single_record_learner(
+        feature_size: usize,
+        num_epochs: usize,
+        checkpoint: Option<CheckpointConfig>,
+    ) -> Learner<Adam, StepLr, Mse> {
+        let record = GameRecord {
+            moves: [Position::F5, Position::D6, Position::C3]
+                .iter()
+                .map(|p| p.to_index() as u8)
+                .collect(),
+            black_score: 40,
+            white_score: 24,
+            ..Default::default()
+        };
+
+        LearnerBuilder::default()
+            .model(Model {
+                params: vec![vec![0.0; feature_size]; 3],
+            })
+            .train_dataloader(Dataloader::new(vec![record], 1))
+            .optimizer(Adam::new(0.01, 0.9, 0.999, 1e-8))
+            .num_epochs(num_epochs)
+            .loss_function(Mse::new())
+            .lr_scheduler(Some(StepLr::new(2, 0.5)))
+            .checkpoint(checkpoint)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fit_resumes_from_checkpoint() -> ResultBoxErr<()> {
+        let feature_size = TempuraEvaluator::default().feature_size();
+        let path = std::env::temp_dir().join(format!(
+            "reversi_learner_fit_resume_test_{}.bin",
+            std::process::id()
+        ));
+
+        let mut uninterrupted = single_record_learner(feature_size, 4, None);
+        uninterrupted.fit(&ProgressBar::hidden())?;
+
+        // 2 エポックで中断し、チェックポイントから 4 エポック目まで学習する
+        let mut interrupted = single_record_learner(
+            feature_size,
+            2,
+            Some(CheckpointConfig {
+                path: path.to_string_lossy().into_owned(),
+                interval: 2,
+            }),
+        );
+        interrupted.fit(&ProgressBar::hidden())?;
+
+        let mut resumed = single_record_learner(feature_size, 4, None);
+        resumed.resume_from(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(resumed.start_epoch, 2);
+
+        let progress_bar = ProgressBar::hidden();
+        resumed.fit(&progress_bar)?;
+        assert_eq!(progress_bar.position(), 4);
+
+        assert_eq!(resumed.model.params, uninterrupted.model.params);
+        assert_eq!(resumed.last_loss, uninterrupted.last_loss);
+        Ok(())
+    }
+
+    #[test]
+    fn test_shared_rotations_features() {
+        let record = |moves: [Position; 3]| GameRecord {
+            moves: moves.iter().map(|p| p.to_index() as u8).collect(),
+            black_score: 40,
+            white_score: 24,
+            ..Default::default()
+        };
+        // 2 つ目は 1 つ目を 180 度回転させた棋譜
+        let records = [
+            record([Position::F5, Position::D6, Position::C3]),
+            record([Position::C4, Position::E3, Position::F6]),
+        ];
+
+        let feature_size = TempuraEvaluator::default().feature_size();
+        let learner: Learner<Adam, StepLr, Mse> = LearnerBuilder::default()
+            .model(Model::new(feature_size))
+            .train_dataloader(Dataloader::new(vec![], 1))
+            .optimizer(Adam::new(0.001, 0.9, 0.999, 1e-8))
+            .num_epochs(1)
+            .loss_function(Mse::new())
+            .shared_rotations(true)
+            .build()
+            .unwrap();
+
+        // 回転で重みを共有するので、回転した局面は同じ予測値になる
+        let evaluator = learner.feature_evaluator();
+        assert!(evaluator.shared_rotations);
+        let predictions = learner.predict_batch(&evaluator, &records);
+        assert_eq!(predictions.len(), 6);
+        assert_eq!(predictions[..3], predictions[3..]);
+    }
+
+    #[test]
+    fn test_restore_best_model() {
+        let mut learner: Learner<Adam, StepLr, Mse> = LearnerBuilder::default()
+            .model(Model { params: vec![] })
+            .train_dataloader(Dataloader::new(vec![], 1))
+            .optimizer(Adam::new(0.001, 0.9, 0.999, 1e-8))
+            .num_epochs(10)
+            .loss_function(Mse::new())
+            .early_stopping(Some(EarlyStoppingConfig {
+                patience: 2,
+                min_delta: 0.0,
+            }))
+            .build()
+            .unwrap();
+        learner.best_loss = f32::MAX;
+
+        // 2エポック目が最良で、その後は悪化する
+        let epochs = [
+            (vec![1.0], 1.0),
+            (vec![2.0], 0.5),
+            (vec![3.0], 0.8),
+            (vec![4.0], 0.9),
+        ];
+        let mut stopped = false;
+        for (params, validation_loss) in epochs {
+            learner.model.params = vec![params];
+            stopped = learner.record_validation_loss(validation_loss);
+        }
+        assert!(stopped);
+
+        learner.restore_best_model();
+        assert_eq!(learner.model.params, vec![vec![2.0]]);
+        assert_eq!(learner.best_model().unwrap().params, vec![vec![2.0]]);
+    }
 }
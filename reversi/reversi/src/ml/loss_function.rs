@@ -1,7 +1,9 @@
 mod cross_entropy;
+mod huber;
 mod mse;
 
 pub use cross_entropy::*;
+pub use huber::*;
 pub use mse::*;
 
 pub trait LossFunction: Default + Clone {
@@ -0,0 +1,109 @@
+use super::{Loss, LossFunction};
+
+/// 外れ値に強い損失関数
+///
+/// 誤差の絶対値が `delta` 以下なら二乗誤差、それより大きければ線形になる
+#[derive(Debug, Clone)]
+pub struct Huber {
+    pub delta: f32,
+}
+
+impl Default for Huber {
+    fn default() -> Self {
+        Huber { delta: 1.0 }
+    }
+}
+
+impl Huber {
+    pub fn new(delta: f32) -> Self {
+        Huber { delta }
+    }
+}
+
+impl LossFunction for Huber {
+    fn compute(&self, preds: &[f32], targets: &[f32]) -> Loss {
+        assert_eq!(
+            preds.len(),
+            targets.len(),
+            "Outputs and targets must have the same length."
+        );
+        let len = preds.len() as f32;
+
+        let (losses, grads): (Vec<f32>, Vec<f32>) = preds
+            .iter()
+            .zip(targets.iter())
+            .map(|(&pred, &target)| {
+                let error = pred - target;
+                if error.abs() <= self.delta {
+                    (0.5 * error * error, error)
+                } else {
+                    (
+                        self.delta * (error.abs() - 0.5 * self.delta),
+                        self.delta * error.signum(),
+                    )
+                }
+            })
+            .unzip();
+
+        // 合計および平均の計算
+        let loss_value = losses.into_iter().sum::<f32>() / len;
+        let grad = grads.into_iter().map(|g| g / len).collect();
+
+        Loss {
+            value: loss_value,
+            grad,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_huber_loss() {
+        let huber = Huber::new(1.0);
+        let preds = vec![0.5, 3.0];
+        let targets = vec![0.0, 0.0];
+
+        let loss = huber.compute(&preds, &targets);
+
+        // (0.5 * 0.5^2 + 1.0 * (3.0 - 0.5)) / 2
+        assert!((loss.value - 1.3125).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_huber_gradient_matches_numerical() {
+        let huber = Huber::new(1.0);
+        // delta の内側と外側、負の誤差を含む
+        let preds = vec![0.3, -0.8, 2.5, -4.0];
+        let targets = vec![0.0, 0.0, 0.0, 0.0];
+        let h = 1e-3;
+
+        let loss = huber.compute(&preds, &targets);
+        for i in 0..preds.len() {
+            let mut plus = preds.clone();
+            plus[i] += h;
+            let mut minus = preds.clone();
+            minus[i] -= h;
+
+            let numerical = (huber.compute(&plus, &targets).value
+                - huber.compute(&minus, &targets).value)
+                / (2.0 * h);
+            assert!(
+                (loss.grad[i] - numerical).abs() < 1e-3,
+                "index {}: analytical {} numerical {}",
+                i,
+                loss.grad[i],
+                numerical
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Outputs and targets must have the same length.")]
+    fn test_huber_length_mismatch() {
+        let huber = Huber::default();
+        huber.compute(&[0.0], &[0.0, 1.0]);
+    }
+}
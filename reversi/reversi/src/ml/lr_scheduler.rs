@@ -4,8 +4,11 @@ mod step_lr;
 pub use exponential_lr::ExponentialLr;
 pub use step_lr::StepLr;
 
+use serde::{de::DeserializeOwned, Serialize};
+
 use super::optimizer::Optimizer;
 
-pub trait LrScheduler: Clone {
+// チェックポイントに現在のステップ数を保存できるようシリアライズ可能にする
+pub trait LrScheduler: Clone + Serialize + DeserializeOwned {
     fn step(&mut self, optimizer: &mut impl Optimizer);
 }
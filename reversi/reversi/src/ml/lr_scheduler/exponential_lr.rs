@@ -1,8 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 use crate::ml::optimizer::Optimizer;
 
 use super::LrScheduler;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExponentialLr {
     gamma: f32, // 減衰率
     current_step: usize,
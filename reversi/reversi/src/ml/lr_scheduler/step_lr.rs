@@ -1,8 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 use crate::ml::optimizer::Optimizer;
 
 use super::LrScheduler;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StepLr {
     step_size: usize, // 学習率を減衰させるステップ数
     gamma: f32,       // 減衰率
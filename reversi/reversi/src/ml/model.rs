@@ -4,10 +4,59 @@ use std::{
     path::Path,
 };
 
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{sparse_vector::SparseVector, ResultBoxErr};
 
+/// この件数以上のバッチは並列に推論する
+const PARALLEL_FORWARD_THRESHOLD: usize = 64;
+
+/// モデルファイルの先頭に書き込むマジックナンバー
+const MODEL_FILE_MAGIC: [u8; 4] = *b"TRVM";
+
+/// モデルファイルのフォーマットバージョン（ヘッダなしの旧形式が 1）
+const MODEL_FORMAT_VERSION: u32 = 2;
+
+/// モデルファイルのヘッダ
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ModelFileHeader {
+    magic: [u8; 4],
+    version: u32,
+    phase_count: u32,
+    feature_size: u32,
+}
+
+impl ModelFileHeader {
+    fn new(model: &Model) -> Self {
+        Self {
+            magic: MODEL_FILE_MAGIC,
+            version: MODEL_FORMAT_VERSION,
+            phase_count: model.params.len() as u32,
+            feature_size: model.params.first().map_or(0, |p| p.len()) as u32,
+        }
+    }
+
+    /// マジックナンバーとバージョンを検証する
+    fn validate(&self) -> ResultBoxErr<()> {
+        if self.magic != MODEL_FILE_MAGIC {
+            return Err(format!(
+                "モデルファイルではありません (マジックナンバー: {:?})",
+                self.magic
+            )
+            .into());
+        }
+        if self.version != MODEL_FORMAT_VERSION {
+            return Err(format!(
+                "未対応のモデルファイルのバージョンです (ファイル: {}, 対応: {})",
+                self.version, MODEL_FORMAT_VERSION
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Model {
     pub params: Vec<Vec<f32>>,
@@ -35,27 +84,105 @@ impl Model {
         let mut file = File::open(file_path)?;
         let mut buf = vec![];
         file.read_to_end(&mut buf)?;
-        let model: Self = bincode::deserialize(&buf)?;
 
-        Ok(model)
+        Self::read_from(buf.as_slice())
     }
 
     pub fn save_model<P: AsRef<Path>>(model: &Model, file_path: P) -> ResultBoxErr<()> {
+        let mut buf = vec![];
+        model.write_to(&mut buf)?;
+
         let mut file = File::create(file_path)?;
-        let serialized = bincode::serialize(model)?;
-        file.write_all(&serialized)?;
+        file.write_all(&buf)?;
         file.flush()?;
         Ok(())
     }
 
+    /// 拡張子が `.gz` なら gzip 圧縮されたファイルとして、それ以外は非圧縮で読み込む
+    pub fn load_model_auto<P: AsRef<Path>>(file_path: P) -> ResultBoxErr<Self> {
+        if !is_gzip_path(&file_path) {
+            return Self::load_model(file_path);
+        }
+
+        let decoder = GzDecoder::new(File::open(file_path)?);
+        Self::read_from(decoder)
+    }
+
+    /// 拡張子が `.gz` なら gzip 圧縮して、それ以外は非圧縮で保存する
+    pub fn save_model_auto<P: AsRef<Path>>(model: &Model, file_path: P) -> ResultBoxErr<()> {
+        if !is_gzip_path(&file_path) {
+            return Self::save_model(model, file_path);
+        }
+
+        let mut encoder = GzEncoder::new(File::create(file_path)?, Compression::default());
+        model.write_to(&mut encoder)?;
+        encoder.finish()?.flush()?;
+        Ok(())
+    }
+
+    /// ヘッダとパラメータを書き込む
+    fn write_to<W: Write>(&self, mut writer: W) -> ResultBoxErr<()> {
+        bincode::serialize_into(&mut writer, &ModelFileHeader::new(self))?;
+        bincode::serialize_into(&mut writer, self)?;
+        Ok(())
+    }
+
+    /// ヘッダを検証してからパラメータを読み込む
+    ///
+    /// 先頭がマジックナンバーでなければ、ヘッダのない旧形式 (バージョン 1) として読み込む。
+    fn read_from<R: Read>(mut reader: R) -> ResultBoxErr<Self> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        let mut reader = magic.as_slice().chain(reader);
+        if magic != MODEL_FILE_MAGIC {
+            return Ok(bincode::deserialize_from(reader)?);
+        }
+
+        let header: ModelFileHeader = bincode::deserialize_from(&mut reader)?;
+        header.validate()?;
+
+        let model: Self = bincode::deserialize_from(&mut reader)?;
+        let actual = ModelFileHeader::new(&model);
+        if (actual.phase_count, actual.feature_size) != (header.phase_count, header.feature_size) {
+            return Err(format!(
+                "モデルの次元がヘッダと一致しません (ヘッダ: {}x{}, 実際: {}x{})",
+                header.phase_count, header.feature_size, actual.phase_count, actual.feature_size
+            )
+            .into());
+        }
+
+        Ok(model)
+    }
+
     pub fn forward(&self, inputs: &[ModelInput]) -> Vec<f32> {
-        inputs
-            .par_iter()
-            .map(|input| input.feature.dot(&self.params[input.phase]).unwrap())
-            .collect()
+        if inputs.len() >= PARALLEL_FORWARD_THRESHOLD {
+            self.forward_parallel(inputs)
+        } else {
+            self.forward_sequential(inputs)
+        }
+    }
+
+    fn forward_sequential(&self, inputs: &[ModelInput]) -> Vec<f32> {
+        inputs.iter().map(|input| self.predict(input)).collect()
+    }
+
+    fn forward_parallel(&self, inputs: &[ModelInput]) -> Vec<f32> {
+        inputs.par_iter().map(|input| self.predict(input)).collect()
+    }
+
+    fn predict(&self, input: &ModelInput) -> f32 {
+        input.feature.dot(&self.params[input.phase]).unwrap()
     }
 }
 
+/// gzip 圧縮するファイルかどうかを拡張子で判定する
+fn is_gzip_path<P: AsRef<Path>>(file_path: P) -> bool {
+    file_path
+        .as_ref()
+        .extension()
+        .is_some_and(|extension| extension == "gz")
+}
+
 pub fn load_models<P: AsRef<Path>>(file_path: P) -> ResultBoxErr<Vec<Model>> {
     let mut file = File::open(file_path)?;
     let mut buf = vec![];
@@ -80,4 +207,110 @@ mod tests {
 
     #[test]
     fn test_forward() {}
+
+    #[test]
+    fn test_save_and_load_model_auto() -> ResultBoxErr<()> {
+        let model = Model::new(50);
+        let dir = std::env::temp_dir();
+
+        for extension in ["bin", "bin.gz"] {
+            let file_name = format!(
+                "reversi_model_auto_test_{}.{}",
+                std::process::id(),
+                extension
+            );
+            let path = dir.join(file_name);
+            Model::save_model_auto(&model, &path)?;
+
+            // .gz のときだけ gzip のマジックナンバーで始まる
+            let bytes = std::fs::read(&path)?;
+            assert_eq!(bytes.starts_with(&[0x1f, 0x8b]), is_gzip_path(&path));
+
+            let loaded = Model::load_model_auto(&path)?;
+            std::fs::remove_file(&path)?;
+            assert_eq!(loaded.params, model.params);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_model_rejects_invalid_header() -> ResultBoxErr<()> {
+        let model = Model::new(10);
+        let path = std::env::temp_dir().join(format!(
+            "reversi_model_header_test_{}.bin",
+            std::process::id()
+        ));
+
+        let mut wrong_magic = ModelFileHeader::new(&model);
+        wrong_magic.magic = *b"XXXX";
+        let mut wrong_version = ModelFileHeader::new(&model);
+        wrong_version.version = MODEL_FORMAT_VERSION + 1;
+        let mut wrong_size = ModelFileHeader::new(&model);
+        wrong_size.feature_size += 1;
+
+        for header in [wrong_magic, wrong_version, wrong_size] {
+            let mut buf = bincode::serialize(&header)?;
+            buf.extend(bincode::serialize(&model)?);
+            std::fs::write(&path, &buf)?;
+            assert!(Model::load_model(&path).is_err());
+        }
+
+        // 途中で切れたファイルもエラーになる
+        std::fs::write(&path, MODEL_FILE_MAGIC)?;
+        assert!(Model::load_model(&path).is_err());
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_legacy_model_without_header() -> ResultBoxErr<()> {
+        let model = Model::new(10);
+        let dir = std::env::temp_dir();
+
+        for extension in ["bin", "bin.gz"] {
+            let path = dir.join(format!(
+                "reversi_model_legacy_test_{}.{}",
+                std::process::id(),
+                extension
+            ));
+            if is_gzip_path(&path) {
+                let mut encoder = GzEncoder::new(File::create(&path)?, Compression::default());
+                bincode::serialize_into(&mut encoder, &model)?;
+                encoder.finish()?;
+            } else {
+                std::fs::write(&path, bincode::serialize(&model)?)?;
+            }
+
+            let loaded = Model::load_model_auto(&path)?;
+            std::fs::remove_file(&path)?;
+            assert_eq!(loaded.params, model.params);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_forward_parallel_matches_sequential() {
+        let feature_size = 100;
+        let model = Model::new(feature_size);
+        let inputs: Vec<ModelInput> = (0..PARALLEL_FORWARD_THRESHOLD * 2)
+            .map(|i| {
+                let elements: Vec<(usize, f32)> = (0..10)
+                    .map(|j| ((i * 7 + j * 13) % feature_size, 1.0 + j as f32 * 0.1))
+                    .collect();
+                ModelInput {
+                    phase: i % 60,
+                    feature: SparseVector::from(&elements, feature_size).unwrap(),
+                }
+            })
+            .collect();
+
+        let sequential = model.forward_sequential(&inputs);
+        let parallel = model.forward_parallel(&inputs);
+        let to_bits = |v: &[f32]| v.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+        assert_eq!(to_bits(&sequential), to_bits(&parallel));
+        assert_eq!(to_bits(&model.forward(&inputs)), to_bits(&sequential));
+    }
 }
@@ -4,9 +4,12 @@ mod sgd;
 pub use adam::*;
 pub use sgd::*;
 
+use serde::{de::DeserializeOwned, Serialize};
+
 use crate::SparseVector;
 
-pub trait Optimizer: Default + Clone {
+// チェックポイントに内部状態を保存できるようシリアライズ可能にする
+pub trait Optimizer: Default + Clone + Serialize + DeserializeOwned {
     fn step(&mut self, params: &mut [f32], grads: &SparseVector);
     fn set_learning_rate(&mut self, lr: f32);
     fn get_learning_rate(&self) -> f32;
@@ -1,15 +1,18 @@
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+
 use crate::sparse_vector::SparseVector;
 
 use super::Optimizer;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Adam {
     learning_rate: f32,
     beta1: f32,
     beta2: f32,
     epsilon: f32,
+    weight_decay: f32,
     m: HashMap<usize, f32>,
     v: HashMap<usize, f32>,
     t: usize,
@@ -22,16 +25,32 @@ impl Adam {
             beta1,
             beta2,
             epsilon,
+            weight_decay: 0.0,
             m: HashMap::new(),
             v: HashMap::new(),
             t: 0,
         }
     }
+
+    /// AdamW方式の重み減衰を設定する
+    ///
+    /// 減衰は勾配とは切り離して、毎ステップすべての重みに適用される
+    pub fn with_weight_decay(mut self, weight_decay: f32) -> Self {
+        self.weight_decay = weight_decay;
+        self
+    }
 }
 
 impl Optimizer for Adam {
     fn step(&mut self, params: &mut [f32], grads: &SparseVector) {
         self.t += 1;
+
+        // 重み減衰（勾配が疎でも全ての重みに適用する）
+        if self.weight_decay > 0.0 {
+            let decay = 1.0 - self.learning_rate * self.weight_decay;
+            params.iter_mut().for_each(|p| *p *= decay);
+        }
+
         grads.iter().for_each(|(i, g)| {
             // 第1モーメントの更新
             let m = self.m.entry(i).or_insert(0.0);
@@ -87,6 +106,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_adam_weight_decay() -> ResultBoxErr<()> {
+        let mut optimizer = Adam::new(0.01, 0.9, 0.999, 1e-8).with_weight_decay(0.1);
+        let mut params = vec![1.0, -2.0, 3.0];
+        let zero_grads = SparseVector::from(&[], 3)?;
+
+        let norm = |params: &[f32]| params.iter().map(|p| p * p).sum::<f32>().sqrt();
+        let mut last_norm = norm(&params);
+        for _ in 0..3 {
+            optimizer.step(&mut params, &zero_grads);
+            let current_norm = norm(&params);
+            assert!(current_norm < last_norm);
+            last_norm = current_norm;
+        }
+
+        // 減衰なしでは勾配ゼロのステップで重みは変化しない
+        let mut optimizer = Adam::new(0.01, 0.9, 0.999, 1e-8);
+        let mut params = vec![1.0, -2.0, 3.0];
+        optimizer.step(&mut params, &zero_grads);
+        assert_eq!(params, vec![1.0, -2.0, 3.0]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_adam_learning_rate() {
         let mut optimizer = Adam::new(0.001, 0.9, 0.999, 1e-8);
@@ -1,7 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use super::Optimizer;
 use crate::SparseVector;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Sgd {
     learning_rate: f32,
 }
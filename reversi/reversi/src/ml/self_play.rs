@@ -1,7 +1,11 @@
+use std::{path::Path, sync::Arc};
+
 use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 
-use crate::{Ai, BitBoard, Game, Negaalpha, Searcher, TestEvaluator};
+use crate::{
+    Ai, BitBoard, Game, Negaalpha, ResultBoxErr, Searcher, TempuraEvaluator, TestEvaluator,
+};
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub enum Winner {
@@ -19,18 +23,77 @@ pub struct GameRecord {
     pub white_score: u8,
 }
 
+/// 自己対局で使う評価関数の種類
+#[derive(Debug, Default, Clone)]
+pub enum EvaluatorType {
+    #[default]
+    Test,
+    /// 学習済みモデルを読み込んだ TempuraEvaluator
+    ///
+    /// 最新のモデルを指定すると、改善していくエージェント自身の対局からデータを作れる。
+    /// モデルは [`EvaluatorType::load_tempura`] で一度だけ読み込み、全対局の AI で共有する。
+    Tempura { evaluator: Arc<TempuraEvaluator> },
+}
+
+impl EvaluatorType {
+    /// 学習済みモデルを読み込んで `Tempura` を作る
+    ///
+    /// `shared_rotations` はモデルの学習時と同じ設定にする。
+    pub fn load_tempura<P: AsRef<Path>>(
+        model_path: P,
+        shared_rotations: bool,
+    ) -> ResultBoxErr<Self> {
+        let evaluator = TempuraEvaluator::load(model_path)?.with_shared_rotations(shared_rotations);
+        Ok(EvaluatorType::Tempura {
+            evaluator: Arc::new(evaluator),
+        })
+    }
+
+    /// この評価関数で探索する AI を作る
+    pub fn create_ai(&self, search_depth: u8) -> Ai {
+        let searcher = match self {
+            EvaluatorType::Test => {
+                Searcher::TestNegaalpha(Negaalpha::new(TestEvaluator::default()))
+            }
+            EvaluatorType::Tempura { evaluator } => {
+                Searcher::TempuraNegaalpha(Negaalpha::new(Arc::clone(evaluator)))
+            }
+        };
+        Ai {
+            searcher,
+            search_depth,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SelfPlaySetting {
     // black_ai_setting: AiSetting,
     // white_ai_setting: AiSetting,
+    /// 序盤のランダム手数の上限（この値を含む）
     pub max_random_moves: usize,
+    /// 序盤のランダム手数の下限
     pub min_random_moves: usize,
+    /// 両者が使う評価関数
+    pub evaluator: EvaluatorType,
 }
 
-pub fn self_play(setting: &SelfPlaySetting) -> GameRecord {
+impl SelfPlaySetting {
+    /// 1 局分のランダム手数を `min_random_moves..=max_random_moves` から一様に選ぶ
+    ///
+    /// 多様性が特定の手数に偏らないよう、対局ごとに手数を変える。
+    /// 下限と上限が同じなら常にその手数になる。
+    pub fn sample_random_moves<R: Rng>(&self, rng: &mut R) -> usize {
+        let max = self.max_random_moves.max(self.min_random_moves);
+        rng.gen_range(self.min_random_moves..=max)
+    }
+}
+
+/// ランダムな序盤のあと、`setting.evaluator` の AI 同士で 1 局対局する
+pub fn self_play(setting: &SelfPlaySetting) -> ResultBoxErr<GameRecord> {
     let mut rng = rand::thread_rng();
     let mut game = Game::initial();
-    let random_moves = rng.gen_range(setting.min_random_moves..setting.max_random_moves);
+    let random_moves = setting.sample_random_moves(&mut rng);
     for _ in 0..random_moves {
         if game.is_game_over() {
             break;
@@ -43,15 +106,8 @@ pub fn self_play(setting: &SelfPlaySetting) -> GameRecord {
         let _ = game.progress(current_player, *pos);
     }
 
-    let mut black_ai = Ai {
-        searcher: Searcher::TestNegaalpha(Negaalpha::new(TestEvaluator::default())),
-        search_depth: 4,
-    };
-
-    let mut white_ai = Ai {
-        searcher: Searcher::TestNegaalpha(Negaalpha::new(TestEvaluator::default())),
-        search_depth: 4,
-    };
+    let mut black_ai = setting.evaluator.create_ai(4);
+    let mut white_ai = setting.evaluator.create_ai(4);
 
     loop {
         if game.is_game_over() {
@@ -86,10 +142,100 @@ pub fn self_play(setting: &SelfPlaySetting) -> GameRecord {
         std::cmp::Ordering::Greater => Winner::Black,
     };
 
-    GameRecord {
+    Ok(GameRecord {
         moves,
         winner,
         black_score,
         white_score,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{ml::Model, Position};
+
+    #[test]
+    fn test_self_play_with_model() -> ResultBoxErr<()> {
+        let path = std::env::temp_dir().join(format!(
+            "reversi_self_play_model_test_{}.bin",
+            std::process::id()
+        ));
+        Model::save_model(&TempuraEvaluator::default().model, &path)?;
+        let evaluator = EvaluatorType::load_tempura(&path, false);
+        std::fs::remove_file(&path)?;
+
+        let setting = SelfPlaySetting {
+            max_random_moves: 9,
+            min_random_moves: 6,
+            evaluator: evaluator?,
+        };
+        let record = self_play(&setting)?;
+
+        // 棋譜を再生すると合法手だけで終局し、記録された石数と一致する
+        let mut game = Game::initial();
+        for &index in &record.moves {
+            game.progress(game.current_player(), Position::from_index(index as usize))?;
+        }
+        assert!(game.is_game_over());
+        assert_eq!(game.black_score() as u8, record.black_score);
+        assert_eq!(game.white_score() as u8, record.white_score);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sample_random_moves() {
+        let mut rng = rand::thread_rng();
+        let setting = SelfPlaySetting {
+            max_random_moves: 12,
+            min_random_moves: 4,
+            evaluator: EvaluatorType::Test,
+        };
+        let counts: HashSet<usize> = (0..500)
+            .map(|_| setting.sample_random_moves(&mut rng))
+            .collect();
+        assert!(counts.iter().all(|count| (4..=12).contains(count)));
+        assert!(counts.len() > 1);
+
+        // 下限と上限が同じなら固定の手数になる
+        let fixed = SelfPlaySetting {
+            max_random_moves: 8,
+            min_random_moves: 8,
+            evaluator: EvaluatorType::Test,
+        };
+        assert!((0..100).all(|_| fixed.sample_random_moves(&mut rng) == 8));
+    }
+
+    #[test]
+    fn test_load_tempura_shares_model() -> ResultBoxErr<()> {
+        let path = std::env::temp_dir().join(format!(
+            "reversi_shared_model_test_{}.bin",
+            std::process::id()
+        ));
+        Model::save_model(&TempuraEvaluator::default().model, &path)?;
+        let evaluator = EvaluatorType::load_tempura(&path, true);
+        std::fs::remove_file(&path)?;
+
+        let evaluator = evaluator?;
+        let EvaluatorType::Tempura { evaluator: loaded } = &evaluator else {
+            panic!("Tempura が読み込まれていません");
+        };
+        assert!(loaded.shared_rotations);
+
+        // AI を作ってもモデルは読み込み直さず、同じものを共有する
+        let _black_ai = evaluator.create_ai(4);
+        let _white_ai = evaluator.create_ai(4);
+        assert_eq!(Arc::strong_count(loaded), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_tempura_missing_model() {
+        let path = std::env::temp_dir().join("reversi_missing_model.bin");
+        assert!(EvaluatorType::load_tempura(path, false).is_err());
     }
 }
@@ -3,18 +3,27 @@ use iced::widget::canvas::{Cache, Frame, Geometry, Path, Program, Stroke, Text};
 use iced::{mouse, Color, Point, Rectangle, Size};
 use reversi::CellState;
 
-use crate::Message;
+use crate::{FlipAnimation, Message};
 
 const BOARD_SIZE: usize = 8;
 const MARGIN: f32 = 40.0;
 const LABEL_SIZE: f32 = 20.0;
 const CELL_STROKE_WIDTH: f32 = 2.0;
 const STONE_RADIUS_FACTOR: f32 = 1.0 / 3.0;
+const HINT_RADIUS_FACTOR: f32 = 1.0 / 10.0;
+const LAST_MOVE_RADIUS_FACTOR: f32 = 0.42;
+const LAST_MOVE_STROKE_WIDTH: f32 = 3.0;
 
 pub struct BoardView<'a> {
     pub board: reversi::BoardState,
     pub stones_cache: &'a Cache,
     pub is_clickable: bool,
+    /// 着手可能なマス（人間の手番のときにヒントとして表示する）
+    pub valid_moves: Vec<reversi::Position>,
+    /// 直前に着手されたマス
+    pub last_move: Option<reversi::Position>,
+    /// 再生中の裏返しアニメーション
+    pub flip_animation: Option<&'a FlipAnimation>,
 }
 
 #[derive(Default)]
@@ -45,7 +54,18 @@ impl<'a> Program<Message> for BoardView<'a> {
             self.draw_stones(frame, &layout);
         });
 
-        vec![background_geometry, stones_geometry]
+        // ヒントと直前の着手は手番やプレイヤー種別で変わるのでキャッシュしない
+        let mut overlay_frame = Frame::new(renderer, bounds.size());
+        self.draw_last_move(&mut overlay_frame, &layout);
+        if self.is_clickable {
+            self.draw_valid_move_hints(&mut overlay_frame, &layout);
+        }
+
+        vec![
+            background_geometry,
+            stones_geometry,
+            overlay_frame.into_geometry(),
+        ]
     }
 
     fn update(
@@ -75,6 +95,7 @@ impl<'a> Program<Message> for BoardView<'a> {
                                     y: row as u8,
                                 },
                                 request_id: -1,
+                                generation: 0,
                             }),
                         );
                     }
@@ -177,13 +198,25 @@ impl<'a> BoardView<'a> {
 
     fn draw_stones(&self, frame: &mut Frame, layout: &Layout) {
         for (i, cell) in self.board.cells.iter().enumerate() {
-            let color = match cell {
-                CellState::Disc(reversi::Color::Black) => Color::BLACK,
-                CellState::Disc(reversi::Color::White) => Color::WHITE,
+            let (color, previous_color) = match cell {
+                CellState::Disc(reversi::Color::Black) => (Color::BLACK, Color::WHITE),
+                CellState::Disc(reversi::Color::White) => (Color::WHITE, Color::BLACK),
                 CellState::Empty => continue,
             };
             let col = i % BOARD_SIZE;
             let row = i / BOARD_SIZE;
+
+            // 裏返ったばかりの石は元の色から徐々に変える
+            let color = match self.flip_animation {
+                Some(animation)
+                    if animation
+                        .positions
+                        .contains(&reversi::Position::new(col, row)) =>
+                {
+                    blend(previous_color, color, animation.progress())
+                }
+                _ => color,
+            };
             let x = layout.x_offset + col as f32 * layout.cell_size + layout.cell_size / 2.0;
             let y = layout.y_offset + row as f32 * layout.cell_size + layout.cell_size / 2.0;
             let radius = layout.cell_size * STONE_RADIUS_FACTOR;
@@ -192,6 +225,29 @@ impl<'a> BoardView<'a> {
         }
     }
 
+    fn draw_valid_move_hints(&self, frame: &mut Frame, layout: &Layout) {
+        for pos in &self.valid_moves {
+            let center = cell_center(pos, layout);
+            let radius = layout.cell_size * HINT_RADIUS_FACTOR;
+            let hint = Path::circle(center, radius);
+            frame.fill(&hint, Color::from_rgba(0.0, 0.0, 0.0, 0.4));
+        }
+    }
+
+    fn draw_last_move(&self, frame: &mut Frame, layout: &Layout) {
+        if let Some(pos) = &self.last_move {
+            let center = cell_center(pos, layout);
+            let radius = layout.cell_size * LAST_MOVE_RADIUS_FACTOR;
+            let ring = Path::circle(center, radius);
+            frame.stroke(
+                &ring,
+                Stroke::default()
+                    .with_color(Color::from_rgb(1.0, 0.3, 0.3))
+                    .with_width(LAST_MOVE_STROKE_WIDTH),
+            );
+        }
+    }
+
     fn get_cell_from_position(&self, position: Point, layout: &Layout) -> Option<(usize, usize)> {
         let relative_x = position.x - layout.x_offset;
         let relative_y = position.y - layout.y_offset;
@@ -211,3 +267,17 @@ impl<'a> BoardView<'a> {
         None
     }
 }
+
+fn cell_center(pos: &reversi::Position, layout: &Layout) -> Point {
+    let x = layout.x_offset + pos.x as f32 * layout.cell_size + layout.cell_size / 2.0;
+    let y = layout.y_offset + pos.y as f32 * layout.cell_size + layout.cell_size / 2.0;
+    Point::new(x, y)
+}
+
+fn blend(from: Color, to: Color, t: f32) -> Color {
+    Color::from_rgb(
+        from.r + (to.r - from.r) * t,
+        from.g + (to.g - from.g) * t,
+        from.b + (to.b - from.b) * t,
+    )
+}
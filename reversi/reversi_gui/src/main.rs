@@ -1,8 +1,12 @@
 mod board;
 
 use std::{
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
     thread,
+    time::Duration,
 };
 
 use board::BoardView;
@@ -12,7 +16,7 @@ use iced::{
     widget::{button, canvas, column, pick_list, row, text},
     Element, Length, Settings, Subscription, Task, Theme,
 };
-use reversi::{Ai, BitBoard, Board, BoardState, Game};
+use reversi::{Ai, BitBoard, Board, BoardState, Game, Negaalpha, Searcher, TempuraEvaluator};
 
 pub fn main() -> iced::Result {
     iced::application("Tempura Reversi", Reversi::update, Reversi::view)
@@ -35,6 +39,128 @@ pub enum PlayerType {
 impl PlayerType {
     pub const ALL: [PlayerType; 2] = [PlayerType::Human, PlayerType::Ai];
 }
+
+/// AIの強さ
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+
+    /// 探索の深さ
+    pub fn search_depth(&self) -> u8 {
+        match self {
+            Difficulty::Easy => 2,
+            Difficulty::Normal => 4,
+            Difficulty::Hard => 6,
+            Difficulty::Expert => 8,
+        }
+    }
+
+    /// パターンベースの評価関数を使うかどうか
+    pub fn uses_tempura_evaluator(&self) -> bool {
+        matches!(self, Difficulty::Hard | Difficulty::Expert)
+    }
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Difficulty::Easy => "Easy",
+                Difficulty::Normal => "Normal",
+                Difficulty::Hard => "Hard",
+                Difficulty::Expert => "Expert",
+            }
+        )
+    }
+}
+
+/// パターンベースの評価関数の学習済みモデル
+const TEMPURA_MODEL_FILE: &str = "models.bin";
+
+/// AI同士の対局で、次の着手を要求するまでの待ち時間
+const AUTO_PLAY_DELAY: Duration = Duration::from_millis(500);
+
+/// 裏返った石の色が変わりきるまでの時間
+pub const FLIP_ANIMATION_DURATION: Duration = Duration::from_millis(300);
+
+/// 裏返しアニメーションのフレーム数
+const FLIP_ANIMATION_FRAMES: u32 = 10;
+
+/// 直前の着手で裏返った石のアニメーション
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlipAnimation {
+    /// 裏返った石の位置
+    pub positions: Vec<reversi::Position>,
+    /// 経過したフレーム数
+    pub frame: u32,
+}
+
+impl FlipAnimation {
+    fn new(positions: Vec<reversi::Position>) -> Self {
+        Self {
+            positions,
+            frame: 0,
+        }
+    }
+
+    /// 色の変化の進み具合（0.0 で元の色、1.0 で裏返った後の色）
+    pub fn progress(&self) -> f32 {
+        (self.frame as f32 / FLIP_ANIMATION_FRAMES as f32).min(1.0)
+    }
+
+    /// 1 フレーム進める。アニメーションが終わったら `false` を返す
+    fn advance(&mut self) -> bool {
+        self.frame += 1;
+        self.frame < FLIP_ANIMATION_FRAMES
+    }
+}
+
+/// 現在の手番がAIかどうかを判定する
+fn is_ai_turn(
+    is_game_over: bool,
+    current_player: reversi::Color,
+    black_player_type: Option<PlayerType>,
+    white_player_type: Option<PlayerType>,
+) -> bool {
+    if is_game_over {
+        return false;
+    }
+
+    let player_type = match current_player {
+        reversi::Color::Black => black_player_type,
+        reversi::Color::White => white_player_type,
+    };
+    player_type == Some(PlayerType::Ai)
+}
+
+/// 両方のプレイヤーがAIかどうかを判定する
+fn is_ai_vs_ai(
+    black_player_type: Option<PlayerType>,
+    white_player_type: Option<PlayerType>,
+) -> bool {
+    black_player_type == Some(PlayerType::Ai) && white_player_type == Some(PlayerType::Ai)
+}
+
+/// 着手結果の世代が現在の世代より古いかどうかを判定する
+fn is_outdated_generation(result_generation: u64, current_generation: u64) -> bool {
+    result_generation != current_generation
+}
+
 impl std::fmt::Display for PlayerType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -54,15 +180,29 @@ struct Reversi {
     pub sender_to_ai_worker: Option<mpsc::Sender<Message>>,
     pub black_player_type: Option<PlayerType>,
     pub white_player_type: Option<PlayerType>,
+    pub black_difficulty: Option<Difficulty>,
+    pub white_difficulty: Option<Difficulty>,
     pub next_request_ai_move_id: i32,
     pub waiting_requests: Vec<AiMoveRequest>,
+    pub auto_play_delay: Duration,
+    pub is_auto_play_pending: bool,
+    pub last_move: Option<reversi::Position>,
+    /// AIへの要求の世代。これより古い世代の着手結果は反映しない
+    pub ai_generation: u64,
+    /// 現在の世代の要求に渡しているキャンセルトークン
+    pub ai_cancel_token: Arc<AtomicBool>,
+    /// 再生中の裏返しアニメーション
+    pub flip_animation: Option<FlipAnimation>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct AiMoveRequest {
     pub id: i32,
+    pub generation: u64,
     pub board: BoardState,
     pub player: reversi::Color,
+    pub difficulty: Difficulty,
+    pub cancel_token: Arc<AtomicBool>,
 }
 
 #[derive(Debug, Clone)]
@@ -72,10 +212,16 @@ enum Message {
     MoveMaked {
         pos: reversi::Position,
         request_id: i32,
+        generation: u64,
     },
-    Reset,
+    CancelAiMove,
+    ResetGame,
     BlackPlayerTypeChanged(PlayerType),
     WhitePlayerTypeChanged(PlayerType),
+    BlackDifficultyChanged(Difficulty),
+    WhiteDifficultyChanged(Difficulty),
+    Tick,
+    AnimationFrame,
 }
 
 impl Reversi {
@@ -87,8 +233,16 @@ impl Reversi {
                 sender_to_ai_worker: None,
                 black_player_type: Some(PlayerType::Human),
                 white_player_type: Some(PlayerType::Ai),
+                black_difficulty: Some(Difficulty::default()),
+                white_difficulty: Some(Difficulty::default()),
                 next_request_ai_move_id: 0,
                 waiting_requests: vec![],
+                auto_play_delay: AUTO_PLAY_DELAY,
+                is_auto_play_pending: false,
+                last_move: None,
+                ai_generation: 0,
+                ai_cancel_token: Arc::new(AtomicBool::new(false)),
+                flip_animation: None,
             },
             iced::widget::focus_next(),
         )
@@ -102,7 +256,11 @@ impl Reversi {
                 self.sender_to_ai_worker = Some(sender);
                 self.send_request_if_turn_is_ai();
             }
-            Message::MoveMaked { pos, request_id } => {
+            Message::MoveMaked {
+                pos,
+                request_id,
+                generation,
+            } => {
                 println!("[MoveMaked] move: ({}, {})", pos.x, pos.y);
                 if self.game.is_game_over() {
                     return;
@@ -110,6 +268,9 @@ impl Reversi {
 
                 if request_id < 0 {
                     // GUIからの着手なのでそのまま反映
+                } else if is_outdated_generation(generation, self.ai_generation) {
+                    // キャンセル前の局面に対する着手なので反映しない
+                    return;
                 } else if self.waiting_requests.iter().any(|req| req.id == request_id) {
                     // 応答待ちリクエストからの着手なので待ちリストから削除して反映
                     if let Some(index) = self
@@ -125,29 +286,66 @@ impl Reversi {
                 }
 
                 let player = self.game.current_player();
-                let _ = self.game.progress(player, pos);
+                let flipped =
+                    BitBoard::from_board(self.game.board()).make_move_checked(player, &pos);
+                if self.game.progress(player, pos).is_ok() {
+                    self.last_move = Some(pos);
+                    self.flip_animation = flipped.map(FlipAnimation::new);
+                }
                 self.stones_cache.clear();
-                self.send_request_if_turn_is_ai();
+
+                if is_ai_vs_ai(self.black_player_type, self.white_player_type) {
+                    // AI同士の場合は観戦できるようにTickを待ってから次の着手を要求する
+                    self.is_auto_play_pending = true;
+                } else {
+                    self.send_request_if_turn_is_ai();
+                }
+            }
+            Message::Tick => {
+                if self.is_auto_play_pending {
+                    self.is_auto_play_pending = false;
+                    self.send_request_if_turn_is_ai();
+                }
+            }
+            Message::AnimationFrame => {
+                if let Some(animation) = &mut self.flip_animation {
+                    if !animation.advance() {
+                        self.flip_animation = None;
+                    }
+                    self.stones_cache.clear();
+                }
             }
             Message::AiMove(_) => panic!(),
+            Message::CancelAiMove => {
+                // 計算中のスレッドに中断を通知し、以降の結果を古い世代として無視する
+                self.ai_cancel_token.store(true, Ordering::Relaxed);
+                self.ai_cancel_token = Arc::new(AtomicBool::new(false));
+                self.ai_generation += 1;
+                self.waiting_requests.clear();
+                self.is_auto_play_pending = false;
+            }
             Message::BlackPlayerTypeChanged(player_type) => {
+                self.update(Message::CancelAiMove);
                 self.black_player_type = Some(player_type);
-                if player_type == PlayerType::Human {
-                    self.waiting_requests
-                        .retain(|&req| req.player == reversi::Color::White)
-                }
                 self.send_request_if_turn_is_ai();
             }
             Message::WhitePlayerTypeChanged(player_type) => {
+                self.update(Message::CancelAiMove);
                 self.white_player_type = Some(player_type);
-                if player_type == PlayerType::Human {
-                    self.waiting_requests
-                        .retain(|&req| req.player == reversi::Color::Black)
-                }
                 self.send_request_if_turn_is_ai();
             }
-            Message::Reset => {
-                self.game.reset();
+            Message::BlackDifficultyChanged(difficulty) => {
+                self.black_difficulty = Some(difficulty);
+            }
+            Message::WhiteDifficultyChanged(difficulty) => {
+                self.white_difficulty = Some(difficulty);
+            }
+            Message::ResetGame => {
+                // 前の対局のAI要求は世代が変わるので反映されない
+                self.update(Message::CancelAiMove);
+                self.game = Game::initial();
+                self.last_move = None;
+                self.flip_animation = None;
                 self.stones_cache.clear();
                 self.send_request_if_turn_is_ai();
             }
@@ -170,6 +368,13 @@ impl Reversi {
                 stones_cache: &self.stones_cache,
                 board: self.game.board().board_state(),
                 is_clickable: is_human_turn,
+                valid_moves: if is_human_turn && !self.game.is_game_over() {
+                    self.game.get_current_players_valid_moves()
+                } else {
+                    vec![]
+                },
+                last_move: self.last_move,
+                flip_animation: self.flip_animation.as_ref(),
             })
             .width(Length::FillPortion(2))
             .height(Length::Fill),
@@ -190,6 +395,16 @@ impl Reversi {
                     .padding(10),
                 ]
                 .align_y(Vertical::Center),
+                row![
+                    text("Black AI difficulty: "),
+                    pick_list(
+                        Difficulty::ALL,
+                        self.black_difficulty,
+                        Message::BlackDifficultyChanged,
+                    )
+                    .padding(10),
+                ]
+                .align_y(Vertical::Center),
                 row![
                     text("White player type: "),
                     pick_list(
@@ -200,7 +415,17 @@ impl Reversi {
                     .padding(10),
                 ]
                 .align_y(Vertical::Center),
-                button("Reset").padding(10).on_press(Message::Reset),
+                row![
+                    text("White AI difficulty: "),
+                    pick_list(
+                        Difficulty::ALL,
+                        self.white_difficulty,
+                        Message::WhiteDifficultyChanged,
+                    )
+                    .padding(10),
+                ]
+                .align_y(Vertical::Center),
+                button("New Game").padding(10).on_press(Message::ResetGame),
             ] // .padding(10),
         ]
         .into()
@@ -212,33 +437,48 @@ impl Reversi {
 
     fn subscription(&self) -> Subscription<Message> {
         println!("subscription()");
-        Subscription::run(ai_worker)
+        let mut subscriptions = vec![Subscription::run(ai_worker)];
+        if self.is_auto_play_pending {
+            subscriptions.push(iced::time::every(self.auto_play_delay).map(|_| Message::Tick));
+        }
+        if self.flip_animation.is_some() {
+            let frame_interval = FLIP_ANIMATION_DURATION / FLIP_ANIMATION_FRAMES;
+            subscriptions.push(iced::time::every(frame_interval).map(|_| Message::AnimationFrame));
+        }
+        Subscription::batch(subscriptions)
     }
 
     fn send_request_if_turn_is_ai(&mut self) {
-        let player = self.game.current_player();
-        let player_type = match player {
-            reversi::Color::Black => self.black_player_type,
-            reversi::Color::White => self.white_player_type,
-        };
-        if let Some(t) = player_type {
-            if t == PlayerType::Ai {
-                if let Some(mut sender) = self.sender_to_ai_worker.take() {
-                    let req = AiMoveRequest {
-                        id: self.next_request_ai_move_id,
-                        board: self.game.board().board_state(),
-                        player: self.game.current_player(),
-                    };
-                    let _ = sender.try_send(Message::AiMove(req));
-                    self.waiting_requests.push(req);
-                    self.next_request_ai_move_id += 1;
-                    if self.next_request_ai_move_id < 0 {
-                        self.next_request_ai_move_id = 0;
-                    }
-                    self.sender_to_ai_worker = Some(sender);
-                }
+        if !is_ai_turn(
+            self.game.is_game_over(),
+            self.game.current_player(),
+            self.black_player_type,
+            self.white_player_type,
+        ) {
+            return;
+        }
+
+        if let Some(mut sender) = self.sender_to_ai_worker.take() {
+            let difficulty = match self.game.current_player() {
+                reversi::Color::Black => self.black_difficulty,
+                reversi::Color::White => self.white_difficulty,
+            };
+            let req = AiMoveRequest {
+                id: self.next_request_ai_move_id,
+                generation: self.ai_generation,
+                board: self.game.board().board_state(),
+                player: self.game.current_player(),
+                difficulty: difficulty.unwrap_or_default(),
+                cancel_token: self.ai_cancel_token.clone(),
+            };
+            let _ = sender.try_send(Message::AiMove(req.clone()));
+            self.waiting_requests.push(req);
+            self.next_request_ai_move_id += 1;
+            if self.next_request_ai_move_id < 0 {
+                self.next_request_ai_move_id = 0;
             }
-        };
+            self.sender_to_ai_worker = Some(sender);
+        }
     }
 }
 
@@ -252,19 +492,34 @@ fn ai_worker() -> impl Stream<Item = Message> {
         let _ = output.send(Message::AiWorkerAwaked(sender)).await;
         println!("[stream] ai worker awaked");
 
-        let ai = Arc::new(Mutex::new(Ai::default()));
+        // 評価関数が変わったときだけAIを作り直す（モデルの読み込みを避けるため）
+        let mut ai = Arc::new(Mutex::new(create_ai(Difficulty::default())));
+        let mut uses_tempura_evaluator = Difficulty::default().uses_tempura_evaluator();
 
         loop {
             let msg = receiver_from_app.select_next_some().await;
             println!("[stream] received request");
             if let Message::AiMove(req) = msg {
+                if req.cancel_token.load(Ordering::Relaxed) {
+                    println!("[stream] request cancelled");
+                    continue;
+                }
+
                 let (mut sender, mut receiver_from_thread) =
                     mpsc::channel::<Option<reversi::Position>>(100);
+                if req.difficulty.uses_tempura_evaluator() != uses_tempura_evaluator {
+                    ai = Arc::new(Mutex::new(create_ai(req.difficulty)));
+                    uses_tempura_evaluator = req.difficulty.uses_tempura_evaluator();
+                }
+
                 let ai = ai.clone();
+                let search_depth = req.difficulty.search_depth();
+                let board = req.board;
+                let player = req.player;
                 let handle = thread::spawn(move || {
                     println!("[thread] begin");
                     let mut bit_board = BitBoard::new();
-                    bit_board.set_board_state(&req.board);
+                    bit_board.set_board_state(&board);
 
                     // let mut searcher = Negaalpha::new(evaluate::test_evaluate);
                     // let search_result =
@@ -272,7 +527,8 @@ fn ai_worker() -> impl Stream<Item = Message> {
                     // let pos = search_result.best_move.map(|mv| mv.position);
 
                     if let Ok(mut ai) = ai.lock() {
-                        let pos = ai.decide_move(&bit_board, req.player);
+                        ai.search_depth = search_depth;
+                        let pos = ai.decide_move(&bit_board, player);
                         let _ = sender.try_send(pos);
                     } else {
                         let _ = sender.try_send(None);
@@ -282,11 +538,17 @@ fn ai_worker() -> impl Stream<Item = Message> {
                 let pos_or_none = receiver_from_thread.select_next_some().await;
                 let _ = handle.join();
                 println!("[stream] pos: {:?}", pos_or_none);
+                if req.cancel_token.load(Ordering::Relaxed) {
+                    // 探索中にキャンセルされたので結果を捨てる
+                    println!("[stream] result discarded");
+                    continue;
+                }
                 if let Some(pos) = pos_or_none {
                     let _ = output
                         .send(Message::MoveMaked {
                             pos,
                             request_id: req.id,
+                            generation: req.generation,
                         })
                         .await;
                 }
@@ -295,3 +557,78 @@ fn ai_worker() -> impl Stream<Item = Message> {
         }
     })
 }
+
+/// 難易度に応じた評価関数でAIを作成する
+///
+/// 学習済みモデルが読み込めない場合は通常の評価関数を使う
+fn create_ai(difficulty: Difficulty) -> Ai {
+    let searcher = if difficulty.uses_tempura_evaluator() {
+        match TempuraEvaluator::load(TEMPURA_MODEL_FILE) {
+            Ok(evaluator) => Searcher::TempuraNegaalpha(Negaalpha::new(Arc::new(evaluator))),
+            Err(e) => {
+                println!("failed to load {}: {}", TEMPURA_MODEL_FILE, e);
+                Ai::default().searcher
+            }
+        }
+    } else {
+        Ai::default().searcher
+    };
+
+    Ai {
+        searcher,
+        search_depth: difficulty.search_depth(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_ai_turn() {
+        let black = Some(PlayerType::Human);
+        let white = Some(PlayerType::Ai);
+
+        assert!(!is_ai_turn(false, reversi::Color::Black, black, white));
+        assert!(is_ai_turn(false, reversi::Color::White, black, white));
+
+        // 終局後はAIの手番にならない
+        assert!(!is_ai_turn(true, reversi::Color::White, black, white));
+
+        // 未設定の場合は人間として扱う
+        assert!(!is_ai_turn(false, reversi::Color::Black, None, white));
+    }
+
+    #[test]
+    fn test_difficulty_search_depth() {
+        let depths: Vec<u8> = Difficulty::ALL.iter().map(|d| d.search_depth()).collect();
+        assert!(depths.windows(2).all(|w| w[0] < w[1]));
+
+        assert!(!Difficulty::Easy.uses_tempura_evaluator());
+        assert!(Difficulty::Expert.uses_tempura_evaluator());
+    }
+
+    #[test]
+    fn test_is_outdated_generation() {
+        assert!(!is_outdated_generation(3, 3));
+        assert!(is_outdated_generation(2, 3));
+    }
+
+    #[test]
+    fn test_is_ai_vs_ai() {
+        assert!(is_ai_vs_ai(Some(PlayerType::Ai), Some(PlayerType::Ai)));
+        assert!(!is_ai_vs_ai(Some(PlayerType::Human), Some(PlayerType::Ai)));
+        assert!(!is_ai_vs_ai(Some(PlayerType::Ai), None));
+    }
+
+    #[test]
+    fn test_flip_animation() {
+        let mut animation = FlipAnimation::new(vec![reversi::Position::E5]);
+        assert_eq!(animation.progress(), 0.0);
+
+        // FLIP_ANIMATION_FRAMES フレームで終わる
+        let frames = std::iter::from_fn(|| animation.advance().then_some(())).count() as u32;
+        assert_eq!(frames + 1, FLIP_ANIMATION_FRAMES);
+        assert_eq!(animation.progress(), 1.0);
+    }
+}
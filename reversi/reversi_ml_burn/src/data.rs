@@ -13,7 +13,7 @@ use burn::{
 };
 use indicatif::{ParallelProgressIterator, ProgressBar, ProgressStyle};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use reversi::{self_play, BitBoard, Game, GameRecord, Position, SelfPlaySetting};
+use reversi::{self_play, BitBoard, EvaluatorType, Game, GameRecord, Position, SelfPlaySetting};
 
 use crate::{sparse_feature::SparseFeature, DynResult};
 
@@ -50,12 +50,13 @@ fn make_game_records_impl(game_count: u64, artifact_dir: &str, file_name: &str)
         .progress_with(pb.clone())
         .map(|_| {
             let setting = SelfPlaySetting {
-                max_random_moves: 10,
+                max_random_moves: 9,
                 min_random_moves: 6,
+                evaluator: EvaluatorType::Test,
             };
-            self_play(&setting)
+            self_play(&setting).map_err(|e| e.to_string())
         });
-    let records: Vec<GameRecord> = map.collect();
+    let records: Vec<GameRecord> = map.collect::<Result<_, String>>()?;
 
     let buf = bincode::serialize(&records)?;
 
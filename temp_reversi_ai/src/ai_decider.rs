@@ -1,10 +1,29 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
 use temp_reversi_core::{Game, MoveDecider, Position};
 
-use crate::strategy::Strategy;
+use crate::strategy::{endgame::EndgameSolver, opening_book::OpeningBook, Strategy};
+
+/// A ponder search running on a worker thread.
+struct Pondering {
+    stop: Arc<AtomicBool>,                 // Set to cancel the search
+    handle: JoinHandle<Box<dyn Strategy>>, // Returns the strategy with its warmed caches
+}
 
 /// AI decision-making class that wraps a strategy for move selection.
 pub struct AiDecider {
-    strategy: Box<dyn Strategy>, // Dynamically chosen strategy
+    strategy: Option<Box<dyn Strategy>>, // Dynamically chosen strategy, `None` while pondering
+    opening_book: Option<OpeningBook>,   // Book consulted before the strategy
+    endgame_solver: Option<EndgameSolver>, // Exact solver below its threshold
+    time_budget: Option<Duration>,       // Thinking time per move, instead of a fixed depth
+    pondering: Option<Pondering>,        // Ponder search in progress
 }
 
 impl AiDecider {
@@ -13,12 +32,133 @@ impl AiDecider {
     /// # Arguments
     /// * `strategy` - The strategy to use for move selection.
     pub fn new(strategy: Box<dyn Strategy>) -> Self {
-        Self { strategy }
+        Self {
+            strategy: Some(strategy),
+            opening_book: None,
+            endgame_solver: None,
+            time_budget: None,
+            pondering: None,
+        }
+    }
+
+    /// Creates a new AI decision maker that thinks for a fixed time per move.
+    ///
+    /// The strategy searches as deep as the budget allows (see
+    /// [`Strategy::decide_within`]), so the depth adapts to the position.
+    ///
+    /// # Arguments
+    /// * `strategy` - The strategy to use for move selection.
+    /// * `time_budget` - The thinking time per move.
+    pub fn with_time_budget(strategy: Box<dyn Strategy>, time_budget: Duration) -> Self {
+        let mut decider = Self::new(strategy);
+        decider.time_budget = Some(time_budget);
+        decider
+    }
+
+    /// Creates a new AI decision maker that plays from an opening book when possible.
+    ///
+    /// # Arguments
+    /// * `strategy` - The strategy to use when the position is not in the book.
+    /// * `opening_book` - The opening book to consult first.
+    pub fn with_opening_book(strategy: Box<dyn Strategy>, opening_book: OpeningBook) -> Self {
+        let mut decider = Self::new(strategy);
+        decider.opening_book = Some(opening_book);
+        decider
+    }
+
+    /// Creates a new AI decision maker that solves the endgame exactly.
+    ///
+    /// Once the position has at most `endgame_solver.empty_threshold` empty squares,
+    /// the solver picks the move instead of the strategy.
+    ///
+    /// # Arguments
+    /// * `strategy` - The strategy to use while the position is above the threshold.
+    /// * `endgame_solver` - The solver to switch to below the threshold.
+    pub fn with_endgame_solver(strategy: Box<dyn Strategy>, endgame_solver: EndgameSolver) -> Self {
+        let mut decider = Self::new(strategy);
+        decider.endgame_solver = Some(endgame_solver);
+        decider
+    }
+
+    /// Starts pondering on the position expected after the opponent plays `expected_move`.
+    ///
+    /// Meant to be called while the opponent is thinking. The strategy searches on a
+    /// worker thread, so this returns immediately. The search ends after at most
+    /// `time_budget`, or earlier when [`Self::stop_pondering`] is called; the next
+    /// `select_move` does so. If the opponent plays `expected_move`, that move benefits
+    /// from the caches filled here.
+    ///
+    /// # Arguments
+    /// * `game` - The current state of the game, with the opponent to move.
+    /// * `expected_move` - The move the opponent is expected to play.
+    /// * `time_budget` - The maximum time to spend pondering.
+    ///
+    /// # Returns
+    /// * `Err(&str)` if `expected_move` is not a valid move.
+    pub fn ponder(
+        &mut self,
+        game: &Game,
+        expected_move: Position,
+        time_budget: Duration,
+    ) -> Result<(), &'static str> {
+        let mut expected = Game::new(game.board_state().clone(), game.current_player());
+        expected.apply_move(expected_move)?;
+        if expected.is_game_over() {
+            return Ok(());
+        }
+
+        let mut strategy = self.take_strategy();
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker_stop = Arc::clone(&stop);
+        let handle = thread::spawn(move || {
+            strategy.ponder(&expected, time_budget, worker_stop);
+            strategy
+        });
+        self.pondering = Some(Pondering { stop, handle });
+        Ok(())
+    }
+
+    /// Cancels pondering, if any, and waits for the worker to hand back the strategy.
+    pub fn stop_pondering(&mut self) {
+        if let Some(pondering) = self.pondering.take() {
+            pondering.stop.store(true, Ordering::Relaxed);
+            let strategy = pondering.handle.join().expect("Pondering thread panicked");
+            self.strategy = Some(strategy);
+        }
+    }
+
+    /// Returns whether a ponder search is running or waiting to be stopped.
+    pub fn is_pondering(&self) -> bool {
+        self.pondering.is_some()
+    }
+
+    /// Takes the strategy out of the decider, stopping pondering first.
+    fn take_strategy(&mut self) -> Box<dyn Strategy> {
+        self.stop_pondering();
+        self.strategy
+            .take()
+            .expect("Strategy is present when not pondering")
+    }
+
+    /// Returns the strategy, stopping pondering first.
+    fn strategy_mut(&mut self) -> &mut Box<dyn Strategy> {
+        self.stop_pondering();
+        self.strategy
+            .as_mut()
+            .expect("Strategy is present when not pondering")
+    }
+}
+
+impl Drop for AiDecider {
+    /// Stops pondering so that the worker thread does not outlive the decider.
+    fn drop(&mut self) {
+        self.stop_pondering();
     }
 }
 
 impl MoveDecider for AiDecider {
-    /// Selects the next move using the encapsulated strategy.
+    /// Selects the next move from the opening book, or with the endgame solver once the
+    /// position is within its threshold, or using the encapsulated strategy otherwise.
     ///
     /// # Arguments
     /// * `game` - The current state of the game.
@@ -26,6 +166,233 @@ impl MoveDecider for AiDecider {
     /// # Returns
     /// * `Option<Position>` - The chosen move, or `None` if no move is possible.
     fn select_move(&mut self, game: &Game) -> Option<Position> {
-        self.strategy.evaluate_and_decide(game)
+        let booked = self
+            .opening_book
+            .as_ref()
+            .and_then(|book| book.lookup(game.board_state(), game.current_player()))
+            .filter(|&mv| game.is_valid_move(mv));
+        if booked.is_some() {
+            return booked;
+        }
+
+        if let Some(solver) = &mut self.endgame_solver {
+            if solver.is_applicable(game.board_state()) {
+                return solver.evaluate_and_decide(game);
+            }
+        }
+
+        let time_budget = self.time_budget;
+        let strategy = self.strategy_mut();
+        match time_budget {
+            Some(time_budget) => strategy.decide_within(game, time_budget),
+            None => strategy.evaluate_and_decide(game),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{evaluation::PhaseAwareEvaluator, strategy::negamax::NegamaxStrategy};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Instant,
+    };
+    use temp_reversi_core::{Bitboard, Player};
+
+    /// A strategy that counts how often it is asked for a move.
+    #[derive(Clone)]
+    struct CountingStrategy {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl Strategy for CountingStrategy {
+        fn evaluate_and_decide(&mut self, game: &Game) -> Option<Position> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            game.valid_moves().first().copied()
+        }
+
+        fn ponder(&mut self, game: &Game, _time_budget: Duration, _stop: Arc<AtomicBool>) {
+            self.evaluate_and_decide(game);
+        }
+
+        fn clone_box(&self) -> Box<dyn Strategy> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// A strategy that records the time budget it is given.
+    #[derive(Clone)]
+    struct BudgetStrategy {
+        budget: Arc<Mutex<Option<Duration>>>,
+    }
+
+    impl Strategy for BudgetStrategy {
+        fn evaluate_and_decide(&mut self, game: &Game) -> Option<Position> {
+            game.valid_moves().first().copied()
+        }
+
+        fn decide_within(&mut self, game: &Game, time_budget: Duration) -> Option<Position> {
+            *self.budget.lock().unwrap() = Some(time_budget);
+            self.evaluate_and_decide(game)
+        }
+
+        fn clone_box(&self) -> Box<dyn Strategy> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// A negamax search that records whether its last timed search ran out of time.
+    #[derive(Clone)]
+    struct TimedNegamax {
+        negamax: NegamaxStrategy<PhaseAwareEvaluator>,
+        timed_out: Arc<AtomicBool>,
+    }
+
+    impl Strategy for TimedNegamax {
+        fn evaluate_and_decide(&mut self, game: &Game) -> Option<Position> {
+            self.negamax.evaluate_and_decide(game)
+        }
+
+        fn decide_within(&mut self, game: &Game, time_budget: Duration) -> Option<Position> {
+            let mv = self.negamax.decide_within(game, time_budget);
+            self.timed_out
+                .store(self.negamax.timed_out(), Ordering::SeqCst);
+            mv
+        }
+
+        fn clone_box(&self) -> Box<dyn Strategy> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_opening_book_before_search() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let strategy = CountingStrategy {
+            calls: calls.clone(),
+        };
+
+        let mut book = OpeningBook::new();
+        book.insert_line(&[Position::F5, Position::D6]).unwrap();
+        let mut decider = AiDecider::with_opening_book(Box::new(strategy), book);
+
+        // Booked position: the book move is played without searching.
+        let mut game = Game::default();
+        assert_eq!(decider.select_move(&game), Some(Position::F5));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        // Out of book: the strategy is used.
+        game.apply_moves(&[Position::F5, Position::D6]).unwrap();
+        assert!(decider.select_move(&game).is_some());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_endgame_solver_below_threshold() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let strategy = CountingStrategy {
+            calls: calls.clone(),
+        };
+        let mut decider =
+            AiDecider::with_endgame_solver(Box::new(strategy), EndgameSolver::default());
+
+        // Above the threshold: the strategy is used.
+        assert!(decider.select_move(&Game::default()).is_some());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Only A1 and B1 are empty: the solver is used instead of the strategy.
+        let black = Position::D1.to_bit();
+        let white = Position::C1.to_bit();
+        let filled = !(Position::A1.to_bit() | Position::B1.to_bit() | black | white);
+        let game = Game::new(Bitboard::new(black | filled, white), Player::Black);
+        assert_eq!(decider.select_move(&game), Some(Position::B1));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_time_budget() {
+        let game = Game::default();
+        let budget = Duration::from_millis(200);
+
+        // With a budget, the strategy is asked to decide within it.
+        let seen = Arc::new(Mutex::new(None));
+        let strategy = BudgetStrategy {
+            budget: seen.clone(),
+        };
+        let mut decider = AiDecider::with_time_budget(Box::new(strategy), budget);
+        let mv = decider.select_move(&game);
+        assert!(mv.is_some_and(|mv| game.is_valid_move(mv)));
+        assert_eq!(*seen.lock().unwrap(), Some(budget));
+
+        // Without one, it is not.
+        let seen = Arc::new(Mutex::new(None));
+        let strategy = BudgetStrategy {
+            budget: seen.clone(),
+        };
+        let mut decider = AiDecider::new(Box::new(strategy));
+        assert!(decider.select_move(&game).is_some());
+        assert_eq!(*seen.lock().unwrap(), None);
+    }
+
+    #[test]
+    fn test_time_budget_with_negamax() {
+        // With no time at all, the search is stopped by its deadline, whatever the speed
+        // of the machine, and still plays a legal move.
+        let game = Game::default();
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let strategy = TimedNegamax {
+            negamax: NegamaxStrategy::new(PhaseAwareEvaluator::default(), 60),
+            timed_out: timed_out.clone(),
+        };
+        let mut decider = AiDecider::with_time_budget(Box::new(strategy), Duration::ZERO);
+
+        let mv = decider.select_move(&game);
+        assert!(mv.is_some_and(|mv| game.is_valid_move(mv)));
+        assert!(timed_out.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_ponder_on_expected_move() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let strategy = CountingStrategy {
+            calls: calls.clone(),
+        };
+        let mut decider = AiDecider::new(Box::new(strategy));
+        let game = Game::default();
+        let budget = Duration::from_millis(10);
+
+        assert!(decider.ponder(&game, Position::A1, budget).is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        assert!(decider.ponder(&game, Position::F5, budget).is_ok());
+        assert!(decider.is_pondering());
+        decider.stop_pondering();
+        assert!(!decider.is_pondering());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_stop_pondering_cancels_search() {
+        // Pondering to the end of the game would use the whole budget if not cancelled.
+        let strategy =
+            NegamaxStrategy::with_tt_capacity(PhaseAwareEvaluator::default(), 60, 1 << 16);
+        let mut decider =
+            AiDecider::with_time_budget(Box::new(strategy), Duration::from_millis(10));
+        let game = Game::default();
+        let budget = Duration::from_secs(600);
+
+        let start = Instant::now();
+        decider.ponder(&game, Position::F5, budget).unwrap();
+        decider.stop_pondering();
+        assert!(start.elapsed() < budget / 10, "{:?}", start.elapsed());
+
+        // The strategy is back and can play the expected position.
+        let mut expected = Game::default();
+        expected.apply_move(Position::F5).unwrap();
+        assert!(decider.select_move(&expected).is_some());
     }
 }
@@ -0,0 +1,174 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+use temp_reversi_core::{Game, Player};
+
+use crate::{strategy::Searcher, test_match::random_opening};
+
+/// Search statistics of one side of a benchmark.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SearcherStats {
+    /// Games won.
+    pub wins: usize,
+    /// Nodes visited over all searches.
+    pub nodes: u64,
+    /// Time spent searching.
+    pub elapsed: Duration,
+}
+
+impl SearcherStats {
+    /// Returns the search speed in nodes per second.
+    pub fn nodes_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.nodes as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Aggregated result of a benchmark between two searchers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BenchResult {
+    /// Number of games played.
+    pub games: usize,
+    /// Drawn games.
+    pub draws: usize,
+    /// Statistics of the first and second searcher.
+    pub searchers: [SearcherStats; 2],
+}
+
+impl BenchResult {
+    /// Returns the score rate of a searcher, counting draws as half a win.
+    ///
+    /// # Arguments
+    /// * `index` - 0 for the first searcher, 1 for the second.
+    pub fn win_rate(&self, index: usize) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        (self.searchers[index].wins as f64 + self.draws as f64 / 2.0) / self.games as f64
+    }
+}
+
+impl fmt::Display for BenchResult {
+    /// Formats the result as a table with one row per searcher.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<8} {:>6} {:>9} {:>14} {:>14}",
+            "Searcher", "Wins", "Win rate", "Nodes", "Nodes/s"
+        )?;
+        for (index, stats) in self.searchers.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<8} {:>6} {:>8.1}% {:>14} {:>14.0}",
+                index + 1,
+                stats.wins,
+                self.win_rate(index) * 100.0,
+                stats.nodes,
+                stats.nodes_per_second()
+            )?;
+        }
+        write!(f, "{} games, {} draws", self.games, self.draws)
+    }
+}
+
+/// Plays games between two searchers and aggregates their search statistics.
+///
+/// Each pair of games starts from the same random opening, generated from `seed`,
+/// with the searchers swapping colors. Every move is searched to `depth`.
+///
+/// # Arguments
+/// * `first` - The first searcher. It plays Black in even-numbered games.
+/// * `second` - The second searcher.
+/// * `num_games` - The number of games to play.
+/// * `depth` - The search depth in plies.
+/// * `seed` - Seed for the random openings.
+///
+/// # Returns
+/// * `BenchResult` - The wins, nodes and search time of each searcher.
+pub fn bench(
+    first: &mut dyn Searcher,
+    second: &mut dyn Searcher,
+    num_games: usize,
+    depth: u32,
+    seed: u64,
+) -> BenchResult {
+    let mut result = BenchResult {
+        games: num_games,
+        ..Default::default()
+    };
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut opening = Game::default();
+
+    for game_index in 0..num_games {
+        if game_index % 2 == 0 {
+            opening = random_opening(&mut rng);
+        }
+        // Index of the searcher playing Black.
+        let black_index = game_index % 2;
+
+        let mut game = Game::new(opening.board_state().clone(), opening.current_player());
+        while !game.is_game_over() {
+            let index = match game.current_player() {
+                Player::Black => black_index,
+                Player::White => 1 - black_index,
+            };
+            let searcher: &mut dyn Searcher = if index == 0 {
+                &mut *first
+            } else {
+                &mut *second
+            };
+
+            let start = Instant::now();
+            let selected = searcher.search_best_move_at_depth(&game, depth);
+            let stats = &mut result.searchers[index];
+            stats.elapsed += start.elapsed();
+            stats.nodes += searcher.visited_nodes();
+
+            let position = selected.expect("A searcher returned no move in an unfinished game");
+            game.apply_move(position)
+                .expect("A searcher returned an invalid move");
+        }
+
+        match game.result().unwrap().winner() {
+            Some(Player::Black) => result.searchers[black_index].wins += 1,
+            Some(Player::White) => result.searchers[1 - black_index].wins += 1,
+            None => result.draws += 1,
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        evaluation::{PhaseAwareEvaluator, PositionalEvaluator},
+        strategy::negamax::NegamaxStrategy,
+    };
+
+    #[test]
+    fn test_bench() {
+        let mut first = NegamaxStrategy::new(PhaseAwareEvaluator::default(), 1);
+        first.shuffle_moves = false;
+        let mut second = NegamaxStrategy::new(PositionalEvaluator, 1);
+        second.shuffle_moves = false;
+
+        let result = bench(&mut first, &mut second, 2, 1, 0);
+        assert_eq!(result.games, 2);
+        assert_eq!(
+            result.searchers[0].wins + result.searchers[1].wins + result.draws,
+            2
+        );
+        assert!(result.searchers.iter().all(|stats| stats.nodes > 0));
+        assert!((result.win_rate(0) + result.win_rate(1) - 1.0).abs() < 1e-9);
+        assert!(result.to_string().contains("2 games"));
+    }
+}
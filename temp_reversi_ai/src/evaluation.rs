@@ -10,16 +10,61 @@ pub trait EvaluationFunction {
     /// # Returns
     /// * `i32` - The evaluation score.
     fn evaluate(&self, board: &Bitboard, player: Player) -> i32;
+
+    /// Approximate number of evaluation units per disc of final disc differential.
+    ///
+    /// Evaluators score on different scales. Dividing a score by this value expresses
+    /// it in final disc-difference units, from -64 to 64, so that the scores of
+    /// different evaluators can be compared. Evaluators whose weights change over the
+    /// game can use `board` to return the scale of its phase. The default of 1.0 is for
+    /// evaluators that already score in discs.
+    fn scale_hint(&self, _board: &Bitboard) -> f32 {
+        1.0
+    }
+
+    /// Evaluates the board in approximate final disc-difference units.
+    ///
+    /// # Arguments
+    /// * `board` - The current board state.
+    /// * `player` - The player for whom the evaluation is performed.
+    ///
+    /// # Returns
+    /// * `f32` - The score divided by [`Self::scale_hint`], clamped to -64..=64.
+    fn evaluate_discs(&self, board: &Bitboard, player: Player) -> f32 {
+        (self.evaluate(board, player) as f32 / self.scale_hint(board)).clamp(-64.0, 64.0)
+    }
+
+    /// Evaluate several board states for the same player at once.
+    ///
+    /// The default implementation calls `evaluate` for each board. Evaluators with
+    /// expensive per-board work can override it to process the boards together.
+    ///
+    /// # Arguments
+    /// * `boards` - The board states to evaluate.
+    /// * `player` - The player for whom the evaluation is performed.
+    ///
+    /// # Returns
+    /// * `Vec<i32>` - The evaluation scores, in the same order as `boards`.
+    fn evaluate_batch(&self, boards: &[Bitboard], player: Player) -> Vec<i32> {
+        boards
+            .iter()
+            .map(|board| self.evaluate(board, player))
+            .collect()
+    }
 }
 
+mod composite;
 mod mobility;
 mod pattern;
 mod phase_aware;
 mod positional;
 mod simple;
+mod stability;
 
+pub use composite::*;
 pub use mobility::*;
 pub use pattern::*;
 pub use phase_aware::*;
 pub use positional::*;
 pub use simple::*;
+pub use stability::*;
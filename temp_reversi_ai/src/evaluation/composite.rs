@@ -0,0 +1,135 @@
+use temp_reversi_core::{Bitboard, Player};
+
+use super::{stable_discs, EvaluationFunction};
+
+/// Bitmask of the four corner squares.
+const CORNERS: u64 = 0x8100000000000081;
+
+/// Lightweight evaluator combining mobility, corner occupancy, frontier, and stable discs.
+///
+/// Every factor is computed directly from the bitboards, so evaluation does not
+/// allocate. Each factor is the difference between the player and the opponent:
+///
+/// * mobility - number of valid moves,
+/// * corners - number of occupied corners,
+/// * frontier - number of discs adjacent to an empty square, counted negatively
+///   since frontier discs give the opponent moves,
+/// * stability - number of discs that can never be flipped, see [`stable_discs`].
+#[derive(Debug, Clone, Copy)]
+pub struct CompositeEvaluator {
+    mobility_weight: i32,
+    corner_weight: i32,
+    frontier_weight: i32,
+    stability_weight: i32,
+}
+
+impl CompositeEvaluator {
+    /// Creates an evaluator with the given weight for each factor.
+    ///
+    /// The stability term is disabled; enable it with [`Self::with_stability_weight`].
+    ///
+    /// # Arguments
+    /// * `mobility_weight` - Weight of the mobility difference.
+    /// * `corner_weight` - Weight of the corner occupancy difference.
+    /// * `frontier_weight` - Penalty per frontier disc difference.
+    pub fn new(mobility_weight: i32, corner_weight: i32, frontier_weight: i32) -> Self {
+        Self {
+            mobility_weight,
+            corner_weight,
+            frontier_weight,
+            stability_weight: 0,
+        }
+    }
+
+    /// Sets the weight of the stable disc difference.
+    pub fn with_stability_weight(mut self, stability_weight: i32) -> Self {
+        self.stability_weight = stability_weight;
+        self
+    }
+}
+
+impl Default for CompositeEvaluator {
+    fn default() -> Self {
+        Self::new(5, 25, 2).with_stability_weight(10)
+    }
+}
+
+impl EvaluationFunction for CompositeEvaluator {
+    fn evaluate(&self, board: &Bitboard, player: Player) -> i32 {
+        let (black, white) = board.bits();
+        let (player_bits, opponent_bits) = match player {
+            Player::Black => (black, white),
+            Player::White => (white, black),
+        };
+
+        let mobility = board.valid_moves_count(player) as i32
+            - board.valid_moves_count(player.opponent()) as i32;
+
+        let corners = (player_bits & CORNERS).count_ones() as i32
+            - (opponent_bits & CORNERS).count_ones() as i32;
+
+        let frontier = board.frontier_discs(player).count_ones() as i32
+            - board.frontier_discs(player.opponent()).count_ones() as i32;
+
+        let stability = if self.stability_weight != 0 {
+            stable_discs(board, player).count_ones() as i32
+                - stable_discs(board, player.opponent()).count_ones() as i32
+        } else {
+            0
+        };
+
+        self.mobility_weight * mobility + self.corner_weight * corners
+            - self.frontier_weight * frontier
+            + self.stability_weight * stability
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_reversi_core::Position;
+
+    #[test]
+    fn test_corner_increases_score() {
+        let evaluator = CompositeEvaluator::default();
+        let board = Bitboard::default();
+        let (black, white) = board.bits();
+        let with_corner = Bitboard::new(black | Position::A1, white);
+
+        assert!(
+            evaluator.evaluate(&with_corner, Player::Black)
+                > evaluator.evaluate(&board, Player::Black)
+        );
+        assert!(
+            evaluator.evaluate(&with_corner, Player::White)
+                < evaluator.evaluate(&board, Player::White)
+        );
+    }
+
+    #[test]
+    fn test_stability_term() {
+        let evaluator = CompositeEvaluator::new(0, 0, 0).with_stability_weight(1);
+        let board = Bitboard::new(
+            Position::A1 | Position::B1 | Position::A2 | Position::D4,
+            Position::E5.to_bit(),
+        );
+        assert_eq!(evaluator.evaluate(&board, Player::Black), 3);
+        assert_eq!(evaluator.evaluate(&board, Player::White), -3);
+    }
+
+    #[test]
+    fn test_frontier_counts_discs_next_to_empty() {
+        let evaluator = CompositeEvaluator::new(0, 0, 1);
+
+        // A fully occupied board has no frontier discs.
+        let full = Bitboard::new(u64::MAX, 0);
+        assert_eq!(evaluator.evaluate(&full, Player::Black), 0);
+
+        // All four central discs touch an empty square.
+        let board = Bitboard::new(
+            Position::D5 | Position::E4 | Position::D4,
+            Position::E5.to_bit(),
+        );
+        assert_eq!(evaluator.evaluate(&board, Player::Black), -2);
+    }
+}
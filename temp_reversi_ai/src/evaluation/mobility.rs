@@ -3,23 +3,29 @@ use temp_reversi_core::{Bitboard, Player};
 use super::EvaluationFunction;
 
 /// Mobility evaluator that considers the number of valid moves as the score.
+#[derive(Debug, Clone, Copy)]
 pub struct MobilityEvaluator;
 
 impl EvaluationFunction for MobilityEvaluator {
     fn evaluate(&self, board: &Bitboard, player: Player) -> i32 {
         // Calculate mobility for the current player and opponent
-        let player_mobility = board.valid_moves(player).len() as i32;
-        let opponent_mobility = board.valid_moves(player.opponent()).len() as i32;
+        let player_mobility = board.valid_moves_count(player) as i32;
+        let opponent_mobility = board.valid_moves_count(player.opponent()) as i32;
 
         // Mobility score is the difference between the player's and the opponent's mobility
         player_mobility - opponent_mobility
     }
+
+    /// One move of mobility advantage is worth roughly two discs at the end of the game.
+    fn scale_hint(&self, _board: &Bitboard) -> f32 {
+        0.5
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use temp_reversi_core::{Bitboard, Player};
+    use temp_reversi_core::{utils::parse_transcript, Bitboard, Game, Player};
 
     #[test]
     fn test_mobility_evaluation() {
@@ -38,4 +44,17 @@ mod tests {
         assert_eq!(black_score, -evaluator.evaluate(&board, Player::White),
             "Black's score should be the negative of White's score.");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_evaluate_discs() {
+        let mut game = Game::default();
+        game.apply_moves(&parse_transcript("F5F6E6F4").unwrap()).unwrap();
+        let board = game.board_state();
+        let evaluator = MobilityEvaluator;
+        for player in [Player::Black, Player::White] {
+            let score = evaluator.evaluate(board, player) as f32;
+            assert_ne!(score, 0.0);
+            assert_eq!(evaluator.evaluate_discs(board, player), score * 2.0);
+        }
+    }
+}
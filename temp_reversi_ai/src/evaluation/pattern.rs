@@ -1,8 +1,23 @@
+use std::fs;
+
 use super::EvaluationFunction;
 use crate::patterns::PatternGroup;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use temp_reversi_core::{Bitboard, Player};
 
+/// A pattern group as stored in a model file.
+///
+/// Only the base mask is stored; the rotated patterns are rebuilt on load.
+#[derive(Serialize, Deserialize)]
+struct PatternGroupData {
+    base_mask: u64,
+    name: Option<String>,
+    state_scores: Vec<Vec<i32>>,
+}
+
 /// Evaluates the board based on multiple pattern groups and their scores.
+#[derive(Clone)]
 pub struct PatternEvaluator {
     /// Collection of pattern groups.
     pub groups: Vec<PatternGroup>,
@@ -19,16 +34,181 @@ impl PatternEvaluator {
     pub fn new(groups: Vec<PatternGroup>) -> Self {
         Self { groups }
     }
+
+    /// Saves the pattern groups and their scores to a binary model file.
+    pub fn save_bin(&self, file_path: &str) -> std::io::Result<()> {
+        let groups: Vec<PatternGroupData> = self
+            .groups
+            .iter()
+            .map(|group| PatternGroupData {
+                base_mask: group.patterns[0].mask,
+                name: group.name.clone(),
+                state_scores: group.state_scores.clone(),
+            })
+            .collect();
+        let encoded: Vec<u8> = bincode::serialize(&groups).unwrap();
+        fs::write(file_path, encoded)
+    }
+
+    /// Loads an evaluator from a binary model file written by [`Self::save_bin`].
+    pub fn load_bin(file_path: &str) -> std::io::Result<Self> {
+        let data = fs::read(file_path)?;
+        let groups: Vec<PatternGroupData> = bincode::deserialize(&data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(Self::new(
+            groups
+                .into_iter()
+                .map(|group| {
+                    PatternGroup::new(group.base_mask, group.state_scores, group.name.as_deref())
+                })
+                .collect(),
+        ))
+    }
+
+    /// Lists the pattern states present on a board, for inspecting evaluations.
+    ///
+    /// Each entry is `(group_index, state_index)` for one rotated pattern, where
+    /// `group_index` is the position of the group in `groups`. The score of an
+    /// entry is `groups[group_index].state_scores[phase][state_index]`, with the
+    /// phase given by [`Bitboard::phase`].
+    ///
+    /// # Arguments
+    /// * `board` - The board to inspect.
+    ///
+    /// # Returns
+    /// * `Vec<(usize, usize)>` - The active pattern states, grouped by pattern group.
+    pub fn active_patterns(&self, board: &Bitboard) -> Vec<(usize, usize)> {
+        self.groups
+            .iter()
+            .enumerate()
+            .flat_map(|(group_index, group)| {
+                group
+                    .active_state_indices(board)
+                    .into_iter()
+                    .map(move |state_index| (group_index, state_index))
+            })
+            .collect()
+    }
+
+    /// Returns the score contributed by each pattern group, for explaining evaluations.
+    ///
+    /// The scores are from Black's perspective and sum to
+    /// `self.evaluate(board, Player::Black)`.
+    ///
+    /// # Arguments
+    /// * `board` - The board to evaluate.
+    ///
+    /// # Returns
+    /// * `Vec<(usize, i32)>` - `(group_index, score)` for every group in `groups`.
+    pub fn evaluate_breakdown(&self, board: &Bitboard) -> Vec<(usize, i32)> {
+        let phase = board.phase();
+        self.groups
+            .iter()
+            .enumerate()
+            .map(|(group_index, group)| (group_index, group.evaluate_score(board, phase)))
+            .collect()
+    }
+
+    /// Returns the dense weight vector of a phase in the `extract_features` layout.
+    ///
+    /// The weight of state `s` of the `g`-th group is stored at index
+    /// `offset(g) + s`, where `offset(g)` is the total number of states of the
+    /// groups before it. The dot product of `extract_features(board)` with the
+    /// weights of the board's phase therefore equals the evaluation from Black's
+    /// perspective. Groups without scores for `phase` contribute zero weights.
+    ///
+    /// # Arguments
+    /// * `phase` - Game phase of the weights.
+    ///
+    /// # Returns
+    /// * `Vec<f32>` - One weight per feature index.
+    ///
+    /// [`extract_features`]: crate::learning::extract_features
+    pub fn phase_weights(&self, phase: usize) -> Vec<f32> {
+        let mut weights = Vec::new();
+        for group in &self.groups {
+            let num_states = group.state_scores[0].len();
+            match group.state_scores.get(phase) {
+                Some(scores) => weights.extend(scores.iter().map(|&score| score as f32)),
+                None => weights.resize(weights.len() + num_states, 0.0),
+            }
+        }
+        weights
+    }
+
+    /// Converts the pattern scores into phase-indexed linear model weights.
+    ///
+    /// The result is indexed as `weights[phase][feature_index]`, the layout used
+    /// by linear models trained on [`extract_features`]. See [`Self::phase_weights`]
+    /// for the index mapping.
+    ///
+    /// # Arguments
+    /// * `phase_count` - Number of phases in the result.
+    ///
+    /// [`extract_features`]: crate::learning::extract_features
+    pub fn to_phase_weights(&self, phase_count: usize) -> Vec<Vec<f32>> {
+        (0..phase_count)
+            .map(|phase| self.phase_weights(phase))
+            .collect()
+    }
+
+    /// Evaluates the board, reusing the pattern states of the previous call.
+    ///
+    /// Gives the same score as [`EvaluationFunction::evaluate`], but only the patterns
+    /// touched by the squares changed since the last call are looked up again. This
+    /// is fastest when consecutive calls are for consecutive positions of a game.
+    ///
+    /// # Arguments
+    /// * `board` - The board to evaluate.
+    /// * `player` - The player for whom the evaluation is performed.
+    pub fn evaluate_incremental(&mut self, board: &Bitboard, player: Player) -> i32 {
+        let phase = board.phase();
+        let total_score: i32 = self
+            .groups
+            .iter_mut()
+            .map(|group| group.evaluate_score_incremental(board, phase))
+            .sum();
+
+        match player {
+            Player::Black => total_score,
+            Player::White => -total_score,
+        }
+    }
+
+    /// Performs a single SGD step on the pattern scores active for a board.
+    ///
+    /// The prediction is the evaluation from Black's perspective, and every
+    /// active state score is moved by `lr * (target - prediction)`. The prediction is
+    /// evaluated incrementally, so training on the positions of a game in order is
+    /// cheap.
+    ///
+    /// # Arguments
+    /// * `board` - The board state to learn from.
+    /// * `target` - The target evaluation from Black's perspective.
+    /// * `lr` - Learning rate.
+    pub fn apply_gradient(&mut self, board: &Bitboard, target: i32, lr: f32) {
+        let phase = board.phase();
+        let prediction = self.evaluate_incremental(board, Player::Black);
+        let delta = (lr * (target - prediction) as f32).round() as i32;
+        if delta == 0 {
+            return;
+        }
+
+        for group in &mut self.groups {
+            for state_index in group.active_state_indices(board) {
+                group
+                    .update_score(phase, state_index, delta)
+                    .expect("Active state index must be in range");
+            }
+        }
+    }
 }
 
 impl EvaluationFunction for PatternEvaluator {
     fn evaluate(&self, board: &Bitboard, player: Player) -> i32 {
         let mut total_score = 0;
 
-        // Calculate the phase using `Bitboard::count_stones`
-        let (black_stones, white_stones) = board.count_stones();
-        let total_stones = black_stones + white_stones;
-        let phase = 60 - total_stones.min(60); // Phase is capped at 59
+        let phase = board.phase();
 
         // Iterate through all pattern groups and accumulate scores
         for group in &self.groups {
@@ -42,4 +222,191 @@ impl EvaluationFunction for PatternEvaluator {
 
         total_score
     }
+
+    /// Evaluates the boards in parallel, as pattern lookups dominate the cost.
+    fn evaluate_batch(&self, boards: &[Bitboard], player: Player) -> Vec<i32> {
+        boards
+            .par_iter()
+            .map(|board| self.evaluate(board, player))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::benchmark_positions;
+    use crate::{learning::extract_features, patterns::get_predefined_patterns};
+    use temp_reversi_core::Game;
+
+    #[test]
+    fn test_apply_gradient_reduces_error() {
+        let state_scores = vec![vec![0; 3_usize.pow(9)]; 60];
+        let group = PatternGroup::new(0x0000000000070707, state_scores, None);
+        let mut evaluator = PatternEvaluator::new(vec![group]);
+
+        let board = Bitboard::new(0x0000000000070000, 0x0000000000000700);
+        let target = 100;
+
+        let before = evaluator.evaluate(&board, Player::Black);
+        evaluator.apply_gradient(&board, target, 0.1);
+        let after = evaluator.evaluate(&board, Player::Black);
+
+        assert!((target - after).pow(2) < (target - before).pow(2));
+    }
+
+    #[test]
+    fn test_evaluate_incremental_matches_evaluate() {
+        let mut evaluator = PatternEvaluator::new(get_predefined_patterns());
+        for (g, group) in evaluator.groups.iter_mut().enumerate() {
+            for (phase, scores) in group.state_scores.iter_mut().enumerate() {
+                for (state, score) in scores.iter_mut().enumerate() {
+                    *score = ((state + g + phase) % 11) as i32 - 5;
+                }
+            }
+        }
+
+        let mut game = Game::default();
+        while !game.is_game_over() {
+            let board = game.board_state().clone();
+            for player in [Player::Black, Player::White] {
+                assert_eq!(
+                    evaluator.evaluate_incremental(&board, player),
+                    evaluator.evaluate(&board, player)
+                );
+            }
+            game.apply_move(game.valid_moves()[0]).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_bin() {
+        let state_scores = vec![(0..3_i32.pow(9)).collect(); 60];
+        let group = PatternGroup::new(0x0000000000070707, state_scores, Some("Corner"));
+        let evaluator = PatternEvaluator::new(vec![group]);
+
+        let path = std::env::temp_dir().join(format!(
+            "temp_reversi_pattern_model_test_{}.bin",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        evaluator.save_bin(path).unwrap();
+        let loaded = PatternEvaluator::load_bin(path).unwrap();
+        let _ = fs::remove_file(path);
+
+        assert_eq!(loaded.groups.len(), 1);
+        assert_eq!(loaded.groups[0].name.as_deref(), Some("Corner"));
+        for board in benchmark_positions() {
+            assert_eq!(
+                loaded.evaluate(&board, Player::Black),
+                evaluator.evaluate(&board, Player::Black)
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluate_batch_matches_evaluate() {
+        // Use the state index as the score so that different boards score differently.
+        let state_scores = vec![(0..3_i32.pow(9)).collect(); 60];
+        let group = PatternGroup::new(0x0000000000070707, state_scores, None);
+        let evaluator = PatternEvaluator::new(vec![group]);
+
+        let boards = benchmark_positions();
+        for player in [Player::Black, Player::White] {
+            let expected: Vec<i32> = boards
+                .iter()
+                .map(|board| evaluator.evaluate(board, player))
+                .collect();
+            assert_eq!(evaluator.evaluate_batch(&boards, player), expected);
+        }
+    }
+
+    #[test]
+    fn test_active_patterns_default_board() {
+        let evaluator = PatternEvaluator::new(get_predefined_patterns());
+        let board = Bitboard::default();
+
+        // Every rotation of every group matches exactly one state.
+        let active = evaluator.active_patterns(&board);
+        assert_eq!(active.len(), evaluator.groups.len() * 4);
+        for &(group_index, state_index) in &active {
+            assert!(state_index < evaluator.groups[group_index].state_scores[0].len());
+        }
+
+        assert_eq!(board.phase(), 0);
+    }
+
+    #[test]
+    fn test_evaluate_uses_board_phase() {
+        // Every state of phase `p` scores `p`, so the score reveals the phase used.
+        let state_scores = (0..60).map(|phase| vec![phase; 3_usize.pow(9)]).collect();
+        let group = PatternGroup::new(0x0000000000070707, state_scores, None);
+        let evaluator = PatternEvaluator::new(vec![group]);
+
+        let mut game = Game::default();
+        while !game.is_game_over() {
+            let board = game.board_state();
+            assert_eq!(
+                evaluator.evaluate(board, Player::Black),
+                4 * board.phase() as i32
+            );
+            game.apply_move(game.valid_moves()[0]).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_evaluate_breakdown_sums_to_evaluate() {
+        let groups = vec![
+            PatternGroup::new(
+                0x0000000000070707,
+                vec![(0..3_i32.pow(9)).collect(); 60],
+                None,
+            ),
+            PatternGroup::new(
+                0x00000000000000ff,
+                vec![(0..3_i32.pow(8)).rev().collect(); 60],
+                None,
+            ),
+        ];
+        let evaluator = PatternEvaluator::new(groups);
+
+        for board in benchmark_positions() {
+            let breakdown = evaluator.evaluate_breakdown(&board);
+            assert_eq!(
+                breakdown
+                    .iter()
+                    .map(|&(index, _)| index)
+                    .collect::<Vec<_>>(),
+                vec![0, 1]
+            );
+            let total: i32 = breakdown.iter().map(|&(_, score)| score).sum();
+            assert_eq!(total, evaluator.evaluate(&board, Player::Black));
+        }
+    }
+
+    #[test]
+    fn test_phase_weights_match_evaluate() {
+        let mut groups = get_predefined_patterns();
+        for (g, group) in groups.iter_mut().enumerate() {
+            for (phase, scores) in group.state_scores.iter_mut().enumerate() {
+                for (state, score) in scores.iter_mut().enumerate() {
+                    *score = ((state + g) % 7) as i32 - 3 + phase as i32;
+                }
+            }
+        }
+        let evaluator = PatternEvaluator::new(groups);
+
+        // Feature extraction rebuilds every pattern, so only a few boards are checked.
+        for board in benchmark_positions().into_iter().take(2) {
+            let phase = board.phase();
+            let weights = evaluator.phase_weights(phase);
+            let score = extract_features(&board).dot(&weights);
+            assert_eq!(score as i32, evaluator.evaluate(&board, Player::Black));
+        }
+
+        let weights = evaluator.to_phase_weights(2);
+        assert_eq!(weights.len(), 2);
+        assert_eq!(weights[1], evaluator.phase_weights(1));
+        assert!(evaluator.phase_weights(60).iter().all(|&w| w == 0.0));
+    }
 }
@@ -2,7 +2,13 @@ use temp_reversi_core::{Bitboard, Player};
 
 use super::{mobility::MobilityEvaluator, EvaluationFunction, PositionalEvaluator};
 
+/// First phase of the midgame.
+const MID_PHASE_START: usize = 17;
+/// First phase of the endgame.
+const LATE_PHASE_START: usize = 47;
+
 /// Defines the phase of the game
+#[derive(Debug, Clone, Copy)]
 enum Phase {
     Early,
     Mid,
@@ -11,27 +17,88 @@ enum Phase {
 
 /// Phase-aware evaluator that adjusts weights for mobility, positional values, and score
 /// based on the phase of the game.
-pub struct PhaseAwareEvaluator;
+///
+/// By default the weights switch abruptly at the phase boundaries. With a blend window,
+/// positions within `blend_window` plies of a boundary are scored by linearly
+/// interpolating between the two adjacent phases, which avoids evaluation jumps.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PhaseAwareEvaluator {
+    /// Number of plies on each side of a phase boundary over which scores are blended.
+    pub blend_window: usize,
+}
 
 impl PhaseAwareEvaluator {
-    /// Determine the phase of the game based on the total number of stones.
-    fn determine_phase(&self, board: &Bitboard) -> Phase {
-        let (black_count, white_count) = board.count_stones();
-        let total_stones = black_count + white_count;
+    /// Creates an evaluator that blends scores within `window` plies of a phase boundary.
+    pub fn with_blend(window: usize) -> Self {
+        Self {
+            blend_window: window,
+        }
+    }
 
-        if total_stones <= 20 {
+    /// Determine the phase of the game based on the number of moves played.
+    fn determine_phase(&self, phase: usize) -> Phase {
+        if phase < MID_PHASE_START {
             Phase::Early
-        } else if total_stones <= 50 {
+        } else if phase < LATE_PHASE_START {
             Phase::Mid
         } else {
             Phase::Late
         }
     }
+
+    /// Combines the evaluation factors with the weights of the given phase.
+    fn phase_score(
+        phase: Phase,
+        mobility_score: i32,
+        positional_score: i32,
+        score_diff: i32,
+    ) -> i32 {
+        match phase {
+            Phase::Early => 2 * mobility_score + positional_score,
+            Phase::Mid => 2 * mobility_score + positional_score + score_diff,
+            Phase::Late => score_diff,
+        }
+    }
+
+    /// Evaluation units per disc of the given phase's combination of factors.
+    ///
+    /// Each factor approximates the disc differential on its own scale, so a weighted
+    /// sum of factors is on the weighted sum of their scales.
+    fn phase_scale(phase: Phase, board: &Bitboard) -> f32 {
+        let mobility_scale = MobilityEvaluator.scale_hint(board);
+        let positional_scale = PositionalEvaluator.scale_hint(board);
+        match phase {
+            Phase::Early => 2.0 * mobility_scale + positional_scale,
+            Phase::Mid => 2.0 * mobility_scale + positional_scale + 1.0,
+            Phase::Late => 1.0,
+        }
+    }
+
+    /// Returns the adjacent phases and their weights out of `2 * blend_window` when
+    /// `phase` is within the blend window of a phase boundary.
+    fn blend_weights(&self, phase: usize) -> Option<(Phase, i32, Phase, i32)> {
+        let window = self.blend_window;
+        [
+            (MID_PHASE_START, Phase::Early, Phase::Mid),
+            (LATE_PHASE_START, Phase::Mid, Phase::Late),
+        ]
+        .into_iter()
+        .find(|&(boundary, _, _)| {
+            window > 0 && phase + window > boundary && phase < boundary + window
+        })
+        .map(|(boundary, before, after)| {
+            // Weight of the later phase, from 0 at `boundary - window` to 2 * window
+            // at `boundary + window`.
+            let after_weight = (phase + window - boundary) as i32;
+            let before_weight = 2 * window as i32 - after_weight;
+            (before, before_weight, after, after_weight)
+        })
+    }
 }
 
 impl EvaluationFunction for PhaseAwareEvaluator {
     fn evaluate(&self, board: &Bitboard, player: Player) -> i32 {
-        let phase = self.determine_phase(board);
+        let phase = board.phase();
         let mobility_evaluator = MobilityEvaluator;
         let positional_evaluator = PositionalEvaluator;
 
@@ -43,27 +110,42 @@ impl EvaluationFunction for PhaseAwareEvaluator {
             Player::Black => black_count as i32 - white_count as i32,
             Player::White => white_count as i32 - black_count as i32,
         };
+        let score_for =
+            |phase| Self::phase_score(phase, mobility_score, positional_score, score_diff);
+
+        // Blend the weights of adjacent phases near a boundary
+        if let Some((before, before_weight, after, after_weight)) = self.blend_weights(phase) {
+            return (score_for(before) * before_weight + score_for(after) * after_weight)
+                / (2 * self.blend_window as i32);
+        }
 
         // Apply weights based on the phase
-        let score = match phase {
-            Phase::Early => 2 * mobility_score + positional_score,
-            Phase::Mid => 2 * mobility_score + positional_score + score_diff,
-            Phase::Late => score_diff,
-        };
+        score_for(self.determine_phase(phase))
+    }
+
+    /// The scale follows the phase weights used by [`Self::evaluate`], so that the
+    /// corner-heavy early and mid game scores are brought down to disc units too.
+    fn scale_hint(&self, board: &Bitboard) -> f32 {
+        let phase = board.phase();
+        if let Some((before, before_weight, after, after_weight)) = self.blend_weights(phase) {
+            return (Self::phase_scale(before, board) * before_weight as f32
+                + Self::phase_scale(after, board) * after_weight as f32)
+                / (2 * self.blend_window) as f32;
+        }
 
-        score
+        Self::phase_scale(self.determine_phase(phase), board)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use temp_reversi_core::{Bitboard, Player};
+    use temp_reversi_core::{utils::parse_transcript, Bitboard, Game, Player};
 
     #[test]
     fn test_phase_aware_evaluation() {
         let board = Bitboard::default(); // Initial board state
-        let evaluator = PhaseAwareEvaluator;
+        let evaluator = PhaseAwareEvaluator::default();
 
         // Test early phase
         let early_score = evaluator.evaluate(&board, Player::Black);
@@ -90,4 +172,109 @@ mod tests {
             "Late phase score should be calculated correctly."
         );
     }
+
+    /// Returns the board after the first `ply` moves of a fixed game.
+    fn board_at_ply(ply: usize) -> Bitboard {
+        let moves = parse_transcript(
+            "D3C3C4E3C2C5F5D2E6E7F6G4E1B4G6F4A5B1B2C1G3B3G5H6H7A4G7H8H5G2B5H4E2F2A2A3G1D6G8F8B6A1E8F3C6A7F1H3B7D1F7A8D7H2B8A6C7H1",
+        )
+        .unwrap();
+        let mut game = Game::default();
+        game.apply_moves(&moves[..ply]).unwrap();
+        game.board_state().clone()
+    }
+
+    /// Evaluates `board` with the fixed weights of `phase`.
+    fn evaluate_with_phase(board: &Bitboard, player: Player, phase: Phase) -> i32 {
+        let mobility_score = MobilityEvaluator.evaluate(board, player);
+        let positional_score = PositionalEvaluator.evaluate(board, player);
+        let (black_count, white_count) = board.count_stones();
+        let score_diff = match player {
+            Player::Black => black_count as i32 - white_count as i32,
+            Player::White => white_count as i32 - black_count as i32,
+        };
+        PhaseAwareEvaluator::phase_score(phase, mobility_score, positional_score, score_diff)
+    }
+
+    #[test]
+    fn test_blend_at_boundary_is_average() {
+        let evaluator = PhaseAwareEvaluator::with_blend(4);
+
+        for (boundary, before, after) in [
+            (MID_PHASE_START, Phase::Early, Phase::Mid),
+            (LATE_PHASE_START, Phase::Mid, Phase::Late),
+        ] {
+            let board = board_at_ply(boundary);
+            assert_eq!(board.phase(), boundary);
+
+            for player in [Player::Black, Player::White] {
+                let expected = (evaluate_with_phase(&board, player, before)
+                    + evaluate_with_phase(&board, player, after))
+                    / 2;
+                assert_eq!(evaluator.evaluate(&board, player), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_blend_outside_window_matches_unblended() {
+        let blended = PhaseAwareEvaluator::with_blend(4);
+        let unblended = PhaseAwareEvaluator::default();
+
+        for ply in [0, 13, 21, 30, 43, 51, 58] {
+            let board = board_at_ply(ply);
+            assert_eq!(
+                blended.evaluate(&board, Player::Black),
+                unblended.evaluate(&board, Player::Black)
+            );
+        }
+    }
+
+    #[test]
+    fn test_near_terminal_score_is_close_to_final_margin() {
+        let evaluator = PhaseAwareEvaluator::default();
+        let board = board_at_ply(58);
+        let player = if board.valid_moves_count(Player::Black) > 0 {
+            Player::Black
+        } else {
+            Player::White
+        };
+
+        // Two squares are empty, so the game is decided and plays out quickly.
+        let mut game = Game::new(board.clone(), player);
+        while !game.is_game_over() {
+            let position = game.valid_moves()[0];
+            game.apply_move(position).unwrap();
+        }
+        let (black_count, white_count) = game.current_score();
+        let margin = match player {
+            Player::Black => black_count as f32 - white_count as f32,
+            Player::White => white_count as f32 - black_count as f32,
+        };
+        let score = evaluator.evaluate_discs(&board, player);
+        assert!(margin > 0.0);
+        // The endgame score is in discs, so only the last flips separate it from the margin.
+        assert!((score - margin).abs() <= 8.0);
+    }
+
+    #[test]
+    fn test_evaluate_discs_does_not_saturate() {
+        for evaluator in [
+            PhaseAwareEvaluator::default(),
+            PhaseAwareEvaluator::with_blend(4),
+        ] {
+            for ply in 0..=58 {
+                let board = board_at_ply(ply);
+                for player in [Player::Black, Player::White] {
+                    let score = evaluator.evaluate_discs(&board, player);
+                    assert!(score.abs() < 64.0, "ply {ply}: {score}");
+                }
+            }
+        }
+
+        // The raw early and mid game scores leave the disc range, so the scale matters.
+        let evaluator = PhaseAwareEvaluator::default();
+        assert!((0..LATE_PHASE_START)
+            .any(|ply| evaluator.evaluate(&board_at_ply(ply), Player::Black).abs() > 64));
+    }
 }
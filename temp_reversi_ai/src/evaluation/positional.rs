@@ -2,23 +2,40 @@ use temp_reversi_core::{Bitboard, Player};
 
 use super::EvaluationFunction;
 
+/// Positional values for the board (example values for demonstration).
+#[rustfmt::skip]
+const POSITIONAL_VALUES: [i32; 64] = [
+    100, -20, 10,  5,  5, 10, -20, 100,  // Row 1
+    -20, -50, -2, -2, -2, -2, -50, -20,  // Row 2
+     10,  -2,  3,  2,  2,  3,  -2,  10,  // Row 3
+      5,  -2,  2,  0,  0,  2,  -2,   5,  // Row 4
+      5,  -2,  2,  0,  0,  2,  -2,   5,  // Row 5
+     10,  -2,  3,  2,  2,  3,  -2,  10,  // Row 6
+    -20, -50, -2, -2, -2, -2, -50, -20,  // Row 7
+    100, -20, 10,  5,  5, 10, -20, 100,  // Row 8
+];
+
+/// Largest possible positional score: the sum of the absolute weights (940).
+///
+/// It is reached when the player owns every square with a positive weight and the
+/// opponent every square with a negative weight, so no position scores more. A board
+/// full of the player's discs scores only the signed sum of the weights (156).
+const MAX_POSITIONAL_SCORE: i32 = {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < 64 {
+        sum += POSITIONAL_VALUES[i].abs();
+        i += 1;
+    }
+    sum
+};
+
 /// Positional evaluator that considers board position values.
+#[derive(Debug, Clone, Copy)]
 pub struct PositionalEvaluator;
 
 impl EvaluationFunction for PositionalEvaluator {
     fn evaluate(&self, board: &Bitboard, player: Player) -> i32 {
-        // Positional values for the board (example values for demonstration)
-        let positional_values: [i32; 64] = [
-            100, -20, 10,  5,  5, 10, -20, 100,  // Row 1
-            -20, -50, -2, -2, -2, -2, -50, -20,  // Row 2
-             10,  -2,  3,  2,  2,  3,  -2,  10,  // Row 3
-              5,  -2,  2,  0,  0,  2,  -2,   5,  // Row 4
-              5,  -2,  2,  0,  0,  2,  -2,   5,  // Row 5
-             10,  -2,  3,  2,  2,  3,  -2,  10,  // Row 6
-            -20, -50, -2, -2, -2, -2, -50, -20,  // Row 7
-            100, -20, 10,  5,  5, 10, -20, 100,  // Row 8
-        ];
-
         let (black_bits, white_bits) = board.bits();
         let mut score = 0;
 
@@ -26,9 +43,9 @@ impl EvaluationFunction for PositionalEvaluator {
         for i in 0..64 {
             let mask = 1u64 << i;
             if black_bits & mask != 0 {
-                score += positional_values[i];
+                score += POSITIONAL_VALUES[i];
             } else if white_bits & mask != 0 {
-                score -= positional_values[i];
+                score -= POSITIONAL_VALUES[i];
             }
         }
 
@@ -38,6 +55,12 @@ impl EvaluationFunction for PositionalEvaluator {
             Player::White => -score,
         }
     }
+
+    /// Maps [`MAX_POSITIONAL_SCORE`], the largest possible score, to a disc differential
+    /// of 64, so normalized scores stay within -64..=64 without clamping.
+    fn scale_hint(&self, _board: &Bitboard) -> f32 {
+        MAX_POSITIONAL_SCORE as f32 / 64.0
+    }
 }
 
 #[cfg(test)]
@@ -58,4 +81,23 @@ mod tests {
         let white_score = evaluator.evaluate(&board, Player::White);
         assert_eq!(white_score, 0, "White should have a score of 0 on the default board.");
     }
+
+    #[test]
+    fn test_max_positional_score() {
+        assert_eq!(MAX_POSITIONAL_SCORE, 940);
+
+        // A full board of Black's discs scores the signed sum of the weights.
+        let full = Bitboard::new(u64::MAX, 0);
+        assert_eq!(PositionalEvaluator.evaluate(&full, Player::Black), 156);
+
+        // Owning exactly the positive-weight squares reaches the maximum.
+        let positive = (0..64)
+            .filter(|&i| POSITIONAL_VALUES[i] > 0)
+            .fold(0u64, |bits, i| bits | 1 << i);
+        let best = Bitboard::new(positive, !positive);
+        assert_eq!(
+            PositionalEvaluator.evaluate(&best, Player::Black),
+            MAX_POSITIONAL_SCORE
+        );
+    }
 }
\ No newline at end of file
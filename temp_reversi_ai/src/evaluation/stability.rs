@@ -0,0 +1,108 @@
+use temp_reversi_core::{Bitboard, Player};
+
+/// All squares except the A file.
+const NOT_A_FILE: u64 = 0xfefefefefefefefe;
+/// All squares except the H file.
+const NOT_H_FILE: u64 = 0x7f7f7f7f7f7f7f7f;
+/// Squares on the outer ring of the board.
+const BORDER: u64 = 0xff818181818181ff;
+
+/// Lines through the board as (shift, mask after a left shift, mask after a right
+/// shift, squares with the board edge on one side of the line).
+const AXES: [(u32, u64, u64, u64); 4] = [
+    // Horizontal
+    (1, NOT_A_FILE, NOT_H_FILE, 0x8181818181818181),
+    // Vertical
+    (8, u64::MAX, u64::MAX, 0xff000000000000ff),
+    // Diagonal (A1-H8)
+    (9, NOT_A_FILE, NOT_H_FILE, BORDER),
+    // Anti-diagonal (H1-A8)
+    (7, NOT_H_FILE, NOT_A_FILE, BORDER),
+];
+
+/// Returns the squares adjacent to `bits` in either direction along an axis.
+fn axis_neighbors(bits: u64, shift: u32, positive_mask: u64, negative_mask: u64) -> u64 {
+    ((bits << shift) & positive_mask) | ((bits >> shift) & negative_mask)
+}
+
+/// Returns the squares whose line along an axis contains no empty square.
+fn full_lines(empty: u64, shift: u32, positive_mask: u64, negative_mask: u64) -> u64 {
+    let mut reached = empty;
+    for _ in 0..7 {
+        reached |= axis_neighbors(reached, shift, positive_mask, negative_mask);
+    }
+    !reached
+}
+
+/// Computes the discs of `player` that can never be flipped.
+///
+/// A disc is stable if along each of the four lines through it, the line is full,
+/// or one of its neighbors on the line is the board edge or a stable disc of the
+/// same color. Stability spreads from the corners and is propagated until no more
+/// discs are added. The result is a subset of the truly stable discs.
+///
+/// # Arguments
+/// * `board` - The board to analyze.
+/// * `player` - The player whose discs are checked.
+///
+/// # Returns
+/// * `u64` - Bitmask of the stable discs of `player`.
+pub fn stable_discs(board: &Bitboard, player: Player) -> u64 {
+    let (black, white) = board.bits();
+    let player_bits = match player {
+        Player::Black => black,
+        Player::White => white,
+    };
+    let empty = !(black | white);
+
+    let mut fixed = [0u64; 4];
+    for (fixed, &(shift, positive_mask, negative_mask, edge)) in fixed.iter_mut().zip(&AXES) {
+        *fixed = full_lines(empty, shift, positive_mask, negative_mask) | edge;
+    }
+
+    let mut stable = 0u64;
+    loop {
+        let mut candidates = player_bits;
+        for (&fixed, &(shift, positive_mask, negative_mask, _)) in fixed.iter().zip(&AXES) {
+            candidates &= fixed | axis_neighbors(stable, shift, positive_mask, negative_mask);
+        }
+
+        if candidates == stable {
+            return stable;
+        }
+        stable = candidates;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_reversi_core::Position;
+
+    #[test]
+    fn test_filled_corner_region_is_stable() {
+        let corner =
+            Position::A1 | Position::B1 | Position::C1 | Position::A2 | Position::B2 | Position::A3;
+        let board = Bitboard::new(corner, Position::D4 | Position::E5);
+
+        assert_eq!(stable_discs(&board, Player::Black), corner);
+        assert_eq!(stable_discs(&board, Player::White), 0);
+    }
+
+    #[test]
+    fn test_isolated_center_disc_is_not_stable() {
+        let board = Bitboard::new(Position::D4.to_bit(), 0);
+        assert_eq!(stable_discs(&board, Player::Black), 0);
+
+        let board = Bitboard::default();
+        assert_eq!(stable_discs(&board, Player::Black), 0);
+        assert_eq!(stable_discs(&board, Player::White), 0);
+    }
+
+    #[test]
+    fn test_full_board_is_stable() {
+        let board = Bitboard::new(0x00000000ffffffff, 0xffffffff00000000);
+        assert_eq!(stable_discs(&board, Player::Black), 0x00000000ffffffff);
+        assert_eq!(stable_discs(&board, Player::White), 0xffffffff00000000);
+    }
+}
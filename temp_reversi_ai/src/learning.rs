@@ -1,11 +1,15 @@
 mod dataset;
 mod feature_extraction;
 mod game_dataset;
+mod game_dataset_stream;
 mod game_generator;
+mod progress_reporter;
 mod training_pipeline;
 
 pub use dataset::*;
 pub use feature_extraction::*;
 pub use game_dataset::*;
+pub use game_dataset_stream::*;
 pub use game_generator::*;
+pub use progress_reporter::*;
 pub use training_pipeline::*;
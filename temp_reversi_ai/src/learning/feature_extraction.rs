@@ -29,6 +29,21 @@ use crate::{evaluation::PatternEvaluator, patterns::get_predefined_patterns, uti
 /// ```
 pub fn extract_features(board: &Bitboard) -> SparseVector {
     let evaluator = PatternEvaluator::new(get_predefined_patterns());
+    extract_features_with(&evaluator, board)
+}
+
+/// Extracts a feature vector using the pattern groups of an existing evaluator.
+///
+/// Building the predefined pattern groups is expensive, so this should be used
+/// instead of [`extract_features`] when extracting features from many boards.
+///
+/// # Arguments
+/// * `evaluator` - Evaluator whose pattern groups define the features.
+/// * `board` - The current board state as a `Bitboard`.
+///
+/// # Returns
+/// * A `SparseVector` representing the board's features.
+pub fn extract_features_with(evaluator: &PatternEvaluator, board: &Bitboard) -> SparseVector {
     let (black_mask, white_mask) = board.bits();
 
     // Store feature counts (each key is a unique feature index, value is the occurrence count)
@@ -66,9 +81,10 @@ pub fn extract_features(board: &Bitboard) -> SparseVector {
 mod tests {
     use std::collections::HashSet;
 
-    use temp_reversi_core::{utils::rotate_mask_90_cw, Player, Position};
+    use temp_reversi_core::{utils::rotate_mask_90_cw, Position};
 
     use super::*;
+    use crate::test_utils::benchmark_positions;
 
     /// Tests feature extraction on the default board state.
     ///
@@ -188,46 +204,44 @@ mod tests {
     /// - Individual feature counts do not exceed 4 (since there are 4 rotated versions).
     #[test]
     fn test_rotation_consistency() {
-        let mut board = Bitboard::default();
+        let evaluator = PatternEvaluator::new(get_predefined_patterns());
 
-        // Place some stones in non-symmetric positions
-        board.apply_move(Position::D3, Player::Black).unwrap();
-        board.apply_move(Position::C5, Player::White).unwrap();
-
-        // Extract features for the original board
-        let base_features = extract_features(&board);
-        let base_sum: f32 = base_features.values().iter().sum();
-
-        // Track the maximum value in the feature vector
+        // Track the maximum value in the feature vectors
         let mut max_value: f32 = 0.0;
 
-        // Get black and white bitmasks
-        let (mut black_mask, mut white_mask) = board.bits();
-
-        // Rotate the board 90, 180, and 270 degrees, checking consistency
-        for _ in 0..3 {
-            black_mask = rotate_mask_90_cw(black_mask);
-            white_mask = rotate_mask_90_cw(white_mask);
-
-            // Create a new Bitboard with rotated bitmasks
-            let rotated_board = Bitboard::new(black_mask, white_mask);
-            let rotated_features = extract_features(&rotated_board);
-            let rotated_sum: f32 = rotated_features.values().iter().sum();
-
-            // Check that total sum of feature values remains the same across rotations
-            assert_eq!(
-                base_sum, rotated_sum,
-                "Feature vector sum should be consistent across rotations"
-            );
-
-            // Track max value
-            max_value = max_value.max(
-                *rotated_features
-                    .values()
-                    .iter()
-                    .max_by(|a, b| a.partial_cmp(b).unwrap())
-                    .unwrap(),
-            );
+        for board in benchmark_positions() {
+            // Extract features for the original board
+            let base_features = extract_features_with(&evaluator, &board);
+            let base_sum: f32 = base_features.values().iter().sum();
+
+            // Get black and white bitmasks
+            let (mut black_mask, mut white_mask) = board.bits();
+
+            // Rotate the board 90, 180, and 270 degrees, checking consistency
+            for _ in 0..3 {
+                black_mask = rotate_mask_90_cw(black_mask);
+                white_mask = rotate_mask_90_cw(white_mask);
+
+                // Create a new Bitboard with rotated bitmasks
+                let rotated_board = Bitboard::new(black_mask, white_mask);
+                let rotated_features = extract_features_with(&evaluator, &rotated_board);
+                let rotated_sum: f32 = rotated_features.values().iter().sum();
+
+                // Check that total sum of feature values remains the same across rotations
+                assert_eq!(
+                    base_sum, rotated_sum,
+                    "Feature vector sum should be consistent across rotations"
+                );
+
+                // Track max value
+                max_value = max_value.max(
+                    *rotated_features
+                        .values()
+                        .iter()
+                        .max_by(|a, b| a.partial_cmp(b).unwrap())
+                        .unwrap(),
+                );
+            }
         }
 
         // Ensure that no individual feature count exceeds 4
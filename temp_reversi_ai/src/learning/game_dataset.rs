@@ -1,11 +1,18 @@
-use super::{extract_features, Dataset};
+use super::{extract_features_with, Dataset, ProgressReporter};
 use crate::{
     evaluation::{EvaluationFunction, PatternEvaluator},
     patterns::get_predefined_patterns,
 };
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::fs::{self, metadata};
-use temp_reversi_core::{Game, Position};
+use std::{
+    collections::HashSet,
+    fs::{self, metadata},
+};
+use temp_reversi_core::{
+    utils::{transform_mask, SYMMETRY_COUNT},
+    Bitboard, Game, Position,
+};
 
 /// Represents a game record containing move history and final score.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +23,57 @@ pub struct GameRecord {
     pub final_score: (u8, u8),
 }
 
+/// Caps on the number of training samples per range of empty squares.
+///
+/// Bucket `i` holds the positions with `i * bucket_width` to
+/// `(i + 1) * bucket_width - 1` empty squares; positions with more empty squares
+/// than the last bucket covers are counted in the last bucket.
+#[derive(Debug, Clone)]
+pub struct PhaseSampling {
+    /// Number of empty-square counts per bucket.
+    bucket_width: usize,
+    /// Maximum number of samples in each bucket.
+    caps: Vec<usize>,
+}
+
+impl PhaseSampling {
+    /// Creates a sampling with the given bucket width and per-bucket caps.
+    ///
+    /// # Arguments
+    /// * `bucket_width` - Number of empty-square counts per bucket.
+    /// * `caps` - Maximum number of samples in each bucket.
+    ///
+    /// # Returns
+    /// - `Ok(PhaseSampling)` if the parameters are valid.
+    /// - `Err(String)` if `bucket_width` is zero or `caps` is empty.
+    pub fn new(bucket_width: usize, caps: Vec<usize>) -> Result<Self, String> {
+        if bucket_width == 0 {
+            return Err("Bucket width must be positive".to_string());
+        }
+        if caps.is_empty() {
+            return Err("At least one bucket cap is required".to_string());
+        }
+        Ok(Self { bucket_width, caps })
+    }
+
+    /// Returns the number of empty-square counts per bucket.
+    pub fn bucket_width(&self) -> usize {
+        self.bucket_width
+    }
+
+    /// Returns the maximum number of samples in each bucket.
+    pub fn caps(&self) -> &[usize] {
+        &self.caps
+    }
+
+    /// Returns the bucket of a board.
+    fn bucket(&self, board: &Bitboard) -> usize {
+        let (black, white) = board.count_stones();
+        let empty = 64 - black - white;
+        (empty / self.bucket_width).min(self.caps.len() - 1)
+    }
+}
+
 /// Manages multiple `GameRecord` entries, supporting batch processing, saving, and loading.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GameDataset {
@@ -199,11 +257,37 @@ impl GameDataset {
         }
     }
 
+    /// Shuffles the records in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for the permutation. If `None`, a random seed is chosen and logged.
+    ///
+    /// # Returns
+    ///
+    /// The seed that was used, so that the permutation can be reproduced.
+    pub fn shuffle(&mut self, seed: Option<u64>) -> u64 {
+        let seed = seed.unwrap_or_else(|| {
+            let seed = rand::random();
+            println!("Shuffling dataset with seed {}", seed);
+            seed
+        });
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        self.records.shuffle(&mut rng);
+        seed
+    }
+
     /// Extracts training data in batches from the game records.
     ///
+    /// With `augment`, every position is also sampled in each of its symmetric
+    /// forms (rotations and reflections) with the label of the original position,
+    /// so the number of samples is multiplied by `SYMMETRY_COUNT` (8).
+    ///
     /// # Arguments
     ///
     /// * `batch_size` - The number of records per batch.
+    /// * `augment` - Whether to add the symmetric forms of each position.
     ///
     /// # Returns
     ///
@@ -213,35 +297,390 @@ impl GameDataset {
     ///
     /// ```
     /// let dataset = GameDataset::load_bin("dataset.bin").unwrap();
-    /// let mut batches = dataset.extract_training_data_in_batches(100);
+    /// let mut batches = dataset.extract_training_data_in_batches(100, false);
     /// let first_batch = batches.next().unwrap();
     /// assert!(!first_batch.is_empty());
     /// ```
     pub fn extract_training_data_in_batches(
         &self,
         batch_size: usize,
+        augment: bool,
     ) -> impl Iterator<Item = Dataset> + use<'_> {
         let evaluator = PatternEvaluator::new(get_predefined_patterns());
         let mut batch = Dataset::new();
+        let transforms = if augment { SYMMETRY_COUNT } else { 1 };
 
         self.records.chunks(batch_size).map(move |chunk| {
             batch.features.clear();
             batch.labels.clear();
 
-            for record in chunk.iter().cloned() {
-                let mut game = Game::default();
-                for &pos_idx in &record.moves {
-                    let pos = Position::from_u8(pos_idx).unwrap();
-                    if game.is_valid_move(pos) {
-                        let feature_vector = extract_features(&game.board_state());
-                        let score = evaluator.evaluate(&game.board_state(), game.current_player());
+            for record in chunk {
+                for_each_position(record, |game| {
+                    let board = game.board_state();
+                    let score = evaluator.evaluate(board, game.current_player());
+                    for transform in 0..transforms {
+                        let feature_vector =
+                            extract_features_with(&evaluator, &transform_board(board, transform));
                         batch.add_sample(feature_vector, score as f32);
-                        game.apply_move(pos).unwrap();
                     }
-                }
+                });
             }
 
             batch.clone()
         })
     }
+
+    /// Extracts training samples with at most `sampling.caps[i]` positions in each bucket.
+    ///
+    /// Records are processed in order, and positions falling into a full bucket are
+    /// skipped. The achieved number of samples per bucket is reported through
+    /// `reporter` when done.
+    ///
+    /// # Arguments
+    ///
+    /// * `sampling` - Bucket width and the cap of each bucket.
+    /// * `reporter` - Receives one step per record and the final distribution.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the sampled `Dataset` and the number of samples in each bucket.
+    pub fn extract_stratified_training_data(
+        &self,
+        sampling: &PhaseSampling,
+        reporter: &dyn ProgressReporter,
+    ) -> (Dataset, Vec<usize>) {
+        let evaluator = PatternEvaluator::new(get_predefined_patterns());
+        let mut dataset = Dataset::new();
+        let mut counts = vec![0; sampling.caps.len()];
+
+        reporter.set_total(self.records.len());
+        for record in &self.records {
+            for_each_position(record, |game| {
+                let board = game.board_state();
+                let bucket = sampling.bucket(board);
+                if counts[bucket] >= sampling.caps[bucket] {
+                    return;
+                }
+                counts[bucket] += 1;
+
+                let feature_vector = extract_features_with(&evaluator, board);
+                let score = evaluator.evaluate(board, game.current_player());
+                dataset.add_sample(feature_vector, score as f32);
+            });
+            reporter.increment();
+        }
+
+        let distribution: Vec<String> = counts
+            .iter()
+            .zip(&sampling.caps)
+            .enumerate()
+            .map(|(i, (count, cap))| {
+                let low = i * sampling.bucket_width;
+                format!(
+                    "{}-{}: {}/{}",
+                    low,
+                    low + sampling.bucket_width - 1,
+                    count,
+                    cap
+                )
+            })
+            .collect();
+        reporter.message(&format!(
+            "Samples per empty-square bucket: {}",
+            distribution.join(", ")
+        ));
+        reporter.finish();
+
+        (dataset, counts)
+    }
+
+    /// Extracts training samples, skipping positions that were already sampled.
+    ///
+    /// Positions are compared by their canonical form under the board symmetries and
+    /// the player to move, so a position reached in a mirrored or rotated game is
+    /// sampled only once. The number of skipped duplicates is reported through
+    /// `reporter` when done.
+    ///
+    /// # Arguments
+    ///
+    /// * `augment` - Whether to add the symmetric forms of each unique position.
+    /// * `reporter` - Receives one step per record and the number of duplicates.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the `Dataset` of unique samples and the number of duplicates skipped.
+    pub fn extract_unique_training_data(
+        &self,
+        augment: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> (Dataset, usize) {
+        let evaluator = PatternEvaluator::new(get_predefined_patterns());
+        let mut dataset = Dataset::new();
+        let mut seen = HashSet::new();
+        let mut duplicates = 0;
+        let transforms = if augment { SYMMETRY_COUNT } else { 1 };
+
+        reporter.set_total(self.records.len());
+        for record in &self.records {
+            for_each_position(record, |game| {
+                let board = game.board_state();
+                let key = (board.canonical().bits(), game.current_player());
+                if !seen.insert(key) {
+                    duplicates += 1;
+                    return;
+                }
+                let score = evaluator.evaluate(board, game.current_player());
+                for transform in 0..transforms {
+                    let feature_vector =
+                        extract_features_with(&evaluator, &transform_board(board, transform));
+                    dataset.add_sample(feature_vector, score as f32);
+                }
+            });
+            reporter.increment();
+        }
+
+        reporter.message(&format!(
+            "Skipped {} duplicate positions, kept {} unique positions",
+            duplicates,
+            seen.len()
+        ));
+        reporter.finish();
+
+        (dataset, duplicates)
+    }
+}
+
+/// Replays a record and calls `f` with the game state before each valid move.
+fn for_each_position(record: &GameRecord, mut f: impl FnMut(&Game)) {
+    let mut game = Game::default();
+    for &pos_idx in &record.moves {
+        let pos = Position::from_u8(pos_idx).unwrap();
+        if game.is_valid_move(pos) {
+            f(&game);
+            game.apply_move(pos).unwrap();
+        }
+    }
+}
+
+/// Applies one of the board symmetries to both colors of a board.
+fn transform_board(board: &Bitboard, transform: u8) -> Bitboard {
+    let (black, white) = board.bits();
+    Bitboard::new(
+        transform_mask(black, transform),
+        transform_mask(white, transform),
+    )
+}
+
+/// Loads a dataset, shuffles its records, and saves the result.
+///
+/// # Arguments
+///
+/// * `dataset_base_path` - The base name of the input dataset files.
+/// * `output_base_path` - The base name for the shuffled output files.
+/// * `seed` - Seed for the permutation. If `None`, a random seed is chosen and logged.
+///
+/// # Returns
+///
+/// A `std::io::Result<u64>` containing the seed that was used.
+pub fn shuffle_dataset(
+    dataset_base_path: &str,
+    output_base_path: &str,
+    seed: Option<u64>,
+) -> std::io::Result<u64> {
+    let mut dataset = GameDataset::load_auto(dataset_base_path)?;
+    let seed = dataset.shuffle(seed);
+    dataset.save_auto(output_base_path)?;
+    Ok(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{learning::NoProgress, utils::SparseVector};
+
+    fn numbered_dataset(count: usize) -> GameDataset {
+        let mut dataset = GameDataset::new();
+        for i in 0..count {
+            dataset.add_record(GameRecord {
+                moves: vec![i as u8],
+                final_score: (32, 32),
+            });
+        }
+        dataset
+    }
+
+    #[test]
+    fn test_shuffle_dataset_with_seed_is_reproducible() {
+        let dir = std::env::temp_dir();
+        let input = dir.join(format!("temp_reversi_shuffle_input_{}", std::process::id()));
+        let input = input.to_str().unwrap();
+        numbered_dataset(50).save_auto(input).unwrap();
+
+        let shuffled = |name: &str| -> Vec<Vec<u8>> {
+            let output = dir.join(format!("{}_{}", name, std::process::id()));
+            let output = output.to_str().unwrap();
+            assert_eq!(shuffle_dataset(input, output, Some(123)).unwrap(), 123);
+            let dataset = GameDataset::load_auto(output).unwrap();
+            let _ = std::fs::remove_file(format!("{}.bin", output));
+            dataset.records.into_iter().map(|r| r.moves).collect()
+        };
+        let first = shuffled("temp_reversi_shuffle_output_1");
+        let second = shuffled("temp_reversi_shuffle_output_2");
+        let _ = std::fs::remove_file(format!("{}.bin", input));
+
+        assert_eq!(first, second);
+        assert_ne!(first, (0..50).map(|i| vec![i as u8]).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shuffle_returns_used_seed() {
+        let mut dataset = numbered_dataset(20);
+        let seed = dataset.shuffle(None);
+
+        let mut replay = numbered_dataset(20);
+        replay.shuffle(Some(seed));
+
+        let moves = |d: &GameDataset| {
+            d.records
+                .iter()
+                .map(|r| r.moves.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(moves(&dataset), moves(&replay));
+    }
+
+    #[test]
+    fn test_extract_unique_training_data_skips_symmetric_duplicates() {
+        // F5 D6 and D3 C5 are mirror images along the anti-diagonal.
+        let mut dataset = GameDataset::new();
+        for moves in [[Position::F5, Position::D6], [Position::D3, Position::C5]] {
+            dataset.add_record(GameRecord {
+                moves: moves.iter().map(|p| p.to_u8()).collect(),
+                final_score: (32, 32),
+            });
+        }
+
+        let reporter = MessageRecorder::default();
+        let (unique, duplicates) = dataset.extract_unique_training_data(false, &reporter);
+        assert_eq!(unique.len(), 2);
+        assert_eq!(duplicates, 2);
+        assert_eq!(
+            *reporter.messages.lock().unwrap(),
+            vec!["Skipped 2 duplicate positions, kept 2 unique positions".to_string()]
+        );
+
+        let (augmented, duplicates) = dataset.extract_unique_training_data(true, &NoProgress);
+        assert_eq!(augmented.len(), 2 * SYMMETRY_COUNT as usize);
+        assert_eq!(duplicates, 2);
+    }
+
+    #[test]
+    fn test_extract_training_data_with_augmentation() {
+        let mut dataset = GameDataset::new();
+        dataset.add_record(GameRecord {
+            moves: vec![Position::F5.to_u8(), Position::D6.to_u8()],
+            final_score: (40, 24),
+        });
+
+        let plain = dataset
+            .extract_training_data_in_batches(1, false)
+            .next()
+            .unwrap();
+        let augmented = dataset
+            .extract_training_data_in_batches(1, true)
+            .next()
+            .unwrap();
+        assert_eq!(plain.len(), 2);
+        assert_eq!(augmented.len(), plain.len() * SYMMETRY_COUNT as usize);
+
+        // The second position is the board after F5, sampled in every symmetric form.
+        let mut game = Game::default();
+        game.apply_move(Position::F5).unwrap();
+        let evaluator = PatternEvaluator::new(get_predefined_patterns());
+        // Feature indices are not ordered, so compare them as sorted pairs.
+        let sorted = |feature: &SparseVector| {
+            let mut pairs: Vec<(usize, f32)> = feature
+                .indices()
+                .iter()
+                .copied()
+                .zip(feature.values().iter().copied())
+                .collect();
+            pairs.sort_by_key(|&(index, _)| index);
+            pairs
+        };
+        let per_position = SYMMETRY_COUNT as usize;
+        for transform in 0..SYMMETRY_COUNT {
+            let index = per_position + transform as usize;
+            assert_eq!(augmented.labels[index], plain.labels[1]);
+
+            let expected =
+                extract_features_with(&evaluator, &transform_board(game.board_state(), transform));
+            assert_eq!(sorted(&augmented.features[index]), sorted(&expected));
+        }
+        assert_eq!(
+            sorted(&augmented.features[per_position]),
+            sorted(&plain.features[1])
+        );
+    }
+
+    /// Reporter that records the reported messages.
+    #[derive(Default)]
+    struct MessageRecorder {
+        messages: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl ProgressReporter for MessageRecorder {
+        fn set_total(&self, _total: usize) {}
+
+        fn increment(&self) {}
+
+        fn message(&self, message: &str) {
+            self.messages.lock().unwrap().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_extract_stratified_training_data_respects_caps() {
+        // Play a full game by always taking the first legal move.
+        let mut game = Game::default();
+        let mut moves = Vec::new();
+        while let Some(&mv) = game.valid_moves().first() {
+            moves.push(mv.to_u8());
+            game.apply_move(mv).unwrap();
+        }
+        let mut dataset = GameDataset::new();
+        for _ in 0..2 {
+            dataset.add_record(GameRecord {
+                moves: moves.clone(),
+                final_score: (32, 32),
+            });
+        }
+
+        let sampling = PhaseSampling::new(20, vec![1, 5, 1000]).unwrap();
+        let reporter = MessageRecorder::default();
+        let (samples, counts) = dataset.extract_stratified_training_data(&sampling, &reporter);
+
+        // The last bucket is never full, so it gets every opening position of both games.
+        let mut opening = 0;
+        for_each_position(&dataset.records[0], |game| {
+            if sampling.bucket(game.board_state()) == 2 {
+                opening += 1;
+            }
+        });
+        assert_eq!(counts, vec![1, 5, 2 * opening]);
+        assert_eq!(samples.len(), counts.iter().sum::<usize>());
+
+        let messages = reporter.messages.lock().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("0-19: 1/1"));
+    }
+    #[test]
+    fn test_phase_sampling_rejects_invalid_parameters() {
+        assert!(PhaseSampling::new(0, vec![1, 2]).is_err());
+        assert!(PhaseSampling::new(20, Vec::new()).is_err());
+
+        let sampling = PhaseSampling::new(20, vec![1, 2]).unwrap();
+        assert_eq!(sampling.bucket_width(), 20);
+        assert_eq!(sampling.caps(), &[1, 2]);
+    }
 }
@@ -0,0 +1,245 @@
+use std::{collections::VecDeque, fs::metadata};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{GameDataset, GameRecord};
+
+/// Streams game records from dataset files written by `GameDataset::save_auto`.
+///
+/// Only one dataset file is held in memory at a time, so datasets split into many
+/// parts can be consumed batch by batch without loading everything at once.
+pub struct GameDatasetStream {
+    /// Dataset files that have not been opened yet.
+    pending_files: VecDeque<String>,
+    /// Records remaining in the currently opened file.
+    current: std::vec::IntoIter<GameRecord>,
+    /// Number of records per batch.
+    batch_size: usize,
+    /// Records waiting to be drawn in random order.
+    shuffle_buffer: Vec<GameRecord>,
+    /// Capacity of the shuffle buffer. `0` disables shuffling.
+    shuffle_buffer_size: usize,
+    /// Random number generator used for shuffling.
+    rng: StdRng,
+}
+
+impl GameDatasetStream {
+    /// Opens a stream over the dataset saved under `base_file_name`.
+    ///
+    /// Accepts the same file layout as `GameDataset::load_auto`: either a single
+    /// `{base_file_name}.bin` or consecutive `{base_file_name}_part_{n}.bin` files.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_file_name` - The base name for the input files.
+    /// * `batch_size` - The number of records per batch.
+    ///
+    /// # Returns
+    ///
+    /// A `std::io::Result<GameDatasetStream>`, or an error if no dataset files exist.
+    pub fn open(base_file_name: &str, batch_size: usize) -> std::io::Result<Self> {
+        assert!(batch_size > 0, "Batch size must be positive");
+
+        let mut pending_files = VecDeque::new();
+        let bin_file = format!("{}.bin", base_file_name);
+        if metadata(&bin_file).is_ok() {
+            pending_files.push_back(bin_file);
+        } else {
+            let mut part_num = 1;
+            loop {
+                let file_name = format!("{}_part_{}.bin", base_file_name, part_num);
+                if metadata(&file_name).is_err() {
+                    break;
+                }
+                pending_files.push_back(file_name);
+                part_num += 1;
+            }
+        }
+
+        if pending_files.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "No dataset files found",
+            ));
+        }
+
+        Ok(Self {
+            pending_files,
+            current: Vec::new().into_iter(),
+            batch_size,
+            shuffle_buffer: Vec::new(),
+            shuffle_buffer_size: 0,
+            rng: StdRng::seed_from_u64(0),
+        })
+    }
+
+    /// Enables an in-memory shuffle buffer.
+    ///
+    /// Records are read into a buffer of up to `size` records and drawn from it at
+    /// random. Larger buffers mix records from further apart in the files at the cost
+    /// of memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of records to buffer. `0` disables shuffling.
+    /// * `seed` - Seed for the shuffle order.
+    pub fn with_shuffle_buffer(mut self, size: usize, seed: u64) -> Self {
+        self.shuffle_buffer_size = size;
+        self.shuffle_buffer = Vec::with_capacity(size);
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Returns the next record in file order, opening the next file when needed.
+    fn next_record_in_order(&mut self) -> Option<std::io::Result<GameRecord>> {
+        loop {
+            if let Some(record) = self.current.next() {
+                return Some(Ok(record));
+            }
+
+            let file_name = self.pending_files.pop_front()?;
+            match GameDataset::load_bin(&file_name) {
+                Ok(dataset) => self.current = dataset.records.into_iter(),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+
+    /// Returns the next record, drawn from the shuffle buffer if it is enabled.
+    fn next_record(&mut self) -> Option<std::io::Result<GameRecord>> {
+        if self.shuffle_buffer_size == 0 {
+            return self.next_record_in_order();
+        }
+
+        while self.shuffle_buffer.len() < self.shuffle_buffer_size {
+            match self.next_record_in_order() {
+                Some(Ok(record)) => self.shuffle_buffer.push(record),
+                Some(Err(e)) => return Some(Err(e)),
+                None => break,
+            }
+        }
+
+        if self.shuffle_buffer.is_empty() {
+            return None;
+        }
+        let index = self.rng.gen_range(0..self.shuffle_buffer.len());
+        Some(Ok(self.shuffle_buffer.swap_remove(index)))
+    }
+}
+
+impl Iterator for GameDatasetStream {
+    type Item = std::io::Result<Vec<GameRecord>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch = Vec::with_capacity(self.batch_size);
+        while batch.len() < self.batch_size {
+            match self.next_record() {
+                Some(Ok(record)) => batch.push(record),
+                Some(Err(e)) => return Some(Err(e)),
+                None => break,
+            }
+        }
+
+        if batch.is_empty() {
+            None
+        } else {
+            Some(Ok(batch))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a dataset split into parts and returns its base file name.
+    fn write_parts(name: &str, part_sizes: &[usize]) -> String {
+        let base = std::env::temp_dir().join(format!("{}_{}", name, std::process::id()));
+        let base = base.to_str().unwrap().to_string();
+
+        let mut index = 0;
+        for (i, &size) in part_sizes.iter().enumerate() {
+            let mut dataset = GameDataset::new();
+            for _ in 0..size {
+                dataset.add_record(GameRecord {
+                    moves: vec![(index % 64) as u8, (index / 64) as u8],
+                    final_score: (32, 32),
+                });
+                index += 1;
+            }
+            dataset
+                .save_bin(&format!("{}_part_{}.bin", base, i + 1))
+                .unwrap();
+        }
+
+        base
+    }
+
+    fn remove_parts(base: &str, part_count: usize) {
+        for i in 0..part_count {
+            let _ = std::fs::remove_file(format!("{}_part_{}.bin", base, i + 1));
+        }
+    }
+
+    #[test]
+    fn test_stream_matches_load_auto() {
+        let part_sizes = [30, 25, 7];
+        let base = write_parts("temp_reversi_stream_test", &part_sizes);
+
+        let expected = GameDataset::load_auto(&base).unwrap();
+        let batches: Vec<Vec<GameRecord>> = GameDatasetStream::open(&base, 16)
+            .unwrap()
+            .map(|batch| batch.unwrap())
+            .collect();
+        remove_parts(&base, part_sizes.len());
+
+        let total: usize = batches.iter().map(|batch| batch.len()).sum();
+        assert_eq!(total, expected.len());
+        assert!(batches[..batches.len() - 1]
+            .iter()
+            .all(|batch| batch.len() == 16));
+
+        let streamed: Vec<Vec<u8>> = batches.into_iter().flatten().map(|r| r.moves).collect();
+        let loaded: Vec<Vec<u8>> = expected.records.into_iter().map(|r| r.moves).collect();
+        assert_eq!(streamed, loaded);
+    }
+
+    #[test]
+    fn test_stream_with_shuffle_buffer() {
+        let part_sizes = [40, 40];
+        let base = write_parts("temp_reversi_stream_shuffle_test", &part_sizes);
+
+        let collect = |seed| -> Vec<Vec<u8>> {
+            GameDatasetStream::open(&base, 10)
+                .unwrap()
+                .with_shuffle_buffer(32, seed)
+                .flat_map(|batch| batch.unwrap())
+                .map(|r| r.moves)
+                .collect()
+        };
+        let first = collect(1);
+        let second = collect(1);
+        let in_order: Vec<Vec<u8>> = GameDataset::load_auto(&base)
+            .unwrap()
+            .records
+            .into_iter()
+            .map(|r| r.moves)
+            .collect();
+        remove_parts(&base, part_sizes.len());
+
+        assert_eq!(first, second);
+        assert_ne!(first, in_order);
+
+        let mut sorted = first;
+        sorted.sort();
+        let mut expected = in_order;
+        expected.sort();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn test_open_missing_dataset() {
+        let base = std::env::temp_dir().join("temp_reversi_stream_missing");
+        assert!(GameDatasetStream::open(base.to_str().unwrap(), 8).is_err());
+    }
+}
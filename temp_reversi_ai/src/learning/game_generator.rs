@@ -4,7 +4,7 @@ use std::{
     path::Path,
 };
 
-use super::{GameDataset, GameRecord};
+use super::{GameDataset, GameRecord, ProgressReporter};
 use crate::{ai_decider::AiDecider, strategy::Strategy};
 use rayon::prelude::*;
 use temp_reversi_core::{Game, MoveDecider, Player};
@@ -15,6 +15,10 @@ use temp_reversi_core::{Game, MoveDecider, Player};
 /// - `num_games`: Number of self-play games to generate.
 /// - `black_strategy`: The strategy for the black player.
 /// - `white_strategy`: The strategy for the white player.
+/// - `seed`: Seed for randomized strategies. With a seed, each game reseeds the
+///   strategies from the seed and the game index, so the generated games are
+///   reproducible regardless of the thread scheduling.
+/// - `reporter`: Receives one step per finished game.
 ///
 /// # Returns
 /// - `GameDataset` containing generated game records.
@@ -22,13 +26,23 @@ pub fn generate_self_play_data(
     num_games: usize,
     black_strategy: Box<dyn Strategy>,
     white_strategy: Box<dyn Strategy>,
+    seed: Option<u64>,
+    reporter: &dyn ProgressReporter,
 ) -> GameDataset {
+    reporter.set_total(num_games);
     let records: Vec<GameRecord> = (0..num_games)
         .into_par_iter()
-        .map(|_| {
+        .map(|index| {
             let mut game = Game::default();
-            let mut black_ai = AiDecider::new(black_strategy.clone_box());
-            let mut white_ai = AiDecider::new(white_strategy.clone_box());
+            let mut black = black_strategy.clone_box();
+            let mut white = white_strategy.clone_box();
+            if let Some(seed) = seed {
+                let game_seed = seed.wrapping_add(2 * index as u64);
+                black.reseed(game_seed);
+                white.reseed(game_seed.wrapping_add(1));
+            }
+            let mut black_ai = AiDecider::new(black);
+            let mut white_ai = AiDecider::new(white);
 
             let mut moves: Vec<u8> = Vec::new();
 
@@ -47,6 +61,7 @@ pub fn generate_self_play_data(
                 }
             }
 
+            reporter.increment();
             let (black_score, white_score) = game.current_score();
             GameRecord {
                 moves,
@@ -54,6 +69,7 @@ pub fn generate_self_play_data(
             }
         })
         .collect();
+    reporter.finish();
 
     GameDataset { records }
 }
@@ -65,6 +81,8 @@ pub fn generate_self_play_data(
 /// - `black_strategy`: The strategy for the black player.
 /// - `white_strategy`: The strategy for the white player.
 /// - `dataset_path`: Path to save the generated dataset.
+/// - `seed`: Seed for randomized strategies, see [`generate_self_play_data`].
+/// - `reporter`: Receives one step per finished game.
 ///
 /// # Returns
 /// - `Result<(), String>` indicating success or error.
@@ -73,10 +91,13 @@ pub fn generate_and_save_self_play_data(
     black_strategy: Box<dyn Strategy>,
     white_strategy: Box<dyn Strategy>,
     dataset_path: &str,
+    seed: Option<u64>,
+    reporter: &dyn ProgressReporter,
 ) -> Result<(), String> {
     println!("🔄 Generating {} self-play games...", num_games);
 
-    let game_data = generate_self_play_data(num_games, black_strategy, white_strategy);
+    let game_data =
+        generate_self_play_data(num_games, black_strategy, white_strategy, seed, reporter);
     println!("✅ {} games generated.", game_data.len());
 
     // Ensure the parent directory exists
@@ -92,3 +113,30 @@ pub fn generate_and_save_self_play_data(
     println!("💾 Dataset saved to {}", dataset_path);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{learning::NoProgress, strategy::random::RandomStrategy};
+
+    #[test]
+    fn test_seeded_generation_is_reproducible() {
+        let generate = || {
+            generate_self_play_data(
+                8,
+                Box::new(RandomStrategy::new()),
+                Box::new(RandomStrategy::new()),
+                Some(7),
+                &NoProgress,
+            )
+        };
+        let first = generate();
+        let second = generate();
+
+        let moves = |dataset: &GameDataset| -> Vec<Vec<u8>> {
+            dataset.records.iter().map(|r| r.moves.clone()).collect()
+        };
+        assert_eq!(moves(&first), moves(&second));
+        assert_ne!(first.records[0].moves, first.records[1].moves);
+    }
+}
@@ -0,0 +1,26 @@
+/// Receives progress updates from long-running tasks such as self-play generation.
+///
+/// Methods take `&self` because the tasks report from several threads at once;
+/// implementations use interior mutability.
+pub trait ProgressReporter: Sync {
+    /// Sets the total number of steps of the task.
+    fn set_total(&self, total: usize);
+
+    /// Advances the progress by one step.
+    fn increment(&self);
+
+    /// Reports a message, such as a summary of the result.
+    fn message(&self, _message: &str) {}
+
+    /// Called once when the task has finished.
+    fn finish(&self) {}
+}
+
+/// A reporter that ignores all progress updates.
+pub struct NoProgress;
+
+impl ProgressReporter for NoProgress {
+    fn set_total(&self, _total: usize) {}
+
+    fn increment(&self) {}
+}
@@ -1,9 +1,9 @@
 use std::fs::File;
-use std::io::Read;
+use std::io::{BufWriter, Read, Write};
 use std::path::Path;
 
 use crate::evaluation::PhaseAwareEvaluator;
-use crate::learning::GameDataset;
+use crate::learning::{Dataset, GameDataset, NoProgress, ProgressReporter};
 use crate::strategy::negamax::NegamaxStrategy;
 
 use super::generate_and_save_self_play_data;
@@ -20,21 +20,102 @@ pub struct TrainingConfig {
     pub model_path: String,
     /// Path to save the generated game dataset.
     pub dataset_path: String,
+    /// Seed for randomized strategies during self-play, or `None` for a random run.
+    pub seed: Option<u64>,
+    /// Whether to also train on the 8 symmetric forms of each position.
+    ///
+    /// Multiplies the number of training samples by 8.
+    pub augment_symmetries: bool,
+    /// Whether to train on each position only once.
+    ///
+    /// Positions that were already sampled, directly or in a mirrored or rotated form,
+    /// are skipped, and the number skipped is reported through the pipeline's reporter.
+    pub deduplicate_positions: bool,
+    /// Path of the CSV file that receives the metrics of every epoch, or `None` to
+    /// keep them in memory only. See [`export_metrics_csv`] for the format.
+    pub metrics_path: Option<String>,
+}
+
+/// Metrics recorded at the end of a training epoch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EpochMetrics {
+    /// The epoch number.
+    pub epoch: usize,
+    /// Mean loss over the training data.
+    pub train_loss: f32,
+    /// Mean loss over the validation data.
+    pub val_loss: f32,
+    /// Learning rate used in the epoch.
+    pub lr: f32,
+    /// Mean absolute error of the predictions on the validation data.
+    pub mae: f32,
+}
+
+/// Writes training metrics to a CSV file.
+///
+/// The file starts with the header `epoch,train_loss,val_loss,lr,mae`, followed by
+/// one row per epoch.
+///
+/// # Arguments
+/// * `path` - Path of the CSV file to write.
+/// * `epochs` - The metrics of each epoch.
+pub fn export_metrics_csv(path: &str, epochs: &[EpochMetrics]) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "epoch,train_loss,val_loss,lr,mae")?;
+    for metrics in epochs {
+        writeln!(
+            writer,
+            "{},{},{},{},{}",
+            metrics.epoch, metrics.train_loss, metrics.val_loss, metrics.lr, metrics.mae
+        )?;
+    }
+    writer.flush()
 }
 
 /// Training pipeline for self-play data generation and model training.
 pub struct TrainingPipeline {
     config: TrainingConfig,
+    reporter: Box<dyn ProgressReporter>,
+    metrics: Vec<EpochMetrics>,
 }
 
 impl TrainingPipeline {
     /// Creates a new instance of the training pipeline.
+    ///
+    /// Progress is not reported until a reporter is set with [`Self::with_reporter`].
     pub fn new(config: TrainingConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            reporter: Box::new(NoProgress),
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Sets the reporter that receives one step per generated self-play game.
+    pub fn with_reporter(mut self, reporter: Box<dyn ProgressReporter>) -> Self {
+        self.reporter = reporter;
+        self
+    }
+
+    /// Returns the metrics recorded so far, one entry per epoch.
+    pub fn metrics(&self) -> &[EpochMetrics] {
+        &self.metrics
+    }
+
+    /// Records the metrics of a finished epoch.
+    ///
+    /// If `metrics_path` is set, the CSV file is rewritten with all epochs so far, so
+    /// it stays complete even if training is interrupted.
+    pub fn record_epoch(&mut self, metrics: EpochMetrics) -> std::io::Result<()> {
+        self.metrics.push(metrics);
+        match &self.config.metrics_path {
+            Some(path) => export_metrics_csv(path, &self.metrics),
+            None => Ok(()),
+        }
     }
 
     /// Executes the full training pipeline: generates self-play data and trains the model.
-    pub fn run(&self) {
+    pub fn run(&mut self) {
         self.generate_self_play_data();
         self.train();
     }
@@ -43,15 +124,17 @@ impl TrainingPipeline {
     pub fn generate_self_play_data(&self) {
         generate_and_save_self_play_data(
             self.config.num_games,
-            Box::new(NegamaxStrategy::new(PhaseAwareEvaluator, 5)),
-            Box::new(NegamaxStrategy::new(PhaseAwareEvaluator, 5)),
+            Box::new(NegamaxStrategy::new(PhaseAwareEvaluator::default(), 5)),
+            Box::new(NegamaxStrategy::new(PhaseAwareEvaluator::default(), 5)),
             &self.config.dataset_path,
+            self.config.seed,
+            self.reporter.as_ref(),
         )
         .expect("Failed to generate and save self-play data.");
     }
 
     /// Loads the dataset and trains the model.
-    pub fn train(&self) {
+    pub fn train(&mut self) {
         println!("📊 Loading dataset from {}", self.config.dataset_path);
 
         let dataset = self.load_dataset();
@@ -69,7 +152,7 @@ impl TrainingPipeline {
     }
 
     /// Trains the model using batches extracted from the dataset.
-    fn train_model(&self, dataset: GameDataset) {
+    fn train_model(&mut self, dataset: GameDataset) {
         todo!();
         /*
         let mut trainer = Trainer::new();
@@ -78,14 +161,48 @@ impl TrainingPipeline {
         for epoch in 0..self.config.num_epochs {
             println!("Epoch {}/{}", epoch + 1, self.config.num_epochs);
 
-            let batches = dataset.extract_training_data_in_batches(self.config.batch_size);
-            for batch in batches {
+            for batch in self.training_batches(&dataset) {
                 // trainer.train(&batch, 1); // Train with each batch for 1 epoch
             }
+
+            self.record_epoch(EpochMetrics {
+                epoch: epoch + 1,
+                train_loss: trainer.train_loss(),
+                val_loss: trainer.val_loss(),
+                lr: trainer.learning_rate(),
+                mae: trainer.val_mae(),
+            })
+            .expect("Failed to write the training metrics.");
         }
         */
     }
 
+    /// Extracts the training batches from the dataset as configured.
+    ///
+    /// With `deduplicate_positions`, the unique samples are split into batches of
+    /// `batch_size` samples. Otherwise each batch holds the positions of `batch_size`
+    /// records.
+    pub fn training_batches(&self, dataset: &GameDataset) -> Vec<Dataset> {
+        let batch_size = self.config.batch_size;
+        if !self.config.deduplicate_positions {
+            return dataset
+                .extract_training_data_in_batches(batch_size, self.config.augment_symmetries)
+                .collect();
+        }
+
+        let (unique, _) = dataset
+            .extract_unique_training_data(self.config.augment_symmetries, self.reporter.as_ref());
+        unique
+            .features
+            .chunks(batch_size)
+            .zip(unique.labels.chunks(batch_size))
+            .map(|(features, labels)| Dataset {
+                features: features.to_vec(),
+                labels: labels.to_vec(),
+            })
+            .collect()
+    }
+
     /// Saves the trained model to the specified path.
     fn save_model(&self) {
         if let Some(parent) = Path::new(&self.config.model_path).parent() {
@@ -100,3 +217,154 @@ impl TrainingPipeline {
         */
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+    };
+
+    use temp_reversi_core::Position;
+
+    use super::*;
+    use crate::learning::GameRecord;
+
+    #[test]
+    fn test_export_metrics_csv() {
+        let epochs = [
+            EpochMetrics {
+                epoch: 1,
+                train_loss: 0.8125,
+                val_loss: 0.9,
+                lr: 1e-3,
+                mae: 12.5,
+            },
+            EpochMetrics {
+                epoch: 2,
+                train_loss: 0.5,
+                val_loss: 0.6123,
+                lr: 5e-4,
+                mae: 9.75,
+            },
+        ];
+        let path = std::env::temp_dir().join(format!(
+            "temp_reversi_metrics_test_{}.csv",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        export_metrics_csv(path, &epochs).unwrap();
+        let content = fs::read_to_string(path).unwrap();
+        let _ = fs::remove_file(path);
+
+        let mut lines = content.lines();
+        assert_eq!(lines.next(), Some("epoch,train_loss,val_loss,lr,mae"));
+        let parsed: Vec<EpochMetrics> = lines
+            .map(|line| {
+                let fields: Vec<&str> = line.split(',').collect();
+                EpochMetrics {
+                    epoch: fields[0].parse().unwrap(),
+                    train_loss: fields[1].parse().unwrap(),
+                    val_loss: fields[2].parse().unwrap(),
+                    lr: fields[3].parse().unwrap(),
+                    mae: fields[4].parse().unwrap(),
+                }
+            })
+            .collect();
+        assert_eq!(parsed, epochs);
+    }
+
+    #[test]
+    fn test_record_epoch_exports_csv() {
+        let path = std::env::temp_dir().join(format!(
+            "temp_reversi_pipeline_metrics_test_{}.csv",
+            std::process::id()
+        ));
+        let mut pipeline = TrainingPipeline::new(TrainingConfig {
+            num_games: 0,
+            batch_size: 1,
+            num_epochs: 2,
+            model_path: String::new(),
+            dataset_path: String::new(),
+            seed: None,
+            augment_symmetries: false,
+            deduplicate_positions: false,
+            metrics_path: Some(path.to_str().unwrap().to_string()),
+        });
+
+        for epoch in 1..=2 {
+            let metrics = EpochMetrics {
+                epoch,
+                train_loss: 1.0 / epoch as f32,
+                val_loss: 2.0 / epoch as f32,
+                lr: 1e-3,
+                mae: 10.0,
+            };
+            pipeline.record_epoch(metrics).unwrap();
+
+            // The file is rewritten after every epoch.
+            let content = fs::read_to_string(&path).unwrap();
+            assert_eq!(content.lines().count(), 1 + epoch);
+        }
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(pipeline.metrics().len(), 2);
+        assert_eq!(pipeline.metrics()[1].val_loss, 1.0);
+    }
+
+    /// Reporter that records the reported messages.
+    #[derive(Default, Clone)]
+    struct MessageRecorder {
+        messages: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ProgressReporter for MessageRecorder {
+        fn set_total(&self, _total: usize) {}
+
+        fn increment(&self) {}
+
+        fn message(&self, message: &str) {
+            self.messages.lock().unwrap().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_training_batches_deduplicate_positions() {
+        // F5 D6 and D3 C5 are mirror images along the anti-diagonal.
+        let mut dataset = GameDataset::new();
+        for moves in [[Position::F5, Position::D6], [Position::D3, Position::C5]] {
+            dataset.add_record(GameRecord {
+                moves: moves.iter().map(|p| p.to_u8()).collect(),
+                final_score: (32, 32),
+            });
+        }
+
+        let reporter = MessageRecorder::default();
+        let pipeline = |deduplicate_positions: bool| {
+            TrainingPipeline::new(TrainingConfig {
+                num_games: 0,
+                batch_size: 1,
+                num_epochs: 1,
+                model_path: String::new(),
+                dataset_path: String::new(),
+                seed: None,
+                augment_symmetries: false,
+                deduplicate_positions,
+                metrics_path: None,
+            })
+            .with_reporter(Box::new(reporter.clone()))
+        };
+
+        let batches = pipeline(false).training_batches(&dataset);
+        assert_eq!(batches.iter().map(Dataset::len).sum::<usize>(), 4);
+        assert!(reporter.messages.lock().unwrap().is_empty());
+
+        let batches = pipeline(true).training_batches(&dataset);
+        assert_eq!(batches.len(), 2);
+        assert!(batches.iter().all(|batch| batch.len() == 1));
+        assert_eq!(
+            *reporter.messages.lock().unwrap(),
+            vec!["Skipped 2 duplicate positions, kept 2 unique positions".to_string()]
+        );
+    }
+}
@@ -1,8 +1,12 @@
 pub mod ai_decider;
+pub mod bench;
 pub mod evaluation;
 pub mod learning;
 pub mod patterns;
+pub mod plotter;
 pub mod strategy;
+pub mod test_match;
+pub mod test_utils;
 pub mod utils;
 
 #[cfg(test)]
@@ -1,2 +1,41 @@
+use temp_reversi_ai::{
+    bench::bench,
+    evaluation::{PhaseAwareEvaluator, PositionalEvaluator},
+    strategy::negamax::NegamaxStrategy,
+};
 
-fn main() {}
+/// Default number of games when no argument is given.
+const DEFAULT_NUM_GAMES: usize = 10;
+
+/// Default search depth when no argument is given.
+const DEFAULT_DEPTH: u32 = 5;
+
+/// Compares the phase-aware and positional evaluators.
+///
+/// Usage: `temp_reversi_ai [num_games] [depth]`
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let num_games = args
+        .first()
+        .map(|arg| {
+            arg.parse()
+                .expect("num_games must be a non-negative integer")
+        })
+        .unwrap_or(DEFAULT_NUM_GAMES);
+    let depth = args
+        .get(1)
+        .map(|arg| arg.parse().expect("depth must be a non-negative integer"))
+        .unwrap_or(DEFAULT_DEPTH);
+
+    let mut phase_aware = NegamaxStrategy::new(PhaseAwareEvaluator::default(), depth);
+    phase_aware.shuffle_moves = false;
+    let mut positional = NegamaxStrategy::new(PositionalEvaluator, depth);
+    positional.shuffle_moves = false;
+
+    println!(
+        "Searcher 1: phase-aware, searcher 2: positional ({} games, depth {})",
+        num_games, depth
+    );
+    let result = bench(&mut phase_aware, &mut positional, num_games, depth, 0);
+    println!("{}", result);
+}
@@ -5,10 +5,29 @@ use temp_reversi_core::{
 
 use super::pattern::Pattern;
 
+/// Maximum number of changed squares handled incrementally.
+///
+/// A single move places one stone and flips at most 18, so larger differences
+/// mean the boards are unrelated and the score is recomputed from scratch.
+const MAX_INCREMENTAL_CHANGED_SQUARES: u32 = 19;
+
+/// Cached state for incremental evaluation.
+///
+/// Only the state indices are cached. Scores are read from `state_scores` on every
+/// evaluation, so the cache holds for any phase and stays valid when scores change.
+#[derive(Debug, Clone)]
+struct IncrementalState {
+    /// The last evaluated board.
+    board: Bitboard,
+    /// State index of each pattern for `board`, if the masked state is known.
+    state_indices: Vec<Option<usize>>,
+}
+
 /// Represents a group of patterns sharing the same state scores.
 ///
 /// A `PatternGroup` contains multiple rotated `Pattern`s and a shared set of
 /// state scores indexed by phase and state.
+#[derive(Clone)]
 pub struct PatternGroup {
     /// Rotated patterns belonging to this group.
     pub patterns: Vec<Pattern>,
@@ -17,6 +36,8 @@ pub struct PatternGroup {
     pub state_scores: Vec<Vec<i32>>,
     /// Optional name for debugging or identification.
     pub name: Option<String>,
+    /// Cache used by `evaluate_score_incremental`.
+    incremental: Option<IncrementalState>,
 }
 
 impl PatternGroup {
@@ -46,6 +67,7 @@ impl PatternGroup {
             patterns: vec![base_pattern_obj, rotated_90, rotated_180, rotated_270],
             state_scores,
             name: name.map(|s| s.to_string()),
+            incremental: None,
         }
     }
 
@@ -72,13 +94,132 @@ impl PatternGroup {
 
         score
     }
+
+    /// Resets the incremental evaluation cache to the given board.
+    ///
+    /// # Arguments
+    /// * `board` - The board to evaluate from.
+    pub fn reset_incremental(&mut self, board: &Bitboard) {
+        let state_indices = self
+            .patterns
+            .iter()
+            .map(|pattern| Self::state_index(pattern, board))
+            .collect();
+
+        self.incremental = Some(IncrementalState {
+            board: board.clone(),
+            state_indices,
+        });
+    }
+
+    /// Evaluates the score contribution of this group, reusing the previous evaluation.
+    ///
+    /// Only patterns that overlap squares changed since the last call are looked up
+    /// again. If the boards differ by more than a single move could change, the cache
+    /// is rebuilt with a full recompute, so unrelated boards can be evaluated in any
+    /// order. The phase may differ between calls.
+    ///
+    /// # Arguments
+    /// * `board` - The current board state as a `Bitboard`.
+    /// * `phase` - Current game phase (0-59).
+    ///
+    /// # Returns
+    /// * `i32` - The score contribution of this pattern group.
+    pub fn evaluate_score_incremental(&mut self, board: &Bitboard, phase: usize) -> i32 {
+        let changed = match &self.incremental {
+            Some(cache) => {
+                let (black, white) = board.bits();
+                let (old_black, old_white) = cache.board.bits();
+                (black ^ old_black) | (white ^ old_white)
+            }
+            _ => u64::MAX,
+        };
+
+        if changed.count_ones() > MAX_INCREMENTAL_CHANGED_SQUARES {
+            self.reset_incremental(board);
+        } else if changed != 0 {
+            let cache = self.incremental.as_mut().unwrap();
+            for (pattern, cached_index) in self.patterns.iter().zip(&mut cache.state_indices) {
+                if pattern.mask & changed != 0 {
+                    *cached_index = Self::state_index(pattern, board);
+                }
+            }
+            cache.board = board.clone();
+        }
+
+        let scores = &self.state_scores[phase];
+        self.incremental
+            .as_ref()
+            .unwrap()
+            .state_indices
+            .iter()
+            .flatten()
+            .map(|&index| scores[index])
+            .sum()
+    }
+
+    /// Looks up the state index of a pattern for a board.
+    fn state_index(pattern: &Pattern, board: &Bitboard) -> Option<usize> {
+        let (black_mask, white_mask) = board.bits();
+        pattern
+            .key_to_index
+            .get(&(black_mask & pattern.mask, white_mask & pattern.mask))
+            .copied()
+    }
+
+    /// Returns the state indices of all patterns in this group for the given board state.
+    ///
+    /// # Arguments
+    /// * `board` - The current board state as a `Bitboard`.
+    ///
+    /// # Returns
+    /// * `Vec<usize>` - One state index per pattern (rotation) found in the board.
+    pub fn active_state_indices(&self, board: &Bitboard) -> Vec<usize> {
+        self.patterns
+            .iter()
+            .filter_map(|pattern| Self::state_index(pattern, board))
+            .collect()
+    }
+
+    /// Adds `delta` to the score of a state in the given phase.
+    ///
+    /// Because all rotations share `state_scores`, the update applies to every
+    /// rotation of the pattern at once.
+    ///
+    /// # Arguments
+    /// * `phase` - Game phase of the score to update.
+    /// * `state_index` - Index of the state to update.
+    /// * `delta` - Value added to the current score.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the score was updated.
+    /// * `Err(&str)` if `phase` or `state_index` is out of range.
+    pub fn update_score(
+        &mut self,
+        phase: usize,
+        state_index: usize,
+        delta: i32,
+    ) -> Result<(), &'static str> {
+        let score = self
+            .state_scores
+            .get_mut(phase)
+            .ok_or("Phase out of range")?
+            .get_mut(state_index)
+            .ok_or("State index out of range")?;
+        *score = score.saturating_add(delta);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use temp_reversi_core::utils::{rotate_mask_270_ccw, rotate_mask_90_ccw};
+    use temp_reversi_core::{
+        utils::{rotate_mask_270_ccw, rotate_mask_90_ccw},
+        Game, Position,
+    };
 
     use super::*;
+    use crate::test_utils::benchmark_positions;
 
     /// Tests that each rotated pattern's `key_to_index` is consistent with the base pattern.
     ///
@@ -148,6 +289,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_evaluate_score_incremental() {
+        let base_pattern: u64 = 0x0000000000070707;
+        let state_scores = vec![(0..3_i32.pow(9)).collect(); 2];
+        let mut pattern_group = PatternGroup::new(base_pattern, state_scores, None);
+
+        // Consecutive positions of a game are updated incrementally, with the phase
+        // changing on every ply as in a real game.
+        let mut game = Game::default();
+        pattern_group.reset_incremental(game.board_state());
+        for (ply, mv) in [
+            Position::F5,
+            Position::D6,
+            Position::C3,
+            Position::D3,
+            Position::C4,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            game.apply_move(mv).unwrap();
+            let board = game.board_state().clone();
+            let phase = ply % 2;
+            assert_eq!(
+                pattern_group.evaluate_score_incremental(&board, phase),
+                pattern_group.evaluate_score(&board, phase)
+            );
+        }
+
+        // Score updates are picked up without resetting the cache.
+        let board = game.board_state();
+        let state_index = pattern_group.active_state_indices(board)[0];
+        pattern_group.update_score(0, state_index, 5).unwrap();
+        assert_eq!(
+            pattern_group.evaluate_score_incremental(board, 0),
+            pattern_group.evaluate_score(board, 0)
+        );
+
+        // Alternating between unrelated boards still gives the correct absolute scores.
+        let boards = benchmark_positions();
+        for (i, board) in boards.iter().chain(boards.iter().rev()).enumerate() {
+            let phase = i % 2;
+            assert_eq!(
+                pattern_group.evaluate_score_incremental(board, phase),
+                pattern_group.evaluate_score(board, phase)
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_score_bounds() {
+        let base_pattern: u64 = 0x0000000000000007;
+        let state_scores = vec![vec![0; 3_usize.pow(3)]; 2];
+        let mut pattern_group = PatternGroup::new(base_pattern, state_scores, None);
+
+        assert!(pattern_group.update_score(1, 5, 3).is_ok());
+        assert_eq!(pattern_group.state_scores[1][5], 3);
+
+        assert!(pattern_group.update_score(2, 0, 1).is_err());
+        assert!(pattern_group.update_score(0, 27, 1).is_err());
+    }
+
     /// Tests that `evaluate_score()` produces consistent results across rotated versions of the same board.
     #[test]
     fn test_pattern_group_evaluate_score() {
@@ -0,0 +1,212 @@
+use std::ops::Range;
+
+use plotters::prelude::*;
+
+/// Width and height of the plotted images in pixels.
+const PLOT_SIZE: (u32, u32) = (800, 600);
+/// Width or height in pixels of the areas holding the axis tick labels.
+const LABEL_AREA_SIZE: u32 = 60;
+/// Maximum number of tick labels on the x-axis.
+const MAX_X_LABELS: usize = 10;
+
+/// Plots the win rate of each training generation as a line chart.
+///
+/// The generation is on the x-axis and the win rate, from 0 to 1, on the y-axis.
+/// A dashed line marks a win rate of 0.5, where a generation is even with the baseline.
+///
+/// # Arguments
+/// * `generations` - Pairs of generation number and win rate against the baseline.
+/// * `path` - Path of the PNG image to write.
+///
+/// # Returns
+/// - `Ok(())` if the image was written.
+/// - `Err(String)` if `generations` is empty or the image cannot be drawn or saved.
+pub fn plot_winrate(generations: &[(usize, f64)], path: &str) -> Result<(), String> {
+    let first = generations
+        .iter()
+        .map(|&(generation, _)| generation)
+        .min()
+        .ok_or("No generations to plot")?;
+    let last = generations
+        .iter()
+        .map(|&(generation, _)| generation)
+        .max()
+        .unwrap_or(first)
+        .max(first + 1);
+
+    let root = BitMapBackend::new(path, PLOT_SIZE).into_drawing_area();
+    root.fill(&WHITE).map_err(|e| e.to_string())?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(20)
+        .x_label_area_size(LABEL_AREA_SIZE)
+        .y_label_area_size(LABEL_AREA_SIZE)
+        .build_cartesian_2d(first as f64..last as f64, 0.0..1.0)
+        .map_err(|e| e.to_string())?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels((last - first + 1).min(MAX_X_LABELS))
+        .x_desc("Generation")
+        .y_desc("Win rate")
+        .x_label_formatter(&|generation| format!("{generation:.0}"))
+        .draw()
+        .map_err(|e| e.to_string())?;
+
+    chart
+        .draw_series(DashedLineSeries::new(
+            [(first as f64, 0.5), (last as f64, 0.5)],
+            5,
+            5,
+            BLACK.mix(0.5).into(),
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let mut points: Vec<(f64, f64)> = generations
+        .iter()
+        .map(|&(generation, win_rate)| (generation as f64, win_rate))
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    chart
+        .draw_series(LineSeries::new(
+            points.iter().copied(),
+            BLUE.stroke_width(2),
+        ))
+        .map_err(|e| e.to_string())?;
+    chart
+        .draw_series(
+            points
+                .iter()
+                .map(|&point| Circle::new(point, 3, BLUE.filled())),
+        )
+        .map_err(|e| e.to_string())?;
+
+    root.present().map_err(|e| e.to_string())
+}
+
+/// Plots the training loss and the learning rate of each epoch on one chart.
+///
+/// The epoch is on the x-axis. The loss is drawn in blue against the left y-axis and
+/// the learning rate in red against the right y-axis, so learning-rate drops can be
+/// matched with loss plateaus.
+///
+/// # Arguments
+/// * `losses` - The loss of each epoch.
+/// * `lrs` - The learning rate of each epoch.
+/// * `path` - Path of the PNG image to write.
+///
+/// # Returns
+/// - `Ok(())` if the image was written.
+/// - `Err(String)` if the series are empty or differ in length, or the image cannot be
+///   drawn or saved.
+pub fn plot_loss_and_lr(losses: &[f32], lrs: &[f32], path: &str) -> Result<(), String> {
+    if losses.len() != lrs.len() {
+        return Err(format!(
+            "Series lengths differ: {} losses, {} learning rates",
+            losses.len(),
+            lrs.len()
+        ));
+    }
+    if losses.is_empty() {
+        return Err("No epochs to plot".to_string());
+    }
+
+    let root = BitMapBackend::new(path, PLOT_SIZE).into_drawing_area();
+    root.fill(&WHITE).map_err(|e| e.to_string())?;
+
+    let epochs = 0.0..(losses.len().max(2) - 1) as f32;
+    let mut chart = ChartBuilder::on(&root)
+        .margin(20)
+        .x_label_area_size(LABEL_AREA_SIZE)
+        .y_label_area_size(LABEL_AREA_SIZE)
+        .right_y_label_area_size(LABEL_AREA_SIZE)
+        .build_cartesian_2d(epochs.clone(), value_range(losses))
+        .map_err(|e| e.to_string())?
+        .set_secondary_coord(epochs, value_range(lrs));
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(losses.len().min(MAX_X_LABELS))
+        .x_desc("Epoch")
+        .y_desc("Loss")
+        .x_label_formatter(&|epoch| format!("{epoch:.0}"))
+        .draw()
+        .map_err(|e| e.to_string())?;
+    chart
+        .configure_secondary_axes()
+        .y_desc("Learning rate")
+        .y_label_formatter(&|lr| format!("{lr:.1e}"))
+        .draw()
+        .map_err(|e| e.to_string())?;
+
+    chart
+        .draw_series(LineSeries::new(
+            losses
+                .iter()
+                .enumerate()
+                .map(|(epoch, &loss)| (epoch as f32, loss)),
+            BLUE.stroke_width(2),
+        ))
+        .map_err(|e| e.to_string())?;
+    chart
+        .draw_secondary_series(LineSeries::new(
+            lrs.iter()
+                .enumerate()
+                .map(|(epoch, &lr)| (epoch as f32, lr)),
+            RED.stroke_width(2),
+        ))
+        .map_err(|e| e.to_string())?;
+
+    root.present().map_err(|e| e.to_string())
+}
+
+/// Returns the range spanned by `values`, widened so that it is never empty.
+fn value_range(values: &[f32]) -> Range<f32> {
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let margin = ((max - min) * 0.05).max(f32::EPSILON.max(max.abs() * 0.05));
+    min - margin..max + margin
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_plot_winrate() {
+        let path = std::env::temp_dir().join(format!(
+            "temp_reversi_plot_winrate_test_{}.png",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        let generations = [(0, 0.5), (1, 0.55), (2, 0.62), (3, 0.6), (4, 0.71)];
+
+        plot_winrate(&generations, path).unwrap();
+        let bytes = fs::read(path).unwrap();
+        let _ = fs::remove_file(path);
+
+        assert!(bytes.starts_with(b"\x89PNG"));
+        assert!(plot_winrate(&[], path).is_err());
+    }
+
+    #[test]
+    fn test_plot_loss_and_lr() {
+        let path = std::env::temp_dir().join(format!(
+            "temp_reversi_plot_loss_and_lr_test_{}.png",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        let losses = [1.0, 0.7, 0.55, 0.5, 0.49, 0.4];
+        let lrs = [1e-3, 1e-3, 1e-3, 1e-3, 1e-4, 1e-4];
+
+        plot_loss_and_lr(&losses, &lrs, path).unwrap();
+        let bytes = fs::read(path).unwrap();
+        let _ = fs::remove_file(path);
+        assert!(bytes.starts_with(b"\x89PNG"));
+
+        assert!(plot_loss_and_lr(&losses, &lrs[..5], path).is_err());
+        assert!(plot_loss_and_lr(&[], &[], path).is_err());
+    }
+}
@@ -1,9 +1,60 @@
+pub mod endgame;
 pub mod negamax;
+pub mod opening_book;
 pub mod random;
+pub mod search;
 pub mod simple;
+pub mod softmax;
+pub mod transposition_table;
+
+use std::{
+    fmt,
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
 
 use temp_reversi_core::{Game, Position};
 
+/// Statistics of the last search performed by a strategy.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SearchStats {
+    /// Number of nodes visited.
+    pub visited_nodes: u64,
+    /// Number of transposition table probes that found an entry.
+    pub tt_hits: u64,
+    /// Wall-clock time spent searching.
+    pub elapsed: Duration,
+    /// Deepest fully completed search depth.
+    pub reached_depth: u32,
+}
+
+impl SearchStats {
+    /// Returns the search speed in nodes per second.
+    pub fn nodes_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.visited_nodes as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+impl fmt::Display for SearchStats {
+    /// Formats the statistics as a one-line summary.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "depth {} | nodes {} | tt hits {} | {:.3}s | {:.0} nps",
+            self.reached_depth,
+            self.visited_nodes,
+            self.tt_hits,
+            self.elapsed.as_secs_f64(),
+            self.nodes_per_second()
+        )
+    }
+}
+
 /// The `Strategy` trait defines the interface for different strategies.
 pub trait Strategy: Send + Sync {
     /// Evaluate the current game state and decide the next move.
@@ -15,8 +66,41 @@ pub trait Strategy: Send + Sync {
     /// * `Option<Position>` - The chosen position or `None` if no move is possible.
     fn evaluate_and_decide(&mut self, game: &Game) -> Option<Position>;
 
+    /// Decides the next move within a wall-clock time budget.
+    ///
+    /// Strategies with a timed search override this; the default ignores the budget
+    /// and calls [`Strategy::evaluate_and_decide`].
+    ///
+    /// # Arguments
+    /// * `game` - The current state of the game.
+    /// * `time_budget` - The time available for the move.
+    ///
+    /// # Returns
+    /// * `Option<Position>` - The chosen position or `None` if no move is possible.
+    fn decide_within(&mut self, game: &Game, _time_budget: Duration) -> Option<Position> {
+        self.evaluate_and_decide(game)
+    }
+
+    /// Searches a position ahead of time to warm up caches for the coming move.
+    ///
+    /// Called while the opponent is thinking, usually on a worker thread, with the
+    /// position expected after the opponent's reply. The search stops when
+    /// `time_budget` has elapsed or `stop` is set, whichever comes first. The default
+    /// does nothing, as strategies without caches gain nothing from pondering.
+    ///
+    /// # Arguments
+    /// * `game` - The expected position, with this strategy to move.
+    /// * `time_budget` - The maximum time to spend pondering.
+    /// * `stop` - Set by another thread to cancel pondering, e.g. when the opponent moves.
+    fn ponder(&mut self, _game: &Game, _time_budget: Duration, _stop: Arc<AtomicBool>) {}
+
     /// Clones the strategy as a `Box<dyn Strategy>`.
     fn clone_box(&self) -> Box<dyn Strategy>;
+
+    /// Reseeds the random number generator of a randomized strategy.
+    ///
+    /// Deterministic strategies ignore the seed, which is the default.
+    fn reseed(&mut self, _seed: u64) {}
 }
 
 /// Implements `Clone` for `Box<dyn Strategy>` to enable safe cloning.
@@ -25,3 +109,75 @@ impl Clone for Box<dyn Strategy> {
         self.clone_box()
     }
 }
+
+/// Common interface of the game tree searches, so they can be driven uniformly.
+pub trait Searcher {
+    /// Searches the best move with the searcher's configured depth.
+    ///
+    /// # Arguments
+    /// * `game` - The current game state.
+    ///
+    /// # Returns
+    /// * `Option<Position>` - The best move, or `None` if the player to move has none.
+    fn search_best_move(&mut self, game: &Game) -> Option<Position>;
+
+    /// Searches the best move to the given depth.
+    ///
+    /// Exact solvers always search to the end of the game and ignore `depth`. Among
+    /// root moves with equal scores, the one with the lowest [`Position::to_u8`] is
+    /// chosen, so the result does not depend on the search order.
+    ///
+    /// # Arguments
+    /// * `game` - The current game state.
+    /// * `depth` - The search depth in plies.
+    ///
+    /// # Returns
+    /// * `Option<Position>` - The best move, or `None` if the player to move has none.
+    fn search_best_move_at_depth(&mut self, game: &Game, depth: u32) -> Option<Position>;
+
+    /// Returns the number of nodes visited by the last search.
+    fn visited_nodes(&self) -> u64;
+}
+
+/// Returns `true` if `mv` wins a tie in score against the current best root move.
+///
+/// Ties are broken in favour of the lower [`Position::to_u8`]. To detect a tie under
+/// alpha-beta, such a move must be searched with a window one below the best score,
+/// as an exact tie otherwise fails low.
+pub(crate) fn wins_tie(mv: Position, best: Option<Position>) -> bool {
+    best.is_some_and(|best| mv.to_u8() < best.to_u8())
+}
+
+#[cfg(test)]
+mod tests {
+    use temp_reversi_core::{Bitboard, Player};
+
+    use super::*;
+    use crate::evaluation::SimpleEvaluator;
+    use endgame::EndgameSolver;
+    use negamax::NegamaxStrategy;
+
+    fn best_move_and_nodes(searcher: &mut dyn Searcher, game: &Game) -> (Option<Position>, u64) {
+        let mv = searcher.search_best_move_at_depth(game, 2);
+        (mv, searcher.visited_nodes())
+    }
+
+    #[test]
+    fn test_searchers_are_interchangeable() {
+        // Only A1 is empty, so every searcher must play it.
+        let black = Position::C1.to_bit();
+        let white = !(Position::A1.to_bit() | black);
+        let game = Game::new(Bitboard::new(black, white), Player::Black);
+
+        let mut searchers: Vec<Box<dyn Searcher>> = vec![
+            Box::new(NegamaxStrategy::new(SimpleEvaluator, 3)),
+            Box::new(EndgameSolver::default()),
+        ];
+        for searcher in &mut searchers {
+            let (mv, nodes) = best_move_and_nodes(searcher.as_mut(), &game);
+            assert_eq!(mv, Some(Position::A1));
+            assert!(nodes > 0);
+            assert_eq!(searcher.search_best_move(&game), Some(Position::A1));
+        }
+    }
+}
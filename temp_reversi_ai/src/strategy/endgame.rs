@@ -0,0 +1,352 @@
+use std::time::Instant;
+
+use temp_reversi_core::{Bitboard, Game, Player, Position};
+
+use super::{wins_tie, SearchStats, Searcher, Strategy};
+
+/// Default number of empty squares at or below which the solver is used.
+pub const DEFAULT_EMPTY_THRESHOLD: usize = 14;
+
+/// Exact endgame solver.
+///
+/// Searches to the end of the game with negamax and alpha-beta pruning and returns
+/// the exact final disc differential. Positions with two or fewer empty squares are
+/// resolved directly without further recursion.
+pub struct EndgameSolver {
+    /// Maximum number of empty squares for which the solver should be used.
+    pub empty_threshold: usize,
+    /// Number of nodes visited by the last call to `solve`.
+    pub visited_nodes: u64,
+    /// Statistics of the last call to `solve`.
+    last_stats: SearchStats,
+}
+
+impl Clone for EndgameSolver {
+    /// Clones the threshold; the clone starts without search statistics.
+    fn clone(&self) -> Self {
+        Self::new(self.empty_threshold)
+    }
+}
+
+impl Default for EndgameSolver {
+    fn default() -> Self {
+        Self::new(DEFAULT_EMPTY_THRESHOLD)
+    }
+}
+
+impl EndgameSolver {
+    /// Creates a new EndgameSolver.
+    ///
+    /// # Arguments
+    /// * `empty_threshold` - Maximum number of empty squares for which the solver
+    ///   is applicable. Solving time grows exponentially with this value.
+    pub fn new(empty_threshold: usize) -> Self {
+        Self {
+            empty_threshold,
+            visited_nodes: 0,
+            last_stats: SearchStats::default(),
+        }
+    }
+
+    /// Returns the statistics of the last call to `solve`.
+    ///
+    /// The reached depth is the number of empty squares, as the search always reaches
+    /// the end of the game.
+    pub fn last_stats(&self) -> SearchStats {
+        self.last_stats
+    }
+
+    /// Returns `true` if the board has few enough empty squares to be solved.
+    pub fn is_applicable(&self, board: &Bitboard) -> bool {
+        Self::empty_squares(board).count_ones() as usize <= self.empty_threshold
+    }
+
+    /// Solves the position exactly.
+    ///
+    /// # Arguments
+    /// * `board` - The board to solve.
+    /// * `player` - The player to move.
+    ///
+    /// # Returns
+    /// * `(i32, Option<Position>)` - The final disc differential from `player`'s perspective
+    ///   with perfect play, and the best move (`None` if `player` has to pass).
+    pub fn solve(&mut self, board: &Bitboard, player: Player) -> (i32, Option<Position>) {
+        let start = Instant::now();
+        self.visited_nodes = 1;
+
+        let mut best_score = -(64 + 1);
+        let mut best_move = None;
+        let mut alpha = -(64 + 1);
+        let beta = 64 + 1;
+
+        for mv in board.valid_moves(player) {
+            // Equal scores go to the lower position, which needs an exact tie score.
+            let tie_break = wins_tie(mv, best_move);
+            let child_alpha = if tie_break { alpha - 1 } else { alpha };
+            let child = Self::play(board, mv, player);
+            let score = -self.negamax(&child, player.opponent(), -beta, -child_alpha, false);
+            if score > best_score || (tie_break && score == best_score) {
+                best_score = score;
+                best_move = Some(mv);
+            }
+            alpha = alpha.max(score);
+        }
+
+        if best_move.is_none() {
+            best_score = self.negamax(board, player, alpha, beta, false);
+        }
+
+        self.last_stats = SearchStats {
+            visited_nodes: self.visited_nodes,
+            tt_hits: 0,
+            elapsed: start.elapsed(),
+            reached_depth: Self::empty_squares(board).count_ones(),
+        };
+
+        (best_score, best_move)
+    }
+
+    /// Negamax search to the end of the game.
+    ///
+    /// `passed` is `true` if the previous player had to pass.
+    fn negamax(
+        &mut self,
+        board: &Bitboard,
+        player: Player,
+        mut alpha: i32,
+        beta: i32,
+        passed: bool,
+    ) -> i32 {
+        self.visited_nodes += 1;
+
+        let empty = Self::empty_squares(board);
+        match empty.count_ones() {
+            0 => return Self::disc_difference(board, player),
+            1 => return self.solve_last_empty(board, player, empty),
+            2 => return self.solve_last_two_empties(board, player, empty),
+            _ => {}
+        }
+
+        let valid_moves = board.valid_moves(player);
+        if valid_moves.is_empty() {
+            if passed {
+                // Neither player can move.
+                return Self::disc_difference(board, player);
+            }
+            return -self.negamax(board, player.opponent(), -beta, -alpha, true);
+        }
+
+        let mut best_score = -(64 + 1);
+        for mv in valid_moves {
+            let child = Self::play(board, mv, player);
+            let score = -self.negamax(&child, player.opponent(), -beta, -alpha, false);
+            best_score = best_score.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break; // Beta cutoff
+            }
+        }
+
+        best_score
+    }
+
+    /// Resolves a position with exactly two empty squares without recursion.
+    fn solve_last_two_empties(&mut self, board: &Bitboard, player: Player, empty: u64) -> i32 {
+        let first = Position::from_bit(empty & empty.wrapping_neg()).unwrap();
+        let second = Position::from_bit(empty & (empty - 1)).unwrap();
+
+        // Best score for `mover` when it plays first and the other square remains.
+        let mut best_for = |mover: Player| -> Option<i32> {
+            let mut best: Option<i32> = None;
+            for (mv, rest) in [(first, second), (second, first)] {
+                let flips = board.flips(mv, mover);
+                if flips == 0 {
+                    continue;
+                }
+                let child = Self::apply(board, mv, flips, mover);
+                let score = -self.solve_last_empty(&child, mover.opponent(), rest.to_bit());
+                best = Some(best.map_or(score, |b: i32| b.max(score)));
+            }
+            best
+        };
+
+        if let Some(score) = best_for(player) {
+            return score;
+        }
+        if let Some(score) = best_for(player.opponent()) {
+            return -score;
+        }
+        Self::disc_difference(board, player)
+    }
+
+    /// Resolves a position with exactly one empty square without recursion.
+    fn solve_last_empty(&mut self, board: &Bitboard, player: Player, empty: u64) -> i32 {
+        self.visited_nodes += 1;
+        let mv = Position::from_bit(empty).unwrap();
+
+        for mover in [player, player.opponent()] {
+            let flips = board.flips(mv, mover);
+            if flips != 0 {
+                let child = Self::apply(board, mv, flips, mover);
+                return Self::disc_difference(&child, player);
+            }
+        }
+
+        Self::disc_difference(board, player)
+    }
+
+    /// Returns the bitmask of empty squares.
+    fn empty_squares(board: &Bitboard) -> u64 {
+        let (black, white) = board.bits();
+        !(black | white)
+    }
+
+    /// Returns the disc differential from `player`'s perspective.
+    fn disc_difference(board: &Bitboard, player: Player) -> i32 {
+        let (black_count, white_count) = board.count_stones();
+        let diff = black_count as i32 - white_count as i32;
+        match player {
+            Player::Black => diff,
+            Player::White => -diff,
+        }
+    }
+
+    /// Returns the board after `player` plays the valid move `mv`.
+    fn play(board: &Bitboard, mv: Position, player: Player) -> Bitboard {
+        Self::apply(board, mv, board.flips(mv, player), player)
+    }
+
+    /// Returns the board after placing a stone at `mv` and flipping `flips`.
+    fn apply(board: &Bitboard, mv: Position, flips: u64, player: Player) -> Bitboard {
+        let (black, white) = board.bits();
+        let placed = mv.to_bit() | flips;
+        match player {
+            Player::Black => Bitboard::new(black | placed, white & !flips),
+            Player::White => Bitboard::new(black & !flips, white | placed),
+        }
+    }
+}
+
+impl Strategy for EndgameSolver {
+    /// Solves the position if it is within the threshold.
+    ///
+    /// Returns `None` when the position has more empty squares than
+    /// `empty_threshold`, as solving it could take arbitrarily long. Use
+    /// [`crate::ai_decider::AiDecider::with_endgame_solver`] to fall back to a heuristic strategy.
+    fn evaluate_and_decide(&mut self, game: &Game) -> Option<Position> {
+        if !self.is_applicable(game.board_state()) {
+            return None;
+        }
+        self.search_best_move(game)
+    }
+
+    fn clone_box(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+}
+
+impl Searcher for EndgameSolver {
+    fn search_best_move(&mut self, game: &Game) -> Option<Position> {
+        let (_, best_move) = self.solve(game.board_state(), game.current_player());
+        best_move
+    }
+
+    /// Always solves to the end of the game; `depth` is ignored.
+    fn search_best_move_at_depth(&mut self, game: &Game, _depth: u32) -> Option<Position> {
+        self.search_best_move(game)
+    }
+
+    fn visited_nodes(&self) -> u64 {
+        self.visited_nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::benchmark_positions;
+
+    /// Plain minimax to the end of the game, used as a reference.
+    fn reference_solve(board: &Bitboard, player: Player, passed: bool) -> i32 {
+        let valid_moves = board.valid_moves(player);
+        if valid_moves.is_empty() {
+            if passed {
+                return EndgameSolver::disc_difference(board, player);
+            }
+            return -reference_solve(board, player.opponent(), true);
+        }
+
+        valid_moves
+            .into_iter()
+            .map(|mv| {
+                let mut child = board.clone();
+                child.apply_move(mv, player).unwrap();
+                -reference_solve(&child, player.opponent(), false)
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_solve_known_position() {
+        // Only A1 is empty. Black plays A1 and flips B1; nothing else can be flipped.
+        let black = Position::C1.to_bit();
+        let white = !(Position::A1.to_bit() | black);
+        let board = Bitboard::new(black, white);
+
+        let mut solver = EndgameSolver::default();
+        let (score, best_move) = solver.solve(&board, Player::Black);
+        assert_eq!(best_move, Some(Position::A1));
+        assert_eq!(score, 3 - 61);
+
+        let stats = solver.last_stats();
+        assert_eq!(stats.visited_nodes, solver.visited_nodes);
+        assert_eq!(stats.reached_depth, 1);
+    }
+
+    #[test]
+    fn test_strategy_only_within_threshold() {
+        let mut solver = EndgameSolver::default();
+
+        // The initial position has 60 empty squares, far above the threshold.
+        assert_eq!(solver.evaluate_and_decide(&Game::default()), None);
+
+        let black = Position::C1.to_bit();
+        let white = !(Position::A1.to_bit() | black);
+        let game = Game::new(Bitboard::new(black, white), Player::Black);
+        assert_eq!(solver.evaluate_and_decide(&game), Some(Position::A1));
+    }
+
+    #[test]
+    fn test_solve_prefers_lower_position_on_tie() {
+        // A1 and H8 are empty and mirror each other: each lets Black flip one disc,
+        // and Black takes the other square afterwards, so both moves score the same.
+        let black = Position::C1.to_bit() | Position::F8.to_bit();
+        let white = !(Position::A1.to_bit() | Position::H8.to_bit() | black);
+        let board = Bitboard::new(black, white);
+
+        let mut solver = EndgameSolver::default();
+        let (score, best_move) = solver.solve(&board, Player::Black);
+        assert_eq!(best_move, Some(Position::A1));
+        assert_eq!(score, reference_solve(&board, Player::Black, false));
+    }
+
+    #[test]
+    fn test_solve_matches_reference() {
+        let mut solver = EndgameSolver::new(10);
+
+        for board in benchmark_positions() {
+            if !solver.is_applicable(&board) {
+                continue;
+            }
+            for player in [Player::Black, Player::White] {
+                let (score, best_move) = solver.solve(&board, player);
+                assert_eq!(score, reference_solve(&board, player, false));
+
+                if let Some(mv) = best_move {
+                    assert!(board.valid_moves(player).contains(&mv));
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,23 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
 use crate::evaluation::EvaluationFunction;
 use rand::{seq::SliceRandom, thread_rng};
 use temp_reversi_core::{Bitboard, Game, Player, Position};
 
-use super::Strategy;
+use super::{
+    transposition_table::{Bound, TranspositionTable, TtEntry, DEFAULT_BRANCHING_FACTOR},
+    wins_tie, SearchStats, Searcher, Strategy,
+};
+
+/// Minimum number of empty squares for null-move pruning; closer to the end of the game,
+/// passing is often forced and the null-move assumption does not hold.
+const NULL_MOVE_MIN_EMPTIES: u32 = 20;
 
 /// Negamax-based strategy for decision making with alpha-beta pruning.
 ///
@@ -11,6 +26,44 @@ use super::Strategy;
 pub struct NegamaxStrategy<E: EvaluationFunction + Send + Sync> {
     pub depth: u32,   // The depth to search in the game tree.
     pub evaluator: E, // The evaluation function to use.
+    /// Whether to shuffle moves for variability.
+    pub shuffle_moves: bool,
+    /// Whether to try moves leaving the opponent fewer replies first.
+    ///
+    /// Killer and transposition table moves are still tried before the others.
+    pub mobility_ordering: bool,
+    /// Initial aspiration window half-width for iterative deepening, `None` to disable.
+    pub aspiration_delta: Option<i32>,
+    /// Number of nodes visited by the last search.
+    pub visited_nodes: u64,
+    /// Number of transposition table hits in the last search.
+    tt_hits: u64,
+    /// Statistics of the last completed search.
+    last_stats: SearchStats,
+    /// Moves that caused a beta cutoff, indexed by remaining depth.
+    killer_moves: Vec<[Option<Position>; 2]>,
+    /// Optional transposition table, kept across searches.
+    tt: Option<TranspositionTable>,
+    /// Depth reduction applied to the null-move search.
+    pub null_move_reduction: u32,
+    /// Whether null-move pruning is enabled.
+    null_move: bool,
+    /// Maximum number of single-reply extensions along one search path.
+    pub max_extensions: u32,
+    /// Whether single-reply extensions are enabled.
+    single_reply_extension: bool,
+    /// Number of single-reply extensions on the current search path.
+    extensions: u32,
+    /// Maximum number of plies searched past the horizon by quiescence search, 0 to disable.
+    pub quiescence_depth: u32,
+    /// Quiescence search only tries moves flipping more than this many discs.
+    pub quiescence_min_flips: u32,
+    /// Time at which a timed search is aborted.
+    deadline: Option<Instant>,
+    /// Whether the current search was aborted by the deadline or the stop flag.
+    timed_out: bool,
+    /// Flag set by another thread to abort the current search.
+    stop: Option<Arc<AtomicBool>>,
 }
 
 impl<E: EvaluationFunction + Send + Sync> NegamaxStrategy<E> {
@@ -20,64 +73,645 @@ impl<E: EvaluationFunction + Send + Sync> NegamaxStrategy<E> {
     /// * `evaluator` - The evaluation function to score board states.
     /// * `depth` - The maximum depth of the search tree.
     pub fn new(evaluator: E, depth: u32) -> Self {
-        Self { depth, evaluator }
+        Self {
+            depth,
+            evaluator,
+            shuffle_moves: true,
+            mobility_ordering: false,
+            aspiration_delta: None,
+            visited_nodes: 0,
+            tt_hits: 0,
+            last_stats: SearchStats::default(),
+            killer_moves: Vec::new(),
+            tt: None,
+            null_move_reduction: 2,
+            null_move: false,
+            max_extensions: 4,
+            single_reply_extension: false,
+            extensions: 0,
+            quiescence_depth: 0,
+            quiescence_min_flips: 4,
+            deadline: None,
+            timed_out: false,
+            stop: None,
+        }
+    }
+
+    /// Creates a new NegamaxStrategy with a transposition table sized for `depth`.
+    ///
+    /// The capacity is estimated with [`DEFAULT_BRANCHING_FACTOR`]; use
+    /// [`Self::with_tt_capacity`] to choose it explicitly.
+    ///
+    /// # Arguments
+    /// * `evaluator` - The evaluation function to score board states.
+    /// * `depth` - The maximum depth of the search tree.
+    pub fn with_tt(evaluator: E, depth: u32) -> Self {
+        let capacity = TranspositionTable::capacity_for_depth(depth, DEFAULT_BRANCHING_FACTOR);
+        Self::with_tt_capacity(evaluator, depth, capacity)
+    }
+
+    /// Creates a new NegamaxStrategy with a bounded transposition table.
+    ///
+    /// # Arguments
+    /// * `evaluator` - The evaluation function to score board states.
+    /// * `depth` - The maximum depth of the search tree.
+    /// * `capacity` - The maximum number of transposition table entries.
+    pub fn with_tt_capacity(evaluator: E, depth: u32, capacity: usize) -> Self {
+        Self {
+            tt: Some(TranspositionTable::new(capacity)),
+            ..Self::new(evaluator, depth)
+        }
+    }
+
+    /// Enables or disables null-move pruning.
+    ///
+    /// When enabled, the search lets the side to move pass voluntarily and searches the
+    /// result with the depth reduced by `null_move_reduction`. If even that beats beta,
+    /// the node is pruned. This is only done when the side to move has a legal move,
+    /// outside the endgame, and not twice in a row.
+    pub fn enable_null_move(&mut self, enable: bool) {
+        self.null_move = enable;
+    }
+
+    /// Enables or disables single-reply extensions.
+    ///
+    /// When enabled, a move that is the only legal reply does not consume depth, so
+    /// forced lines are searched past the nominal horizon. At most `max_extensions`
+    /// extensions are applied along one path. Forced passes never consume depth, and
+    /// two passes in a row end the game, so they cannot extend the search indefinitely.
+    pub fn enable_single_reply_extension(&mut self, enable: bool) {
+        self.single_reply_extension = enable;
+    }
+
+    /// Returns the statistics of the last search.
+    pub fn last_stats(&self) -> SearchStats {
+        self.last_stats
+    }
+
+    /// Returns whether the last search was cut short by the time budget or the stop flag.
+    pub fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// Returns the number of entries in the transposition table (0 if there is none).
+    pub fn tt_len(&self) -> usize {
+        self.tt.as_ref().map_or(0, |tt| tt.len())
+    }
+
+    /// Selects a move by iterative deepening up to `self.depth`.
+    ///
+    /// # Arguments
+    /// * `game` - The current game state.
+    ///
+    /// # Returns
+    /// * `Option<Position>` - The selected move, or `None` if no valid move exists.
+    pub fn search_best_move_iterative(&mut self, game: &Game) -> Option<Position> {
+        self.iterative_deepening(game, self.depth, None)
+    }
+
+    /// Selects a move by iterative deepening within a time budget.
+    ///
+    /// The search deepens one ply at a time until the budget is exhausted or the end
+    /// of the game is reached. The clock is checked between root children and inside
+    /// the search; a depth that does not complete in time is discarded, and the best
+    /// move of the last fully completed depth is returned.
+    ///
+    /// # Arguments
+    /// * `game` - The current game state.
+    /// * `time_budget` - The time available for the move.
+    ///
+    /// # Returns
+    /// * `Option<Position>` - The selected move, or `None` if no valid move exists.
+    pub fn search_best_move_timed(
+        &mut self,
+        game: &Game,
+        time_budget: Duration,
+    ) -> Option<Position> {
+        let (black_count, white_count) = game.current_score();
+        let max_depth = (64 - black_count - white_count) as u32;
+        self.iterative_deepening(game, max_depth, Some(Instant::now() + time_budget))
+    }
+
+    /// Scores every valid move with a full-window search to `self.depth`.
+    ///
+    /// Unlike the best-move search, no root move is cut off, so each score is exact at
+    /// the searched depth. This is slower and meant for analysis.
+    ///
+    /// # Arguments
+    /// * `game` - The current game state.
+    ///
+    /// # Returns
+    /// * `Vec<(Position, i32)>` - The valid moves with their scores, best first.
+    pub fn evaluate_moves(&mut self, game: &Game) -> Vec<(Position, i32)> {
+        self.score_root_moves(game, self.depth)
+    }
+
+    /// Returns the `n` best moves with their scores (multi-PV search).
+    ///
+    /// Every root move is searched with a full window, as in [`Self::evaluate_moves`],
+    /// so this is slower than the single best-move search, which prunes root moves
+    /// that cannot beat the best one.
+    ///
+    /// # Arguments
+    /// * `game` - The current game state.
+    /// * `depth` - The search depth in plies.
+    /// * `n` - The maximum number of moves to return.
+    ///
+    /// # Returns
+    /// * `Vec<(Position, i32)>` - Up to `n` moves with their scores, best first.
+    pub fn search_best_moves(&mut self, game: &Game, depth: u32, n: usize) -> Vec<(Position, i32)> {
+        let mut scored = self.score_root_moves(game, depth);
+        scored.truncate(n);
+        scored
+    }
+
+    /// Scores every valid move with a full-window search, best first.
+    fn score_root_moves(&mut self, game: &Game, depth: u32) -> Vec<(Position, i32)> {
+        let board = game.board_state();
+        let player = game.current_player();
+
+        let start = Instant::now();
+        self.reset_search();
+        let mut scored: Vec<(Position, i32)> = board
+            .valid_moves(player)
+            .into_iter()
+            .filter_map(|mv| self.search_root(board, player, &[mv], depth, i32::MIN + 1, i32::MAX))
+            .collect();
+        self.record_stats(start, depth);
+
+        scored.sort_by_key(|&(mv, score)| (std::cmp::Reverse(score), mv.to_u8()));
+        scored
+    }
+
+    /// Deepens the search one ply at a time up to `max_depth` or until `deadline`.
+    ///
+    /// When `aspiration_delta` is set, every depth after the first is searched with a
+    /// window around the previous score, widened and re-searched on fail-low or fail-high.
+    fn iterative_deepening(
+        &mut self,
+        game: &Game,
+        max_depth: u32,
+        deadline: Option<Instant>,
+    ) -> Option<Position> {
+        let board = game.board_state();
+        let player = game.current_player();
+
+        let mut valid_moves = board.valid_moves(player);
+        if self.shuffle_moves {
+            valid_moves.shuffle(&mut thread_rng());
+        }
+        // Fall back to any valid move if not even depth 1 completes in time.
+        let mut best_move = valid_moves.first().copied();
+        let mut previous_score: Option<i32> = None;
+        let mut reached_depth = 0;
+
+        let start = Instant::now();
+        self.reset_search();
+        self.deadline = deadline;
+        'deepening: for depth in 1..=max_depth.max(1) {
+            let (mut alpha, mut beta) = match (previous_score, self.aspiration_delta) {
+                (Some(score), Some(delta)) => (
+                    score.saturating_sub(delta).max(i32::MIN + 1),
+                    score.saturating_add(delta),
+                ),
+                _ => (i32::MIN + 1, i32::MAX),
+            };
+
+            let result = loop {
+                let Some((mv, score)) =
+                    self.search_root(board, player, &valid_moves, depth, alpha, beta)
+                else {
+                    break 'deepening; // Timed out; discard the partial result.
+                };
+
+                // On fail-low or fail-high, open the failing side of the window and search again.
+                if score <= alpha && alpha > i32::MIN + 1 {
+                    alpha = i32::MIN + 1;
+                } else if score >= beta && beta < i32::MAX {
+                    beta = i32::MAX;
+                } else {
+                    break (mv, score);
+                }
+            };
+
+            best_move = Some(result.0);
+            previous_score = Some(result.1);
+            reached_depth = depth;
+
+            // Search the best move first at the next depth.
+            if let Some(index) = valid_moves.iter().position(|&mv| Some(mv) == best_move) {
+                valid_moves[..=index].rotate_right(1);
+            }
+        }
+        self.deadline = None;
+        self.record_stats(start, reached_depth);
+
+        best_move
+    }
+
+    /// Resets the per-search counters and move ordering state.
+    fn reset_search(&mut self) {
+        self.visited_nodes = 0;
+        self.tt_hits = 0;
+        self.killer_moves.clear();
+    }
+
+    /// Records the statistics of a search that started at `start`.
+    fn record_stats(&mut self, start: Instant, reached_depth: u32) {
+        self.last_stats = SearchStats {
+            visited_nodes: self.visited_nodes,
+            tt_hits: self.tt_hits,
+            elapsed: start.elapsed(),
+            reached_depth,
+        };
+    }
+
+    /// Searches all root moves to the given depth within the window `(alpha, beta)`.
+    ///
+    /// # Returns
+    /// * `Option<(Position, i32)>` - The best move and its score, or `None` if the search
+    ///   timed out or there is no valid move.
+    fn search_root(
+        &mut self,
+        board: &Bitboard,
+        player: Player,
+        valid_moves: &[Position],
+        depth: u32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> Option<(Position, i32)> {
+        let mut best = None;
+        let mut best_score = i32::MIN + 1;
+        let hash = board.zobrist_hash(player);
+        self.timed_out = false;
+
+        for &mv in valid_moves {
+            if self.is_time_up() {
+                return None;
+            }
+
+            // Unless shuffling for variety, equal scores go to the lower position.
+            let tie_break = !self.shuffle_moves && wins_tie(mv, best);
+            let child_alpha = if tie_break {
+                alpha.saturating_sub(1).max(i32::MIN + 1)
+            } else {
+                alpha
+            };
+
+            let child_hash = Bitboard::zobrist_update(hash, mv, board.flips(mv, player), player);
+            let mut new_board = board.clone();
+            new_board.apply_move(mv, player).unwrap();
+            let score = -self.negamax(
+                &new_board,
+                child_hash,
+                depth - 1,
+                -beta,
+                -child_alpha,
+                player.opponent(),
+                false,
+            );
+            if self.timed_out {
+                return None;
+            }
+            if score > best_score || best.is_none() || (tie_break && score == best_score) {
+                best_score = score;
+                best = Some(mv);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break; // Beta cutoff
+            }
+        }
+
+        best.map(|mv| (mv, best_score))
+    }
+
+    /// Moves the killer moves recorded for `depth` to the front, keeping the order of the rest.
+    fn order_moves(&self, moves: &mut [Position], depth: u32) {
+        let Some(killers) = self.killer_moves.get(depth as usize) else {
+            return;
+        };
+
+        let mut front = 0;
+        for killer in killers.iter().flatten() {
+            if let Some(index) = moves[front..].iter().position(|mv| mv == killer) {
+                moves[front..=front + index].rotate_right(1);
+                front += 1;
+            }
+        }
+    }
+
+    /// Sorts moves by the number of replies they leave the opponent, fewest first.
+    ///
+    /// Restricting the opponent's mobility is a cheap and strong heuristic in Reversi,
+    /// so trying those moves first tends to produce earlier cutoffs. The sort is
+    /// stable, so moves with equal mobility keep their order.
+    fn order_by_mobility(board: &Bitboard, player: Player, moves: &mut [Position]) {
+        moves.sort_by_cached_key(|&mv| {
+            let mut child = board.clone();
+            child.apply_move(mv, player).unwrap();
+            child.valid_moves_count(player.opponent())
+        });
+    }
+
+    /// Records a move that caused a beta cutoff at `depth`.
+    ///
+    /// Two killers are kept per depth; the most recent one is tried first.
+    fn record_killer(&mut self, depth: u32, mv: Position) {
+        let depth = depth as usize;
+        if self.killer_moves.len() <= depth {
+            self.killer_moves.resize(depth + 1, [None, None]);
+        }
+
+        let killers = &mut self.killer_moves[depth];
+        if killers[0] != Some(mv) {
+            killers[1] = killers[0];
+            killers[0] = Some(mv);
+        }
+    }
+
+    /// Returns the number of empty squares on the board.
+    fn empty_count(board: &Bitboard) -> u32 {
+        let (black_count, white_count) = board.count_stones();
+        (64 - black_count - white_count) as u32
+    }
+
+    /// Searches moves that flip many discs past the horizon until the position is quiet.
+    ///
+    /// The side to move may stand pat on the static evaluation or play a move flipping
+    /// more than `quiescence_min_flips` discs. This avoids horizon effects where a large
+    /// capture is just beyond the search depth.
+    ///
+    /// # Arguments
+    /// * `board` - The board state at the horizon.
+    /// * `depth` - The remaining quiescence depth.
+    /// * `alpha` - The lower bound of the search window.
+    /// * `beta` - The upper bound of the search window.
+    /// * `player` - The player to move.
+    ///
+    /// # Returns
+    /// * `i32` - The score of the board for `player`.
+    fn quiescence(
+        &mut self,
+        board: &Bitboard,
+        depth: u32,
+        mut alpha: i32,
+        beta: i32,
+        player: Player,
+    ) -> i32 {
+        let stand_pat = self.evaluator.evaluate(board, player);
+        if depth == 0 || stand_pat >= beta {
+            return stand_pat;
+        }
+        alpha = alpha.max(stand_pat);
+
+        let mut max_eval = stand_pat;
+        for mv in board.valid_moves(player) {
+            let flips = board.flips(mv, player);
+            if flips.count_ones() <= self.quiescence_min_flips {
+                continue;
+            }
+
+            self.visited_nodes += 1;
+            let mut new_board = board.clone();
+            new_board.apply_move(mv, player).unwrap();
+            let eval = -self.quiescence(&new_board, depth - 1, -beta, -alpha, player.opponent());
+            max_eval = max_eval.max(eval);
+            alpha = alpha.max(eval);
+            if alpha >= beta {
+                break;
+            }
+        }
+        max_eval
+    }
+
+    /// Checks the deadline and the stop flag, and records whether the search must end.
+    fn is_time_up(&mut self) -> bool {
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                self.timed_out = true;
+            }
+        }
+        if let Some(stop) = &self.stop {
+            if stop.load(Ordering::Relaxed) {
+                self.timed_out = true;
+            }
+        }
+        self.timed_out
     }
 
     /// Negamax recursive function with alpha-beta pruning.
     ///
     /// # Arguments
     /// * `board` - Current state of the board.
+    /// * `hash` - Zobrist hash of `board` with `player` to move.
     /// * `depth` - Remaining depth to search.
     /// * `alpha` - Current best score for the maximizing player.
     /// * `beta` - Current best score for the minimizing player.
     /// * `player` - The current player making the move.
+    /// * `after_pass` - Whether the previous move was a pass, so that null-move pruning
+    ///   does not pass twice in a row.
     ///
     /// # Returns
     /// * `i32` - The score of the board.
     ///
     /// This function shuffles the valid moves to add stochasticity, which helps
     /// avoid deterministic behavior in symmetrical board states.
+    #[allow(clippy::too_many_arguments)]
     fn negamax(
         &mut self,
         board: &Bitboard,
+        hash: u64,
         depth: u32,
         mut alpha: i32,
-        beta: i32,
+        mut beta: i32,
         player: Player,
+        after_pass: bool,
     ) -> i32 {
+        self.visited_nodes += 1;
+
+        // Abort the search once the deadline of a timed search has passed
+        if self.is_time_up() {
+            return 0;
+        }
+
         // Base case: Leaf node or depth limit reached
-        if depth == 0 || board.is_game_over() {
-            let score = self.evaluator.evaluate(board, player);
-            return score;
+        if board.is_game_over() {
+            return self.evaluator.evaluate(board, player);
+        }
+        if depth == 0 {
+            return self.quiescence(board, self.quiescence_depth, alpha, beta, player);
+        }
+
+        // Use a stored result when it was searched at least as deep
+        let alpha_orig = alpha;
+        let mut tt_move = None;
+        if let Some(entry) = self.tt.as_ref().and_then(|tt| tt.probe(hash)) {
+            self.tt_hits += 1;
+            tt_move = entry.best_move;
+            if entry.depth >= depth {
+                match entry.bound {
+                    Bound::Exact => return entry.score,
+                    Bound::Lower => alpha = alpha.max(entry.score),
+                    Bound::Upper => beta = beta.min(entry.score),
+                }
+                if alpha >= beta {
+                    return entry.score;
+                }
+            }
+        }
+
+        // Null-move pruning: if passing already beats beta, a real move will too
+        if self.null_move
+            && !after_pass
+            && beta < i32::MAX
+            && depth > self.null_move_reduction
+            && Self::empty_count(board) >= NULL_MOVE_MIN_EMPTIES
+            && board.valid_moves_count(player) > 0
+        {
+            let score = -self.negamax(
+                board,
+                Bitboard::zobrist_pass(hash),
+                depth - 1 - self.null_move_reduction,
+                -beta,
+                -beta + 1,
+                player.opponent(),
+                true,
+            );
+
+            if score >= beta && !self.timed_out {
+                return score;
+            }
         }
 
         let mut max_eval = std::i32::MIN + 1;
+        let mut best_move = None;
         let mut valid_moves = board.valid_moves(player);
 
+        // The game is not over, so the opponent can move after a forced pass.
+        // The pass does not consume depth.
+        if valid_moves.is_empty() {
+            return -self.negamax(
+                board,
+                Bitboard::zobrist_pass(hash),
+                depth,
+                -beta,
+                -alpha,
+                player.opponent(),
+                true,
+            );
+        }
+
         // Shuffle the moves to introduce randomness
-        valid_moves.shuffle(&mut thread_rng());
+        if self.shuffle_moves {
+            valid_moves.shuffle(&mut thread_rng());
+        }
+
+        if self.mobility_ordering {
+            Self::order_by_mobility(board, player, &mut valid_moves);
+        }
+
+        // Try moves that caused cutoffs at this depth first, and the stored best move before them
+        self.order_moves(&mut valid_moves, depth);
+        if let Some(index) = valid_moves.iter().position(|&mv| Some(mv) == tt_move) {
+            valid_moves[..=index].rotate_right(1);
+        }
+
+        // A single reply is forced, so searching it does not consume depth
+        let extend = self.single_reply_extension
+            && valid_moves.len() == 1
+            && self.extensions < self.max_extensions;
+        let child_depth = if extend { depth } else { depth - 1 };
+        if extend {
+            self.extensions += 1;
+        }
 
         for mv in valid_moves {
+            let child_hash = Bitboard::zobrist_update(hash, mv, board.flips(mv, player), player);
             let mut new_board = board.clone();
             let r = new_board.apply_move(mv, player);
             if let Err(_) = r {
                 println!("{new_board}");
                 panic!();
             }
-            let eval = -self.negamax(&new_board, depth - 1, -beta, -alpha, player.opponent());
-            max_eval = max_eval.max(eval);
+            let eval = -self.negamax(
+                &new_board,
+                child_hash,
+                child_depth,
+                -beta,
+                -alpha,
+                player.opponent(),
+                false,
+            );
+            if eval > max_eval {
+                max_eval = eval;
+                best_move = Some(mv);
+            }
             alpha = alpha.max(eval);
             if alpha >= beta {
+                self.record_killer(depth, mv);
                 break; // Beta cutoff
             }
         }
+
+        if extend {
+            self.extensions -= 1;
+        }
+
+        // Results of an aborted search are incomplete and must not be stored
+        if !self.timed_out {
+            if let Some(tt) = self.tt.as_mut() {
+                let bound = if max_eval <= alpha_orig {
+                    Bound::Upper
+                } else if max_eval >= beta {
+                    Bound::Lower
+                } else {
+                    Bound::Exact
+                };
+                tt.store(TtEntry {
+                    hash,
+                    depth,
+                    score: max_eval,
+                    bound,
+                    best_move,
+                });
+            }
+        }
+
         max_eval
     }
 }
 
+impl<E> Clone for NegamaxStrategy<E>
+where
+    E: EvaluationFunction + Send + Sync + Clone,
+{
+    /// Clones the evaluator and search settings.
+    ///
+    /// The clone gets an empty transposition table of the same capacity and no
+    /// search state, so it can search independently of the original.
+    fn clone(&self) -> Self {
+        let evaluator = self.evaluator.clone();
+        let mut clone = match &self.tt {
+            Some(tt) => Self::with_tt_capacity(evaluator, self.depth, tt.capacity()),
+            None => Self::new(evaluator, self.depth),
+        };
+        clone.shuffle_moves = self.shuffle_moves;
+        clone.mobility_ordering = self.mobility_ordering;
+        clone.aspiration_delta = self.aspiration_delta;
+        clone.null_move_reduction = self.null_move_reduction;
+        clone.null_move = self.null_move;
+        clone.max_extensions = self.max_extensions;
+        clone.single_reply_extension = self.single_reply_extension;
+        clone.quiescence_depth = self.quiescence_depth;
+        clone.quiescence_min_flips = self.quiescence_min_flips;
+        clone
+    }
+}
+
 impl<E> Strategy for NegamaxStrategy<E>
 where
-    E: EvaluationFunction + Send + Sync,
+    E: EvaluationFunction + Send + Sync + Clone + 'static,
 {
     /// Evaluates the game state and selects the best move using the Negamax algorithm.
     ///
@@ -89,44 +723,75 @@ where
     ///
     /// This method ensures randomness in decision-making by shuffling valid moves.
     fn evaluate_and_decide(&mut self, game: &Game) -> Option<Position> {
-        let mut best_move = None;
-        let mut best_score = std::i32::MIN + 1;
-        let mut alpha = std::i32::MIN + 1;
-        let beta = std::i32::MAX;
         let board = game.board_state();
         let player = game.current_player();
 
         let mut valid_moves = board.valid_moves(player);
-        valid_moves.shuffle(&mut thread_rng()); // Shuffle moves for variability
-
-        for &mv in &valid_moves {
-            let mut new_board = board.clone();
-            new_board.apply_move(mv, player).unwrap();
-            let score = -self.negamax(&new_board, self.depth - 1, -beta, -alpha, player.opponent());
-            if score > best_score {
-                best_score = score;
-                best_move = Some(mv);
-            }
-            alpha = alpha.max(score);
+        if self.shuffle_moves {
+            valid_moves.shuffle(&mut thread_rng()); // Shuffle moves for variability
         }
 
-        if best_move.is_none() && !valid_moves.is_empty() {
-            best_move = Some(valid_moves.first().unwrap().clone());
-        }
+        let start = Instant::now();
+        self.reset_search();
+        let result = self.search_root(
+            board,
+            player,
+            &valid_moves,
+            self.depth,
+            i32::MIN + 1,
+            i32::MAX,
+        );
+        self.record_stats(start, self.depth);
 
-        best_move
+        result.map(|(mv, _)| mv)
+    }
+
+    /// Searches by iterative deepening until the budget is used up.
+    fn decide_within(&mut self, game: &Game, time_budget: Duration) -> Option<Position> {
+        self.search_best_move_timed(game, time_budget)
+    }
+
+    /// Fills the transposition table with a timed search of the expected position.
+    ///
+    /// The search is aborted like a timed-out one as soon as `stop` is set. Without a
+    /// transposition table nothing would be kept, so no search is done.
+    fn ponder(&mut self, game: &Game, time_budget: Duration, stop: Arc<AtomicBool>) {
+        if self.tt.is_some() {
+            self.stop = Some(stop);
+            self.search_best_move_timed(game, time_budget);
+            self.stop = None;
+        }
     }
 
     fn clone_box(&self) -> Box<dyn Strategy> {
-        todo!()
+        Box::new(self.clone())
+    }
+}
+
+impl<E> Searcher for NegamaxStrategy<E>
+where
+    E: EvaluationFunction + Send + Sync,
+{
+    /// Searches by iterative deepening up to `self.depth`.
+    fn search_best_move(&mut self, game: &Game) -> Option<Position> {
+        self.search_best_move_iterative(game)
+    }
+
+    fn search_best_move_at_depth(&mut self, game: &Game, depth: u32) -> Option<Position> {
+        self.iterative_deepening(game, depth, None)
+    }
+
+    fn visited_nodes(&self) -> u64 {
+        self.visited_nodes
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::evaluation::{PhaseAwareEvaluator, SimpleEvaluator};
+    use crate::evaluation::{PhaseAwareEvaluator, PositionalEvaluator, SimpleEvaluator};
 
     use super::*;
+    use crate::test_utils::benchmark_positions;
     use temp_reversi_cli::cli_display;
     use temp_reversi_core::{run_game, Game, MoveDecider};
 
@@ -143,6 +808,535 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_negamax_on_benchmark_positions() {
+        let mut strategy = NegamaxStrategy::new(SimpleEvaluator, 2);
+
+        for board in benchmark_positions() {
+            for player in [Player::Black, Player::White] {
+                let valid_moves = board.valid_moves(player);
+                let game = Game::new(board.clone(), player);
+                let move_option = strategy.evaluate_and_decide(&game);
+
+                match move_option {
+                    Some(mv) => assert!(valid_moves.contains(&mv)),
+                    None => assert!(valid_moves.is_empty()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_best_move_timed() {
+        let game = Game::default();
+        let mut strategy = NegamaxStrategy::new(SimpleEvaluator, 1);
+
+        let budget = Duration::from_millis(50);
+        let move_option = strategy.search_best_move_timed(&game, budget);
+
+        // The game cannot be searched to the end in the budget, so the clock stops it.
+        assert!(game.valid_moves().contains(&move_option.unwrap()));
+        assert!(strategy.timed_out);
+        assert!(strategy.last_stats().reached_depth < 60);
+    }
+
+    #[test]
+    fn test_search_best_move_timed_zero_budget() {
+        // Even when no depth completes, a valid move is returned.
+        let game = Game::default();
+        let mut strategy = NegamaxStrategy::new(SimpleEvaluator, 1);
+
+        let move_option = strategy.search_best_move_timed(&game, Duration::ZERO);
+        assert!(game.valid_moves().contains(&move_option.unwrap()));
+    }
+
+    #[test]
+    fn test_aspiration_windows() {
+        let mut total_plain = 0;
+        let mut total_aspiration = 0;
+
+        for board in benchmark_positions() {
+            for player in [Player::Black, Player::White] {
+                if board.valid_moves(player).is_empty() {
+                    continue;
+                }
+                let game = Game::new(board.clone(), player);
+
+                let mut plain = NegamaxStrategy::new(PositionalEvaluator, 4);
+                plain.shuffle_moves = false;
+                let plain_move = plain.search_best_move_iterative(&game);
+
+                let mut aspiration = NegamaxStrategy::new(PositionalEvaluator, 4);
+                aspiration.shuffle_moves = false;
+                aspiration.aspiration_delta = Some(32);
+                let aspiration_move = aspiration.search_best_move_iterative(&game);
+
+                assert_eq!(plain_move, aspiration_move);
+                total_plain += plain.visited_nodes;
+                total_aspiration += aspiration.visited_nodes;
+            }
+        }
+
+        assert!(total_aspiration < total_plain);
+    }
+
+    #[test]
+    fn test_killer_move_ordering() {
+        let mut strategy = NegamaxStrategy::new(SimpleEvaluator, 3);
+        let mut moves = vec![Position::D3, Position::C4, Position::F5, Position::E6];
+
+        // Without killers, the order is unchanged.
+        strategy.order_moves(&mut moves, 2);
+        assert_eq!(
+            moves,
+            vec![Position::D3, Position::C4, Position::F5, Position::E6]
+        );
+
+        // A move that caused a cutoff at depth 2 is tried before its siblings at that depth.
+        strategy.record_killer(2, Position::F5);
+        strategy.order_moves(&mut moves, 2);
+        assert_eq!(
+            moves,
+            vec![Position::F5, Position::D3, Position::C4, Position::E6]
+        );
+
+        // The newest killer comes first, followed by the previous one.
+        strategy.record_killer(2, Position::E6);
+        strategy.order_moves(&mut moves, 2);
+        assert_eq!(
+            moves,
+            vec![Position::E6, Position::F5, Position::D3, Position::C4]
+        );
+
+        // Killers are kept per depth.
+        let mut other = vec![Position::D3, Position::F5];
+        strategy.order_moves(&mut other, 1);
+        assert_eq!(other, vec![Position::D3, Position::F5]);
+    }
+
+    #[test]
+    fn test_mobility_ordering() {
+        let mut game = Game::default();
+        game.apply_moves(&[Position::F5, Position::D6, Position::C3])
+            .unwrap();
+        let board = game.board_state();
+        let player = game.current_player();
+
+        let opponent_mobility = |mv: Position| {
+            let mut child = board.clone();
+            child.apply_move(mv, player).unwrap();
+            child.valid_moves_count(player.opponent())
+        };
+
+        let mut moves = board.valid_moves(player);
+        let (low, high) = (
+            *moves
+                .iter()
+                .min_by_key(|&&mv| opponent_mobility(mv))
+                .unwrap(),
+            *moves
+                .iter()
+                .max_by_key(|&&mv| opponent_mobility(mv))
+                .unwrap(),
+        );
+        assert!(opponent_mobility(low) < opponent_mobility(high));
+
+        // Put the high-mobility move first; ordering must move the low-mobility one ahead.
+        let high_index = moves.iter().position(|&mv| mv == high).unwrap();
+        moves.swap(0, high_index);
+        NegamaxStrategy::<SimpleEvaluator>::order_by_mobility(board, player, &mut moves);
+
+        let position = |mv| moves.iter().position(|&m| m == mv).unwrap();
+        assert!(position(low) < position(high));
+        assert!(moves
+            .windows(2)
+            .all(|pair| opponent_mobility(pair[0]) <= opponent_mobility(pair[1])));
+
+        // The search result does not depend on the ordering.
+        let mut plain = NegamaxStrategy::new(PositionalEvaluator, 4);
+        plain.shuffle_moves = false;
+        let mut ordered = NegamaxStrategy::new(PositionalEvaluator, 4);
+        ordered.shuffle_moves = false;
+        ordered.mobility_ordering = true;
+        let full = (i32::MIN + 1, i32::MAX);
+        for board in benchmark_positions() {
+            for player in [Player::Black, Player::White] {
+                let valid_moves = board.valid_moves(player);
+                assert_eq!(
+                    plain
+                        .search_root(&board, player, &valid_moves, 4, full.0, full.1)
+                        .map(|(_, score)| score),
+                    ordered
+                        .search_root(&board, player, &valid_moves, 4, full.0, full.1)
+                        .map(|(_, score)| score)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_forced_pass_is_not_terminal() {
+        let mut found = false;
+        for board in benchmark_positions() {
+            for player in [Player::Black, Player::White] {
+                if !board.valid_moves(player).is_empty() || board.is_game_over() {
+                    continue;
+                }
+                found = true;
+
+                // A forced pass hands the move to the opponent at the same depth.
+                let mut strategy = NegamaxStrategy::new(SimpleEvaluator, 3);
+                strategy.shuffle_moves = false;
+                let hash = board.zobrist_hash(player);
+                let score =
+                    strategy.negamax(&board, hash, 2, i32::MIN + 1, i32::MAX, player, false);
+                let opponent_score = strategy.negamax(
+                    &board,
+                    Bitboard::zobrist_pass(hash),
+                    2,
+                    i32::MIN + 1,
+                    i32::MAX,
+                    player.opponent(),
+                    true,
+                );
+                assert_eq!(score, -opponent_score);
+                assert!(score > i32::MIN + 1);
+            }
+        }
+        assert!(found, "The benchmark positions include a forced pass");
+
+        // A full board is a genuine terminal and is evaluated directly.
+        let full = Bitboard::new(0x00000000ffffffff, 0xffffffff00000000);
+        let mut strategy = NegamaxStrategy::new(SimpleEvaluator, 3);
+        let hash = full.zobrist_hash(Player::Black);
+        assert_eq!(
+            strategy.negamax(&full, hash, 3, i32::MIN + 1, i32::MAX, Player::Black, false),
+            0
+        );
+        assert_eq!(strategy.visited_nodes, 1);
+    }
+
+    #[test]
+    fn test_ponder_warms_transposition_table() {
+        let mut game = Game::default();
+        game.apply_move(Position::F5).unwrap();
+
+        // A depth-2 search probes the table only at the root children, which cannot
+        // transpose into each other, so a cold search has no hits.
+        let mut cold = NegamaxStrategy::with_tt_capacity(PositionalEvaluator, 2, 1 << 16);
+        cold.evaluate_and_decide(&game);
+        assert_eq!(cold.last_stats().tt_hits, 0);
+
+        let mut warm = NegamaxStrategy::with_tt_capacity(PositionalEvaluator, 2, 1 << 16);
+        warm.ponder(&game, Duration::from_millis(100), Arc::default());
+        assert!(warm.tt_len() > 0);
+        warm.evaluate_and_decide(&game);
+        assert!(warm.last_stats().tt_hits > 0);
+
+        // Without a transposition table, pondering does not search.
+        let mut plain = NegamaxStrategy::new(PositionalEvaluator, 2);
+        plain.ponder(&game, Duration::from_millis(100), Arc::default());
+        assert_eq!(plain.last_stats().visited_nodes, 0);
+    }
+
+    #[test]
+    fn test_ponder_stops_on_flag() {
+        let game = Game::default();
+        let mut strategy = NegamaxStrategy::with_tt_capacity(PositionalEvaluator, 60, 1 << 16);
+
+        // With the flag already set, not even depth 1 completes.
+        strategy.ponder(
+            &game,
+            Duration::from_secs(60),
+            Arc::new(AtomicBool::new(true)),
+        );
+        assert_eq!(strategy.last_stats().reached_depth, 0);
+        assert!(strategy.stop.is_none());
+    }
+
+    #[test]
+    fn test_equal_scores_prefer_lower_position() {
+        // The four opening moves are symmetric, so they all score the same.
+        let game = Game::default();
+        let mut strategy = NegamaxStrategy::new(PositionalEvaluator, 3);
+        strategy.shuffle_moves = false;
+        let scores = strategy.evaluate_moves(&game);
+        assert!(scores.iter().all(|&(_, score)| score == scores[0].1));
+        let lowest = *game
+            .valid_moves()
+            .iter()
+            .min_by_key(|mv| mv.to_u8())
+            .unwrap();
+        assert_eq!(scores[0].0, lowest);
+
+        // The choice does not depend on the order in which the moves are searched.
+        let board = game.board_state();
+        let mut moves = game.valid_moves();
+        for _ in 0..moves.len() {
+            moves.rotate_left(1);
+            let (mv, _) = strategy
+                .search_root(
+                    board,
+                    game.current_player(),
+                    &moves,
+                    3,
+                    i32::MIN + 1,
+                    i32::MAX,
+                )
+                .unwrap();
+            assert_eq!(mv, lowest);
+        }
+        assert_eq!(strategy.evaluate_and_decide(&game), Some(lowest));
+    }
+
+    #[test]
+    fn test_previous_best_move_first_reduces_nodes() {
+        let depth = 5;
+        let mut with_reuse = 0;
+        let mut without_reuse = 0;
+
+        for board in benchmark_positions() {
+            let player = Player::Black;
+            let moves = board.valid_moves(player);
+            if moves.is_empty() {
+                continue;
+            }
+            let game = Game::new(board.clone(), player);
+
+            let mut strategy = NegamaxStrategy::new(PositionalEvaluator, depth);
+            strategy.shuffle_moves = false;
+            strategy.search_best_move_iterative(&game);
+            with_reuse += strategy.visited_nodes;
+
+            // The same deepening loop, always searching the root moves in generation order.
+            let mut strategy = NegamaxStrategy::new(PositionalEvaluator, depth);
+            strategy.shuffle_moves = false;
+            strategy.reset_search();
+            for d in 1..=depth {
+                strategy.search_root(&board, player, &moves, d, i32::MIN + 1, i32::MAX);
+            }
+            without_reuse += strategy.visited_nodes;
+        }
+
+        assert!(
+            with_reuse < without_reuse,
+            "{with_reuse} nodes with reuse, {without_reuse} without"
+        );
+    }
+
+    #[test]
+    fn test_with_tt_preallocates_for_depth() {
+        let strategy = NegamaxStrategy::with_tt(PositionalEvaluator, 6);
+        let capacity = strategy.tt.as_ref().unwrap().capacity();
+        assert_eq!(
+            capacity,
+            TranspositionTable::capacity_for_depth(6, DEFAULT_BRANCHING_FACTOR)
+        );
+        assert_eq!(strategy.tt_len(), 0);
+    }
+
+    #[test]
+    fn test_transposition_table() {
+        let capacity = 256;
+
+        for board in benchmark_positions() {
+            for player in [Player::Black, Player::White] {
+                let valid_moves = board.valid_moves(player);
+                if valid_moves.is_empty() {
+                    continue;
+                }
+
+                let mut plain = NegamaxStrategy::new(PositionalEvaluator, 4);
+                plain.shuffle_moves = false;
+                let expected =
+                    plain.search_root(&board, player, &valid_moves, 4, i32::MIN + 1, i32::MAX);
+
+                let mut with_tt =
+                    NegamaxStrategy::with_tt_capacity(PositionalEvaluator, 4, capacity);
+                with_tt.shuffle_moves = false;
+                let actual =
+                    with_tt.search_root(&board, player, &valid_moves, 4, i32::MIN + 1, i32::MAX);
+
+                // The table does not change the search result and stays within its capacity.
+                assert_eq!(
+                    expected.map(|(_, score)| score),
+                    actual.map(|(_, score)| score)
+                );
+                assert!(with_tt.tt_len() <= capacity);
+            }
+        }
+    }
+
+    #[test]
+    fn test_null_move_pruning() {
+        let mut plain_nodes = 0;
+        let mut null_move_nodes = 0;
+
+        for board in benchmark_positions() {
+            let player = Player::Black;
+            if board.valid_moves(player).is_empty() {
+                continue;
+            }
+            let game = Game::new(board, player);
+
+            let mut plain = NegamaxStrategy::new(PositionalEvaluator, 6);
+            plain.shuffle_moves = false;
+            let plain_move = plain.search_best_move_iterative(&game);
+            plain_nodes += plain.visited_nodes;
+
+            let mut null_move = NegamaxStrategy::new(PositionalEvaluator, 6);
+            null_move.shuffle_moves = false;
+            null_move.enable_null_move(true);
+            let null_move_move = null_move.search_best_move_iterative(&game);
+            null_move_nodes += null_move.visited_nodes;
+
+            assert_eq!(plain_move, null_move_move);
+        }
+
+        assert!(
+            null_move_nodes < plain_nodes,
+            "{null_move_nodes} nodes with null moves, {plain_nodes} without"
+        );
+    }
+
+    #[test]
+    fn test_null_move_allowed_deeper_in_null_subtree() {
+        let mut game = Game::default();
+        game.apply_moves(&[Position::F5, Position::D6, Position::C3, Position::D3])
+            .unwrap();
+        let board = game.board_state();
+        let player = game.current_player();
+        let hash = board.zobrist_hash(player);
+
+        // Search as if the previous move was a null move: no null move at this node,
+        // but the nodes below may still try one.
+        let mut plain = NegamaxStrategy::new(PositionalEvaluator, 7);
+        plain.shuffle_moves = false;
+        let plain_score = plain.negamax(board, hash, 7, -1, 0, player, true);
+
+        let mut null_move = NegamaxStrategy::new(PositionalEvaluator, 7);
+        null_move.shuffle_moves = false;
+        null_move.enable_null_move(true);
+        let null_move_score = null_move.negamax(board, hash, 7, -1, 0, player, true);
+
+        assert_eq!(plain_score >= 0, null_move_score >= 0);
+        assert!(null_move.visited_nodes < plain.visited_nodes);
+    }
+
+    #[test]
+    fn test_single_reply_extension() {
+        let mut found = false;
+        for board in benchmark_positions() {
+            for player in [Player::Black, Player::White] {
+                if board.valid_moves_count(player) != 1 {
+                    continue;
+                }
+                found = true;
+                let hash = board.zobrist_hash(player);
+
+                // With one extension, the forced move is searched one ply past the horizon.
+                let mut extended = NegamaxStrategy::new(SimpleEvaluator, 3);
+                extended.shuffle_moves = false;
+                extended.enable_single_reply_extension(true);
+                extended.max_extensions = 1;
+                let extended_score =
+                    extended.negamax(&board, hash, 1, i32::MIN + 1, i32::MAX, player, false);
+
+                let mut deeper = NegamaxStrategy::new(SimpleEvaluator, 3);
+                deeper.shuffle_moves = false;
+                let deeper_score =
+                    deeper.negamax(&board, hash, 2, i32::MIN + 1, i32::MAX, player, false);
+
+                assert_eq!(extended_score, deeper_score);
+                assert!(extended.visited_nodes > 2);
+                assert_eq!(extended.extensions, 0);
+            }
+        }
+        assert!(
+            found,
+            "The benchmark positions include a single-reply position"
+        );
+    }
+
+    #[test]
+    fn test_quiescence_search() {
+        // Black can flip the whole first row by playing H1.
+        let board = Bitboard::new(0x01, 0x7e);
+        let hash = board.zobrist_hash(Player::Black);
+
+        let mut plain = NegamaxStrategy::new(SimpleEvaluator, 1);
+        assert_eq!(
+            plain.negamax(
+                &board,
+                hash,
+                0,
+                i32::MIN + 1,
+                i32::MAX,
+                Player::Black,
+                false
+            ),
+            -5
+        );
+
+        let mut quiescence = NegamaxStrategy::new(SimpleEvaluator, 1);
+        quiescence.quiescence_depth = 2;
+        assert_eq!(
+            quiescence.negamax(
+                &board,
+                hash,
+                0,
+                i32::MIN + 1,
+                i32::MAX,
+                Player::Black,
+                false
+            ),
+            8
+        );
+
+        // Captures of at most `quiescence_min_flips` discs are not searched.
+        quiescence.quiescence_min_flips = 6;
+        assert_eq!(
+            quiescence.negamax(
+                &board,
+                hash,
+                0,
+                i32::MIN + 1,
+                i32::MAX,
+                Player::Black,
+                false
+            ),
+            -5
+        );
+    }
+
+    #[test]
+    fn test_clone_box() {
+        let mut strategy = NegamaxStrategy::with_tt_capacity(PositionalEvaluator, 3, 1024);
+        strategy.shuffle_moves = false;
+        strategy.enable_null_move(true);
+        let expected = strategy.evaluate_and_decide(&Game::default());
+
+        let mut clone = strategy.clone_box();
+        assert_eq!(clone.evaluate_and_decide(&Game::default()), expected);
+    }
+
+    #[test]
+    fn test_last_stats() {
+        let game = Game::default();
+        let mut strategy = NegamaxStrategy::with_tt_capacity(PositionalEvaluator, 4, 1024);
+
+        strategy.evaluate_and_decide(&game);
+        let stats = strategy.last_stats();
+        assert_eq!(stats.visited_nodes, strategy.visited_nodes);
+        assert_eq!(stats.reached_depth, 4);
+
+        strategy.search_best_move_iterative(&game);
+        let stats = strategy.last_stats();
+        assert_eq!(stats.visited_nodes, strategy.visited_nodes);
+        assert_eq!(stats.reached_depth, 4);
+        assert!(stats.tt_hits > 0);
+    }
+
     /// A wrapper to use NegamaxStrategy with MoveDecider trait.
     pub struct NegamaxMoveDecider {
         strategy: NegamaxStrategy<PhaseAwareEvaluator>,
@@ -150,7 +1344,7 @@ mod tests {
 
     impl NegamaxMoveDecider {
         pub fn new(depth: u32) -> Self {
-            let evaluator = PhaseAwareEvaluator;
+            let evaluator = PhaseAwareEvaluator::default();
             let strategy = NegamaxStrategy::new(evaluator, depth);
             Self { strategy }
         }
@@ -170,8 +1364,44 @@ mod tests {
 
         // Run the game
         match run_game(black_player, white_player, cli_display) {
-            Ok(()) => println!("Game over!"),
+            Ok(outcome) => println!("Game over! {}", outcome),
             Err(err) => eprintln!("Error: {}", err),
         }
     }
+
+    #[test]
+    fn test_search_best_moves_returns_top_n() {
+        let mut game = Game::default();
+        game.apply_moves(&[Position::F5, Position::D6, Position::C3])
+            .unwrap();
+        let player = game.current_player();
+
+        // At depth 1 a move scores the evaluation of the resulting board.
+        let evaluator = PositionalEvaluator;
+        let mut expected: Vec<i32> = game
+            .valid_moves()
+            .into_iter()
+            .map(|mv| {
+                let mut board = game.board_state().clone();
+                board.apply_move(mv, player).unwrap();
+                evaluator.evaluate(&board, player)
+            })
+            .collect();
+        expected.sort_by_key(|&score| std::cmp::Reverse(score));
+
+        let mut strategy = NegamaxStrategy::new(evaluator, 3);
+        let top = strategy.search_best_moves(&game, 1, 2);
+        assert_eq!(
+            top.iter().map(|&(_, score)| score).collect::<Vec<_>>(),
+            expected[..2]
+        );
+        for (mv, score) in top {
+            let mut board = game.board_state().clone();
+            board.apply_move(mv, player).unwrap();
+            assert_eq!(score, PositionalEvaluator.evaluate(&board, player));
+        }
+
+        let all = strategy.search_best_moves(&game, 1, usize::MAX);
+        assert_eq!(all.len(), expected.len());
+    }
 }
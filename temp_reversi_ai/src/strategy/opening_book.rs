@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs};
+use temp_reversi_core::{
+    utils::{inverse_transform_mask, transform_mask},
+    Bitboard, Game, Player, Position,
+};
+
+/// An opening book mapping positions to recommended moves.
+///
+/// Positions are stored in a canonical orientation, so rotated or mirrored
+/// openings share the same entry.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OpeningBook {
+    /// Recommended moves (board indices 0-63, canonical orientation) keyed by
+    /// the Zobrist hash of the canonical position.
+    entries: HashMap<u64, u8>,
+}
+
+impl OpeningBook {
+    /// Creates an empty opening book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of booked positions.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the book has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds a recommended move for a position.
+    ///
+    /// # Arguments
+    /// * `board` - The board state.
+    /// * `player` - The player to move.
+    /// * `mv` - The recommended move.
+    pub fn insert(&mut self, board: &Bitboard, player: Player, mv: Position) {
+        let (hash, transform) = Self::canonical_key(board, player);
+        let canonical_move = Position::from_bit(transform_mask(mv.to_bit(), transform)).unwrap();
+        self.entries.insert(hash, canonical_move.to_u8());
+    }
+
+    /// Adds every position of an opening line, with the next move of the line as its
+    /// recommended move.
+    ///
+    /// # Returns
+    /// `Err(&str)` if the line contains an invalid move; the positions before it are kept.
+    pub fn insert_line(&mut self, moves: &[Position]) -> Result<(), &'static str> {
+        let mut game = Game::default();
+        for &mv in moves {
+            self.insert(game.board_state(), game.current_player(), mv);
+            game.apply_move(mv)?;
+        }
+        Ok(())
+    }
+
+    /// Looks up the recommended move for a position.
+    ///
+    /// # Returns
+    /// The recommended move in the orientation of `board`, or `None` if the position is not booked.
+    pub fn lookup(&self, board: &Bitboard, player: Player) -> Option<Position> {
+        let (hash, transform) = Self::canonical_key(board, player);
+        let canonical_move = Position::from_u8(*self.entries.get(&hash)?).ok()?;
+        Position::from_bit(inverse_transform_mask(canonical_move.to_bit(), transform)).ok()
+    }
+
+    /// Saves the book to a binary file.
+    pub fn save_bin(&self, file_path: &str) -> std::io::Result<()> {
+        let encoded: Vec<u8> = bincode::serialize(self).unwrap();
+        fs::write(file_path, encoded)
+    }
+
+    /// Loads a book from a binary file.
+    pub fn load_bin(file_path: &str) -> std::io::Result<Self> {
+        let data = fs::read(file_path)?;
+        bincode::deserialize(&data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the hash of the canonical position and the transform that produces it.
+    fn canonical_key(board: &Bitboard, player: Player) -> (u64, u8) {
+        let (canonical, transform) = board.canonical_with_transform();
+        (canonical.zobrist_hash(player), transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_symmetric_positions() {
+        let mut book = OpeningBook::new();
+        book.insert_line(&[Position::F5, Position::D6]).unwrap();
+
+        let mut expected = Game::default();
+        expected.apply_moves(&[Position::F5, Position::D6]).unwrap();
+        let (expected_hash, _) = OpeningBook::canonical_key(expected.board_state(), Player::Black);
+
+        // All four first moves are symmetric, so the book answers in each orientation
+        // with the move that leads to the booked line.
+        for first in [Position::F5, Position::E6, Position::D3, Position::C4] {
+            let mut game = Game::default();
+            game.apply_move(first).unwrap();
+
+            let reply = book.lookup(game.board_state(), Player::White).unwrap();
+            game.apply_move(reply).unwrap();
+            assert_eq!(
+                OpeningBook::canonical_key(game.board_state(), Player::Black).0,
+                expected_hash
+            );
+        }
+
+        assert_eq!(
+            book.lookup(&Bitboard::default(), Player::Black),
+            Some(Position::F5)
+        );
+        assert_eq!(book.lookup(&Bitboard::default(), Player::White), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let mut book = OpeningBook::new();
+        book.insert_line(&[Position::F5, Position::D6, Position::C3])
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!(
+            "temp_reversi_opening_book_test_{}.bin",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        book.save_bin(path).unwrap();
+        let loaded = OpeningBook::load_bin(path).unwrap();
+        let _ = fs::remove_file(path);
+
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(
+            loaded.lookup(&Bitboard::default(), Player::Black),
+            Some(Position::F5)
+        );
+    }
+}
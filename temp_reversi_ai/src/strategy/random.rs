@@ -1,20 +1,59 @@
 use super::Strategy;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::SeedableRng;
 use temp_reversi_core::{Game, Position};
 
 /// A random strategy that selects a move randomly from the list of valid moves.
-pub struct RandomStrategy;
+pub struct RandomStrategy {
+    rng: StdRng,
+    seed: Option<u64>, // Last seed given, carried into clones
+}
+
+impl RandomStrategy {
+    /// Creates a random strategy seeded from system entropy.
+    pub fn new() -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+            seed: None,
+        }
+    }
+
+    /// Creates a random strategy whose move sequence is determined by `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            seed: Some(seed),
+        }
+    }
+}
+
+impl Default for RandomStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Strategy for RandomStrategy {
     fn evaluate_and_decide(&mut self, game: &Game) -> Option<Position> {
-        let mut rng = thread_rng();
         let valid_moves = game.valid_moves();
-        valid_moves.choose(&mut rng).copied()
+        valid_moves.choose(&mut self.rng).copied()
     }
 
+    /// Clones the strategy with a fresh generator from the same seed.
+    ///
+    /// A seeded strategy's clone replays the moves of a new strategy with that seed,
+    /// so runs built from clones stay reproducible. Unseeded strategies are cloned
+    /// with a generator seeded from system entropy.
     fn clone_box(&self) -> Box<dyn Strategy> {
-        Box::new(Self)
+        match self.seed {
+            Some(seed) => Box::new(Self::with_seed(seed)),
+            None => Box::new(Self::new()),
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        *self = Self::with_seed(seed);
     }
 }
 
@@ -26,7 +65,7 @@ mod tests {
     #[test]
     fn test_random_strategy() {
         let game = Game::default();
-        let mut strategy = RandomStrategy;
+        let mut strategy = RandomStrategy::new();
 
         let move_option = strategy.evaluate_and_decide(&game);
         assert!(
@@ -34,4 +73,46 @@ mod tests {
             "RandomStrategy should return a valid move."
         );
     }
+
+    #[test]
+    fn test_seeded_strategies_pick_same_moves() {
+        let mut first = RandomStrategy::with_seed(42);
+        let mut second = RandomStrategy::with_seed(42);
+
+        let mut game = Game::default();
+        while !game.is_game_over() {
+            let selected = first.evaluate_and_decide(&game);
+            assert_eq!(selected, second.evaluate_and_decide(&game));
+            game.apply_move(selected.unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_clone_keeps_seed() {
+        let original = RandomStrategy::with_seed(7);
+        let mut clone = original.clone_box();
+        let mut fresh = RandomStrategy::with_seed(7);
+
+        let mut game = Game::default();
+        while !game.is_game_over() {
+            let selected = clone.evaluate_and_decide(&game);
+            assert_eq!(selected, fresh.evaluate_and_decide(&game));
+            game.apply_move(selected.unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_unseeded_clones_play_different_games() {
+        let strategy = RandomStrategy::new();
+        let play = |mut clone: Box<dyn Strategy>| {
+            let mut game = Game::default();
+            while !game.is_game_over() {
+                let selected = clone.evaluate_and_decide(&game).unwrap();
+                game.apply_move(selected).unwrap();
+            }
+            game.move_history()
+        };
+
+        assert_ne!(play(strategy.clone_box()), play(strategy.clone_box()));
+    }
 }
@@ -0,0 +1,63 @@
+use temp_reversi_core::{Game, Position};
+
+use super::{Searcher, Strategy};
+
+/// A strategy that plays the move found by any [`Searcher`].
+///
+/// Lets code written against `Strategy`, such as [`crate::ai_decider::AiDecider`] or
+/// the test matches, use a searcher without a hand-written `Strategy` impl.
+#[derive(Clone)]
+pub struct SearchStrategy<S> {
+    /// The search that selects the moves.
+    pub searcher: S,
+}
+
+impl<S: Searcher> SearchStrategy<S> {
+    /// Creates a strategy that plays the moves found by `searcher`.
+    pub fn new(searcher: S) -> Self {
+        Self { searcher }
+    }
+}
+
+impl<S> Strategy for SearchStrategy<S>
+where
+    S: Searcher + Clone + Send + Sync + 'static,
+{
+    /// Plays the move found by [`Searcher::search_best_move`].
+    fn evaluate_and_decide(&mut self, game: &Game) -> Option<Position> {
+        self.searcher.search_best_move(game)
+    }
+
+    fn clone_box(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use temp_reversi_core::{Bitboard, Player};
+
+    use super::*;
+    use crate::{
+        evaluation::SimpleEvaluator,
+        strategy::{endgame::EndgameSolver, negamax::NegamaxStrategy},
+    };
+
+    #[test]
+    fn test_search_strategy_with_any_searcher() {
+        // Only A1 is empty, so every searcher must play it.
+        let black = Position::C1.to_bit();
+        let white = !(Position::A1.to_bit() | black);
+        let game = Game::new(Bitboard::new(black, white), Player::Black);
+
+        let negamax = NegamaxStrategy::new(SimpleEvaluator, 3);
+        let strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(SearchStrategy::new(negamax)),
+            Box::new(SearchStrategy::new(EndgameSolver::default())),
+        ];
+        for strategy in &strategies {
+            let mut clone = strategy.clone_box();
+            assert_eq!(clone.evaluate_and_decide(&game), Some(Position::A1));
+        }
+    }
+}
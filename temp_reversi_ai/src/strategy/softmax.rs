@@ -0,0 +1,162 @@
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use temp_reversi_core::{Game, Position};
+
+use super::{negamax::NegamaxStrategy, Strategy};
+use crate::evaluation::EvaluationFunction;
+
+/// A strategy that samples moves from a softmax over their search scores.
+///
+/// Every valid move is scored with a full-window negamax search, and a move is drawn
+/// with probability proportional to `exp(score / temperature)`. Higher temperatures
+/// give more diverse games, which is useful for self-play data; a temperature of 0
+/// always plays the best move.
+pub struct SoftmaxStrategy<E: EvaluationFunction + Send + Sync> {
+    /// Sampling temperature, in evaluation units. 0 selects greedily.
+    pub temperature: f64,
+    search: NegamaxStrategy<E>,
+    rng: StdRng,
+}
+
+impl<E: EvaluationFunction + Send + Sync> SoftmaxStrategy<E> {
+    /// Creates a softmax strategy seeded from system entropy.
+    ///
+    /// # Arguments
+    /// * `evaluator` - The evaluation function to score board states.
+    /// * `depth` - The search depth used to score each move.
+    /// * `temperature` - The sampling temperature; 0 selects greedily.
+    pub fn new(evaluator: E, depth: u32, temperature: f64) -> Self {
+        Self::with_rng(evaluator, depth, temperature, StdRng::from_entropy())
+    }
+
+    /// Creates a softmax strategy whose samples are determined by `seed`.
+    pub fn with_seed(evaluator: E, depth: u32, temperature: f64, seed: u64) -> Self {
+        Self::with_rng(evaluator, depth, temperature, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(evaluator: E, depth: u32, temperature: f64, rng: StdRng) -> Self {
+        let mut search = NegamaxStrategy::new(evaluator, depth);
+        search.shuffle_moves = false;
+        Self {
+            temperature,
+            search,
+            rng,
+        }
+    }
+
+    /// Returns the probability of selecting each valid move.
+    ///
+    /// # Arguments
+    /// * `game` - The current game state.
+    ///
+    /// # Returns
+    /// * `Vec<(Position, f64)>` - The valid moves with their probabilities, best first.
+    ///   At temperature 0 the best move has probability 1.
+    pub fn move_probabilities(&mut self, game: &Game) -> Vec<(Position, f64)> {
+        let scored = self.search.evaluate_moves(game);
+        let Some(&(_, best_score)) = scored.first() else {
+            return Vec::new();
+        };
+
+        if self.temperature <= 0.0 {
+            return scored
+                .iter()
+                .enumerate()
+                .map(|(rank, &(mv, _))| (mv, if rank == 0 { 1.0 } else { 0.0 }))
+                .collect();
+        }
+
+        // Shift by the best score so the exponentials cannot overflow.
+        let weights: Vec<f64> = scored
+            .iter()
+            .map(|&(_, score)| ((score - best_score) as f64 / self.temperature).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+        scored
+            .iter()
+            .zip(weights)
+            .map(|(&(mv, _), weight)| (mv, weight / total))
+            .collect()
+    }
+}
+
+impl<E> Strategy for SoftmaxStrategy<E>
+where
+    E: EvaluationFunction + Send + Sync + Clone + 'static,
+{
+    fn evaluate_and_decide(&mut self, game: &Game) -> Option<Position> {
+        let probabilities = self.move_probabilities(game);
+        let distribution = WeightedIndex::new(probabilities.iter().map(|&(_, p)| p)).ok()?;
+        Some(probabilities[distribution.sample(&mut self.rng)].0)
+    }
+
+    /// Clones the strategy with a generator seeded from system entropy, so that
+    /// clones sample different moves.
+    fn clone_box(&self) -> Box<dyn Strategy> {
+        Box::new(Self::new(
+            self.search.evaluator.clone(),
+            self.search.depth,
+            self.temperature,
+        ))
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::evaluation::PhaseAwareEvaluator;
+
+    #[test]
+    fn test_zero_temperature_is_greedy() {
+        let mut game = Game::default();
+        game.apply_move(Position::F5).unwrap();
+
+        let mut softmax = SoftmaxStrategy::with_seed(PhaseAwareEvaluator::default(), 2, 0.0, 1);
+        let mut greedy = NegamaxStrategy::new(PhaseAwareEvaluator::default(), 2);
+        greedy.shuffle_moves = false;
+        let best = greedy.evaluate_moves(&game)[0].0;
+
+        for _ in 0..10 {
+            assert_eq!(softmax.evaluate_and_decide(&game), Some(best));
+        }
+    }
+
+    #[test]
+    fn test_high_temperature_samples_all_moves() {
+        let mut game = Game::default();
+        game.apply_move(Position::F5).unwrap();
+        let valid_moves: HashSet<Position> = game.valid_moves().into_iter().collect();
+
+        let mut strategy = SoftmaxStrategy::with_seed(PhaseAwareEvaluator::default(), 1, 1e9, 7);
+        let probabilities = strategy.move_probabilities(&game);
+        let total: f64 = probabilities.iter().map(|&(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(probabilities.iter().all(|&(_, p)| p > 0.0));
+
+        let sampled: HashSet<Position> = (0..200)
+            .map(|_| strategy.evaluate_and_decide(&game).unwrap())
+            .collect();
+        assert_eq!(sampled, valid_moves);
+    }
+
+    #[test]
+    fn test_clones_sample_different_moves() {
+        let mut game = Game::default();
+        game.apply_move(Position::F5).unwrap();
+        let strategy = SoftmaxStrategy::with_seed(PhaseAwareEvaluator::default(), 1, 1e9, 3);
+
+        let sample = |mut clone: Box<dyn Strategy>| -> Vec<Position> {
+            (0..20)
+                .map(|_| clone.evaluate_and_decide(&game).unwrap())
+                .collect()
+        };
+        assert_ne!(sample(strategy.clone_box()), sample(strategy.clone_box()));
+    }
+}
@@ -0,0 +1,215 @@
+use temp_reversi_core::Position;
+
+/// Kind of score stored in a transposition table entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    /// The score is exact.
+    Exact,
+    /// The score is a lower bound (the search failed high).
+    Lower,
+    /// The score is an upper bound (the search failed low).
+    Upper,
+}
+
+/// A search result stored in the transposition table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TtEntry {
+    /// Zobrist hash of the position, including the side to move (see `Game::tt_key`).
+    pub hash: u64,
+    /// Remaining search depth of the stored result.
+    pub depth: u32,
+    /// Score of the position from the perspective of the player to move.
+    pub score: i32,
+    /// Whether `score` is exact or a bound.
+    pub bound: Bound,
+    /// Best move found, if any.
+    pub best_move: Option<Position>,
+}
+
+/// Typical number of moves searched per node, used to size tables by search depth.
+pub const DEFAULT_BRANCHING_FACTOR: usize = 8;
+
+/// Smallest capacity chosen by [`TranspositionTable::capacity_for_depth`].
+pub const MIN_TT_CAPACITY: usize = 1 << 10;
+
+/// Largest capacity chosen by [`TranspositionTable::capacity_for_depth`].
+pub const MAX_TT_CAPACITY: usize = 1 << 22;
+
+/// Fixed-capacity transposition table with a depth-preferred replacement policy.
+///
+/// Entries are stored in a slot chosen from the position hash. When two positions
+/// map to the same slot, the result from the deeper search is kept.
+pub struct TranspositionTable {
+    slots: Vec<Option<TtEntry>>,
+    len: usize,
+}
+
+impl TranspositionTable {
+    /// Creates an empty table holding at most `capacity` entries.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "Transposition table capacity must be positive"
+        );
+        Self {
+            slots: vec![None; capacity],
+            len: 0,
+        }
+    }
+
+    /// Creates an empty table sized for a search of the given depth.
+    ///
+    /// # Arguments
+    /// * `depth` - The search depth in plies.
+    /// * `branching_factor` - The expected number of moves searched per node.
+    pub fn for_depth(depth: u32, branching_factor: usize) -> Self {
+        Self::new(Self::capacity_for_depth(depth, branching_factor))
+    }
+
+    /// Estimates the capacity needed to hold every node of a search.
+    ///
+    /// A search of depth `d` with branching factor `b` visits about `b^d` nodes. The
+    /// estimate is clamped to [`MIN_TT_CAPACITY`]..=[`MAX_TT_CAPACITY`] so that shallow
+    /// searches still get a useful table and deep ones do not exhaust memory.
+    pub fn capacity_for_depth(depth: u32, branching_factor: usize) -> usize {
+        branching_factor
+            .max(1)
+            .checked_pow(depth)
+            .unwrap_or(usize::MAX)
+            .clamp(MIN_TT_CAPACITY, MAX_TT_CAPACITY)
+    }
+
+    /// Returns the maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of stored entries.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the table holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all entries.
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+        self.len = 0;
+    }
+
+    /// Looks up the entry for a position.
+    pub fn probe(&self, hash: u64) -> Option<&TtEntry> {
+        self.slots[self.index(hash)]
+            .as_ref()
+            .filter(|entry| entry.hash == hash)
+    }
+
+    /// Stores an entry, keeping the deeper result when the slot is already taken.
+    pub fn store(&mut self, entry: TtEntry) {
+        let index = self.index(entry.hash);
+        match &self.slots[index] {
+            None => {
+                self.slots[index] = Some(entry);
+                self.len += 1;
+            }
+            Some(existing) if existing.hash == entry.hash || entry.depth >= existing.depth => {
+                self.slots[index] = Some(entry);
+            }
+            Some(_) => {}
+        }
+    }
+
+    /// Returns the slot index for a hash.
+    fn index(&self, hash: u64) -> usize {
+        (hash % self.slots.len() as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(hash: u64, depth: u32, score: i32) -> TtEntry {
+        TtEntry {
+            hash,
+            depth,
+            score,
+            bound: Bound::Exact,
+            best_move: None,
+        }
+    }
+
+    #[test]
+    fn test_store_and_probe() {
+        let mut table = TranspositionTable::new(8);
+        assert!(table.is_empty());
+
+        table.store(entry(3, 2, 10));
+        assert_eq!(table.probe(3), Some(&entry(3, 2, 10)));
+        assert_eq!(table.probe(11), None); // Same slot, different position
+        assert_eq!(table.len(), 1);
+
+        table.clear();
+        assert!(table.is_empty());
+        assert_eq!(table.probe(3), None);
+    }
+
+    #[test]
+    fn test_depth_preferred_replacement() {
+        let mut table = TranspositionTable::new(8);
+
+        // 3 and 11 collide in a table of 8 slots.
+        table.store(entry(3, 4, 10));
+        table.store(entry(11, 2, 20));
+        assert_eq!(table.probe(3), Some(&entry(3, 4, 10)));
+        assert_eq!(table.probe(11), None);
+
+        table.store(entry(11, 5, 30));
+        assert_eq!(table.probe(11), Some(&entry(11, 5, 30)));
+        assert_eq!(table.len(), 1);
+
+        // The same position is always updated.
+        table.store(entry(11, 1, 40));
+        assert_eq!(table.probe(11), Some(&entry(11, 1, 40)));
+    }
+
+    #[test]
+    fn test_occupancy_never_exceeds_capacity() {
+        let mut table = TranspositionTable::new(16);
+        for hash in 0..1000u64 {
+            table.store(entry(
+                hash.wrapping_mul(0x9E37_79B9_7F4A_7C15),
+                (hash % 7) as u32,
+                0,
+            ));
+            assert!(table.len() <= table.capacity());
+        }
+        assert_eq!(table.len(), 16);
+    }
+
+    #[test]
+    fn test_capacity_for_depth() {
+        assert_eq!(
+            TranspositionTable::capacity_for_depth(0, 8),
+            MIN_TT_CAPACITY
+        );
+        assert_eq!(TranspositionTable::capacity_for_depth(5, 8), 1 << 15);
+        assert_eq!(
+            TranspositionTable::capacity_for_depth(60, 8),
+            MAX_TT_CAPACITY
+        );
+
+        for depth in 0..=10 {
+            let requested = TranspositionTable::capacity_for_depth(depth, 6);
+            let table = TranspositionTable::for_depth(depth, 6);
+            assert!(table.capacity() >= requested);
+            assert!(table.is_empty());
+        }
+    }
+}
@@ -0,0 +1,704 @@
+use std::{
+    fmt,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use temp_reversi_core::{Bitboard, Game, Player, Position};
+
+use crate::{
+    evaluation::PatternEvaluator,
+    strategy::{negamax::NegamaxStrategy, Strategy},
+};
+
+/// Number of random moves played at the start of each game pair.
+const OPENING_MOVES: usize = 4;
+
+/// Z-score of the two-sided 95% confidence level.
+const Z_95: f64 = 1.96;
+
+/// A strategy shared with the worker threads that select its moves.
+type SharedStrategy = Arc<Mutex<Box<dyn Strategy>>>;
+
+/// Records a game lost because a strategy did not return a move in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeoutRecord {
+    /// Index of the game within the match.
+    pub game_index: usize,
+    /// Index of the strategy that timed out (0 for the first, 1 for the second).
+    pub strategy_index: usize,
+    /// The color the strategy was playing.
+    pub player: Player,
+    /// The board position at which the timeout occurred.
+    pub board: Bitboard,
+}
+
+/// Aggregated result of a test match.
+///
+/// `wins` and `losses` are counted from the first strategy's perspective.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchResult {
+    /// Games won by the first strategy.
+    pub wins: usize,
+    /// Games lost by the first strategy.
+    pub losses: usize,
+    /// Drawn games.
+    pub draws: usize,
+    /// Games won by Black.
+    pub black_wins: usize,
+    /// Games won by White.
+    pub white_wins: usize,
+    /// Average number of moves per game, including the opening moves.
+    pub avg_game_length: f64,
+    /// Average final disc differential from the first strategy's perspective.
+    ///
+    /// Games ended by a timeout or forfeit count with the differential at that point.
+    pub avg_margin: f64,
+    /// Wall-clock time taken by the whole match.
+    pub elapsed: Duration,
+    /// Seed used to generate the random openings.
+    pub seed: u64,
+    /// Games decided by a timeout. These are also counted in `wins` or `losses`.
+    pub timeouts: Vec<TimeoutRecord>,
+}
+
+impl fmt::Display for MatchResult {
+    /// Formats the result as a multi-line summary.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "First strategy: {} wins, {} losses, {} draws",
+            self.wins, self.losses, self.draws
+        )?;
+        let (low, high) = elo_confidence_interval(self.wins, self.draws, self.losses);
+        writeln!(
+            f,
+            "Elo difference: {:+.1} (95% CI {:+.1} to {:+.1})",
+            estimate_elo_diff(self.wins, self.draws, self.losses),
+            low,
+            high
+        )?;
+        writeln!(
+            f,
+            "By color: Black {} wins, White {} wins",
+            self.black_wins, self.white_wins
+        )?;
+        writeln!(
+            f,
+            "Average margin: {:+.2}, average length: {:.1} moves",
+            self.avg_margin, self.avg_game_length
+        )?;
+        write!(
+            f,
+            "Timeouts: {}, seed: {}, elapsed: {:.1}s",
+            self.timeouts.len(),
+            self.seed,
+            self.elapsed.as_secs_f64()
+        )
+    }
+}
+
+/// Converts an expected score in `[0, 1]` to an Elo difference with the logistic model.
+fn score_to_elo(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// Estimates the Elo difference of the first player from match results.
+///
+/// Uses the logistic model, where an expected score `p` corresponds to a difference
+/// of `-400 * log10(1 / p - 1)`. A perfect or zero score yields an infinite difference.
+///
+/// # Returns
+/// * `f64` - The estimated difference, `0.0` if no games were played.
+pub fn estimate_elo_diff(wins: usize, draws: usize, losses: usize) -> f64 {
+    let games = wins + draws + losses;
+    if games == 0 {
+        return 0.0;
+    }
+    score_to_elo((wins as f64 + 0.5 * draws as f64) / games as f64)
+}
+
+/// Computes a 95% confidence interval of the Elo difference from match results.
+///
+/// The interval of the mean score is estimated with the normal approximation and
+/// converted to Elo with the same model as [`estimate_elo_diff`].
+///
+/// # Returns
+/// * `(f64, f64)` - The lower and upper bounds, `(0.0, 0.0)` if no games were played.
+pub fn elo_confidence_interval(wins: usize, draws: usize, losses: usize) -> (f64, f64) {
+    let games = (wins + draws + losses) as f64;
+    if games == 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let score = (wins as f64 + 0.5 * draws as f64) / games;
+    let variance = (wins as f64 * (1.0 - score).powi(2)
+        + draws as f64 * (0.5 - score).powi(2)
+        + losses as f64 * score.powi(2))
+        / games;
+    let margin = Z_95 * (variance / games).sqrt();
+
+    (
+        score_to_elo((score - margin).clamp(0.0, 1.0)),
+        score_to_elo((score + margin).clamp(0.0, 1.0)),
+    )
+}
+
+/// The end of a single game.
+enum GameEnd {
+    /// The game was played to the end.
+    Finished(Option<Player>),
+    /// The given player did not return a move in time and loses.
+    Timeout(Player, Bitboard),
+    /// The given player returned no move or an invalid move and loses.
+    Forfeit(Player),
+}
+
+/// Plays a series of games between two strategies, alternating colors every game.
+///
+/// Each pair of games starts from the same random opening of `OPENING_MOVES` moves,
+/// generated from `seed`, with the strategies swapping colors. Deterministic
+/// strategies therefore reproduce the same result for the same seed.
+///
+/// Each move is selected on a worker thread. If a strategy does not return a move
+/// within `move_timeout`, the game is recorded as a loss for that side and the
+/// position is logged. A strategy that is still busy with a previous move times out
+/// again, so a hanging strategy cannot block the match.
+///
+/// # Arguments
+/// * `first` - The first strategy. It plays Black in even-numbered games.
+/// * `second` - The second strategy.
+/// * `num_games` - The number of games to play.
+/// * `move_timeout` - The maximum time allowed for a single move.
+/// * `seed` - Seed for the random openings.
+///
+/// # Returns
+/// * `MatchResult` - The summary of the match, which is also printed at the end.
+pub fn run_test_match(
+    first: Box<dyn Strategy>,
+    second: Box<dyn Strategy>,
+    num_games: usize,
+    move_timeout: Duration,
+    seed: u64,
+) -> MatchResult {
+    let strategies: [SharedStrategy; 2] =
+        [Arc::new(Mutex::new(first)), Arc::new(Mutex::new(second))];
+    let mut result = MatchResult {
+        seed,
+        ..Default::default()
+    };
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut opening = Game::default();
+    let mut total_moves = 0;
+    let mut total_margin = 0;
+    let start = Instant::now();
+
+    for game_index in 0..num_games {
+        if game_index % 2 == 0 {
+            opening = random_opening(&mut rng);
+        }
+
+        // The first strategy plays Black in even-numbered games.
+        let first_color = if game_index % 2 == 0 {
+            Player::Black
+        } else {
+            Player::White
+        };
+        let (black, white) = match first_color {
+            Player::Black => (&strategies[0], &strategies[1]),
+            Player::White => (&strategies[1], &strategies[0]),
+        };
+
+        let mut game = Game::new(opening.board_state().clone(), opening.current_player());
+        let game_end = play_game(&mut game, black, white, move_timeout);
+        total_moves += game.history_len() + OPENING_MOVES;
+        let (black_count, white_count) = game.current_score();
+        let black_margin = black_count as i64 - white_count as i64;
+        total_margin += match first_color {
+            Player::Black => black_margin,
+            Player::White => -black_margin,
+        };
+
+        let winner = match game_end {
+            GameEnd::Finished(winner) => winner,
+            GameEnd::Timeout(player, board) => {
+                let strategy_index = if player == first_color { 0 } else { 1 };
+                eprintln!(
+                    "Game {}: strategy {} ({:?}) timed out at:\n{}",
+                    game_index, strategy_index, player, board
+                );
+                result.timeouts.push(TimeoutRecord {
+                    game_index,
+                    strategy_index,
+                    player,
+                    board,
+                });
+                Some(player.opponent())
+            }
+            GameEnd::Forfeit(player) => Some(player.opponent()),
+        };
+
+        match winner {
+            Some(player) if player == first_color => result.wins += 1,
+            Some(_) => result.losses += 1,
+            None => result.draws += 1,
+        }
+        match winner {
+            Some(Player::Black) => result.black_wins += 1,
+            Some(Player::White) => result.white_wins += 1,
+            None => {}
+        }
+
+        println!(
+            "Game {}/{}: winner {:?}, first strategy {}-{}-{}",
+            game_index + 1,
+            num_games,
+            winner,
+            result.wins,
+            result.losses,
+            result.draws
+        );
+    }
+
+    if num_games > 0 {
+        result.avg_game_length = total_moves as f64 / num_games as f64;
+        result.avg_margin = total_margin as f64 / num_games as f64;
+    }
+    result.elapsed = start.elapsed();
+    println!("{}", result);
+
+    result
+}
+
+impl MatchResult {
+    /// Returns the same result from the second strategy's perspective.
+    fn swapped(&self) -> MatchResult {
+        MatchResult {
+            wins: self.losses,
+            losses: self.wins,
+            avg_margin: -self.avg_margin,
+            timeouts: self
+                .timeouts
+                .iter()
+                .map(|timeout| TimeoutRecord {
+                    strategy_index: 1 - timeout.strategy_index,
+                    ..timeout.clone()
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Results of a round-robin tournament between several strategies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TournamentResult {
+    /// Cross-table of match results. `results[i][j]` is the match between strategies
+    /// `i` and `j` from the perspective of `i`; the diagonal is `None`.
+    pub results: Vec<Vec<Option<MatchResult>>>,
+}
+
+impl TournamentResult {
+    /// Returns the total points of each strategy, counting a draw as half a win.
+    pub fn points(&self) -> Vec<f64> {
+        self.results
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .flatten()
+                    .map(|result| result.wins as f64 + 0.5 * result.draws as f64)
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Returns the strategy indices ordered by points, best first.
+    pub fn ranking(&self) -> Vec<usize> {
+        let points = self.points();
+        let mut ranking: Vec<usize> = (0..points.len()).collect();
+        ranking.sort_by(|&a, &b| points[b].total_cmp(&points[a]));
+        ranking
+    }
+}
+
+impl fmt::Display for TournamentResult {
+    /// Formats the cross-table of win-loss-draw records followed by the ranking.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>4}", "")?;
+        for j in 0..self.results.len() {
+            write!(f, " {:>9}", j)?;
+        }
+        writeln!(f)?;
+
+        for (i, row) in self.results.iter().enumerate() {
+            write!(f, "{:>4}", i)?;
+            for result in row {
+                let cell = match result {
+                    Some(result) => {
+                        format!("{}-{}-{}", result.wins, result.losses, result.draws)
+                    }
+                    None => "-".to_string(),
+                };
+                write!(f, " {:>9}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        let points = self.points();
+        write!(f, "Ranking:")?;
+        for (rank, index) in self.ranking().into_iter().enumerate() {
+            write!(
+                f,
+                "\n{:>4}. strategy {} ({} points)",
+                rank + 1,
+                index,
+                points[index]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays a round-robin tournament in which every pair of strategies plays a match.
+///
+/// Each match is played with [`run_test_match`], so both strategies of a pair play
+/// Black and White equally often. The cross-table and ranking are printed at the end.
+///
+/// # Arguments
+/// * `strategies` - The strategies taking part.
+/// * `games_per_pair` - The number of games played between each pair.
+/// * `move_timeout` - The maximum time allowed for a single move.
+/// * `seed` - Seed for the random openings, shared by all matches.
+///
+/// # Returns
+/// * `TournamentResult` - The cross-table of all matches.
+pub fn run_tournament(
+    strategies: &[Box<dyn Strategy>],
+    games_per_pair: usize,
+    move_timeout: Duration,
+    seed: u64,
+) -> TournamentResult {
+    let count = strategies.len();
+    let mut results = vec![vec![None; count]; count];
+
+    for i in 0..count {
+        for j in i + 1..count {
+            println!("Match: strategy {} vs strategy {}", i, j);
+            let result = run_test_match(
+                strategies[i].clone_box(),
+                strategies[j].clone_box(),
+                games_per_pair,
+                move_timeout,
+                seed,
+            );
+            results[j][i] = Some(result.swapped());
+            results[i][j] = Some(result);
+        }
+    }
+
+    let tournament = TournamentResult { results };
+    println!("{}", tournament);
+    tournament
+}
+
+/// Plays a round-robin tournament between trained pattern models.
+///
+/// Each model file is loaded with [`PatternEvaluator::load_bin`] and played by a
+/// [`NegamaxStrategy`] of the given depth; strategy `i` of the result is
+/// `model_paths[i]`. See [`run_tournament`] for how the matches are played.
+///
+/// # Arguments
+/// * `model_paths` - Paths of the model files taking part.
+/// * `depth` - The search depth of every model.
+/// * `games_per_pair` - The number of games played between each pair.
+/// * `move_timeout` - The maximum time allowed for a single move.
+/// * `seed` - Seed for the random openings, shared by all matches.
+///
+/// # Returns
+/// - `Ok(TournamentResult)` with the cross-table of all matches.
+/// - `Err(String)` if a model file cannot be loaded.
+pub fn run_model_tournament(
+    model_paths: &[String],
+    depth: u32,
+    games_per_pair: usize,
+    move_timeout: Duration,
+    seed: u64,
+) -> Result<TournamentResult, String> {
+    let strategies = model_paths
+        .iter()
+        .map(|path| {
+            let evaluator = PatternEvaluator::load_bin(path)
+                .map_err(|e| format!("Failed to load model {}: {}", path, e))?;
+            Ok(Box::new(NegamaxStrategy::new(evaluator, depth)) as Box<dyn Strategy>)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    for (index, path) in model_paths.iter().enumerate() {
+        println!("Strategy {}: {}", index, path);
+    }
+    Ok(run_tournament(
+        &strategies,
+        games_per_pair,
+        move_timeout,
+        seed,
+    ))
+}
+
+/// Plays `OPENING_MOVES` random moves from the initial position.
+pub(crate) fn random_opening(rng: &mut StdRng) -> Game {
+    let mut game = Game::default();
+    for _ in 0..OPENING_MOVES {
+        // The opening is far too short for the game to end.
+        let position = *game.valid_moves().choose(rng).unwrap();
+        game.apply_move(position).unwrap();
+    }
+    game
+}
+
+/// Plays a game to the end between two shared strategies.
+fn play_game(
+    game: &mut Game,
+    black: &SharedStrategy,
+    white: &SharedStrategy,
+    move_timeout: Duration,
+) -> GameEnd {
+    while !game.is_game_over() {
+        let player = game.current_player();
+        let strategy = match player {
+            Player::Black => black,
+            Player::White => white,
+        };
+        let board = game.board_state().clone();
+
+        let Some(selected) =
+            select_move_with_timeout(strategy, board.clone(), player, move_timeout)
+        else {
+            return GameEnd::Timeout(player, board);
+        };
+
+        let applied = selected.is_some_and(|position| game.apply_move(position).is_ok());
+        if !applied {
+            return GameEnd::Forfeit(player);
+        }
+    }
+
+    GameEnd::Finished(game.result().unwrap().winner())
+}
+
+/// Runs move selection on a worker thread and waits at most `timeout` for the result.
+///
+/// # Returns
+/// * `Some(Option<Position>)` - The strategy's answer.
+/// * `None` - The strategy did not answer in time.
+fn select_move_with_timeout(
+    strategy: &SharedStrategy,
+    board: Bitboard,
+    player: Player,
+    timeout: Duration,
+) -> Option<Option<Position>> {
+    let (sender, receiver) = mpsc::channel();
+    let strategy = Arc::clone(strategy);
+
+    // The worker is detached; if it hangs, it keeps the strategy locked and later
+    // moves of the same strategy time out as well.
+    thread::spawn(move || {
+        let game = Game::new(board, player);
+        let selected = strategy.lock().unwrap().evaluate_and_decide(&game);
+        let _ = sender.send(selected);
+    });
+
+    receiver.recv_timeout(timeout).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluation::PositionalEvaluator;
+    use crate::patterns::PatternGroup;
+    use crate::strategy::{
+        negamax::NegamaxStrategy, random::RandomStrategy, simple::SimpleStrategy,
+    };
+
+    /// A strategy that takes far longer than the allowed time per move.
+    struct SlowStrategy;
+
+    impl Strategy for SlowStrategy {
+        fn evaluate_and_decide(&mut self, game: &Game) -> Option<Position> {
+            thread::sleep(Duration::from_millis(200));
+            game.valid_moves().first().copied()
+        }
+
+        fn clone_box(&self) -> Box<dyn Strategy> {
+            Box::new(Self)
+        }
+    }
+
+    #[test]
+    fn test_run_test_match() {
+        let result = run_test_match(
+            Box::new(RandomStrategy::new()),
+            Box::new(RandomStrategy::new()),
+            4,
+            Duration::from_secs(5),
+            42,
+        );
+
+        assert_eq!(result.wins + result.losses + result.draws, 4);
+        assert_eq!(result.black_wins + result.white_wins + result.draws, 4);
+        assert_eq!(result.seed, 42);
+        assert!(result.avg_game_length > OPENING_MOVES as f64);
+        assert!(result.timeouts.is_empty());
+    }
+
+    #[test]
+    fn test_run_test_match_reproducible() {
+        let run = |seed| {
+            let mut result = run_test_match(
+                Box::new(SimpleStrategy),
+                Box::new(SimpleStrategy),
+                6,
+                Duration::from_secs(5),
+                seed,
+            );
+            result.elapsed = Duration::ZERO;
+            result
+        };
+
+        assert_eq!(run(7), run(7));
+    }
+
+    #[test]
+    fn test_run_test_match_summary_with_swapped_colors() {
+        // Identical deterministic strategies replay the same game with swapped colors,
+        // so each pair is one win and one loss for the first strategy (or two draws).
+        let result = run_test_match(
+            Box::new(SimpleStrategy),
+            Box::new(SimpleStrategy),
+            8,
+            Duration::from_secs(5),
+            3,
+        );
+
+        assert_eq!(result.wins, result.losses);
+        assert_eq!(result.draws % 2, 0);
+        assert_eq!(result.black_wins + result.white_wins + result.draws, 8);
+        assert_eq!(result.avg_margin, 0.0);
+        assert!(result.elapsed > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_elo_of_symmetric_result_is_zero() {
+        assert_eq!(estimate_elo_diff(10, 5, 10), 0.0);
+        let (low, high) = elo_confidence_interval(10, 5, 10);
+        assert!(low < 0.0 && high > 0.0);
+        assert!((low + high).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_elo_of_lopsided_result() {
+        // A 75% score is about 191 Elo.
+        let elo = estimate_elo_diff(70, 10, 20);
+        assert!((elo - 190.85).abs() < 0.01, "{}", elo);
+        assert!(estimate_elo_diff(20, 10, 70) < 0.0);
+
+        let (low, high) = elo_confidence_interval(70, 10, 20);
+        assert!(low > 0.0 && low < elo && elo < high);
+        assert_eq!(estimate_elo_diff(5, 0, 0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_run_tournament_cross_table() {
+        let strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(SimpleStrategy),
+            Box::new(RandomStrategy::with_seed(1)),
+            Box::new(NegamaxStrategy::new(PositionalEvaluator, 1)),
+        ];
+        let tournament = run_tournament(&strategies, 4, Duration::from_secs(5), 11);
+
+        assert_eq!(tournament.results.len(), 3);
+        for (i, row) in tournament.results.iter().enumerate() {
+            assert_eq!(row.len(), 3);
+            assert!(row[i].is_none());
+            for (j, result) in row.iter().enumerate().filter(|&(j, _)| j != i) {
+                let result = result.as_ref().unwrap();
+                let opposite = tournament.results[j][i].as_ref().unwrap();
+                assert_eq!(result.wins + result.losses + result.draws, 4);
+                assert_eq!(result.wins, opposite.losses);
+                assert_eq!(result.draws, opposite.draws);
+            }
+        }
+
+        let total_points: f64 = tournament.points().iter().sum();
+        assert_eq!(total_points, 12.0);
+        let mut ranking = tournament.ranking();
+        ranking.sort();
+        assert_eq!(ranking, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_run_model_tournament() {
+        // Three small corner models that value the corner differently.
+        let model_paths: Vec<String> = [-10, 0, 10]
+            .iter()
+            .enumerate()
+            .map(|(index, &corner_score)| {
+                let mut scores = vec![0; 3_usize.pow(4)];
+                scores[1] = corner_score;
+                let group = PatternGroup::new(0x0000000000000303, vec![scores; 60], None);
+                let path = std::env::temp_dir().join(format!(
+                    "temp_reversi_tournament_model_{}_{}.bin",
+                    index,
+                    std::process::id()
+                ));
+                let path = path.to_str().unwrap().to_string();
+                PatternEvaluator::new(vec![group]).save_bin(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let tournament = run_model_tournament(&model_paths, 1, 2, Duration::from_secs(5), 3);
+        let mut missing = model_paths.clone();
+        missing.push("no_such_model.bin".to_string());
+        let missing_result = run_model_tournament(&missing, 1, 2, Duration::from_secs(5), 3);
+        for path in &model_paths {
+            let _ = std::fs::remove_file(path);
+        }
+
+        let tournament = tournament.unwrap();
+        assert_eq!(tournament.results.len(), 3);
+        for (i, row) in tournament.results.iter().enumerate() {
+            assert_eq!(row.len(), 3);
+            for (j, result) in row.iter().enumerate().filter(|&(j, _)| j != i) {
+                let result = result.as_ref().unwrap();
+                let opposite = tournament.results[j][i].as_ref().unwrap();
+                assert_eq!(result.wins + result.losses + result.draws, 2);
+                assert_eq!(result.wins, opposite.losses);
+            }
+        }
+        assert!(missing_result.is_err());
+    }
+
+    #[test]
+    fn test_run_test_match_timeout() {
+        let result = run_test_match(
+            Box::new(RandomStrategy::new()),
+            Box::new(SlowStrategy),
+            2,
+            Duration::from_millis(20),
+            0,
+        );
+
+        // The slow strategy loses every game by timeout.
+        assert_eq!(result.wins, 2);
+        assert_eq!(result.timeouts.len(), 2);
+        assert!(result.timeouts.iter().all(|t| t.strategy_index == 1));
+
+        // In the first game the slow strategy plays White and times out on its first move.
+        let first = &result.timeouts[0];
+        assert_eq!(first.player, Player::White);
+        let (black_count, white_count) = first.board.count_stones();
+        assert_eq!(black_count + white_count, 4 + OPENING_MOVES + 1);
+    }
+}
@@ -0,0 +1,118 @@
+use temp_reversi_core::{utils::parse_transcript, Bitboard, Game};
+
+/// Benchmark positions, each given by a description and the transcript that reaches it.
+///
+/// The positions are picked to cover every stage of the game: common openings,
+/// midgame positions before and after the corners are contested, endgames small
+/// enough for the endgame solver, forced passes and a finished game. Apart from the
+/// named openings, they are taken from fixed self-play games, so every position is
+/// reachable from the initial board.
+const BENCHMARK_POSITIONS: [(&str, &str); 20] = [
+    // Openings.
+    ("Initial position", ""),
+    ("Perpendicular opening", "F5D6"),
+    ("Diagonal opening", "F5F6"),
+    ("Parallel opening", "F5F4"),
+    ("Tiger", "F5D6C3D3C4"),
+    ("Rabbit", "F5D6C5F4E3"),
+    // Early midgame: no corner is reachable yet.
+    ("Wide mobility for both sides", "D3C3C4E3C2C5F5D2E6E7F6G4"),
+    ("Black has half the mobility of White", "D3C3E6D6B2F4D7E3E2D2E1C4B5B3"),
+    ("Balanced, 38 empties", "C4C5B6E3D6B4F5E6C3D7A5A4F2D3C8F6A3G6D2E2F4G2"),
+    // Midgame: the side to move can take a corner.
+    ("White can take two corners", "C4C3D3E3F2D6E2D2E6G1B2F6D1E1G7C5G2"),
+    ("Black can take a corner", "D3C3C4E3C2C5F5D2E6E7F6G4E1B4G6F4A5B1B2C1G3B3G5H6H7A4"),
+    (
+        "Black can take two corners",
+        "C4C5B6E3D6B4F5E6C3D7A5A4F2D3C8F6A3G6D2E2F4G2F7E8H7C2D1E7B1A7F8G7F3G8C7D8",
+    ),
+    // Late midgame with corners taken.
+    (
+        "Two corners taken, Black has 16 moves",
+        "C4C3D3E3F2D6E2D2E6G1B2F6D1E1G7C5G2A1E7F8F3G5B5C1E8G4F1H6H1B6",
+    ),
+    (
+        "Two corners taken, 18 empties",
+        "D3C3C4E3C2C5F5D2E6E7F6G4E1B4G6F4A5B1B2C1G3B3G5H6H7A4G7H8H5G2B5H4E2F2A2A3G1D6G8F8B6A1",
+    ),
+    (
+        "Two corners taken, 14 empties",
+        "C4C3D3E3F2D6E2D2E6G1B2F6D1E1G7C5G2A1E7F8F3G5B5C1E8G4F1H6H1B6C7G3H3H2B1F5B7A5G6H5G8A7B4C2F4C6",
+    ),
+    // Endgames within reach of the endgame solver (10 or fewer empties).
+    (
+        "10 empties",
+        "C4C5B6E3D6B4F5E6C3D7A5A4F2D3C8F6A3G6D2E2F4G2F7E8H7C2D1E7B1A7F8G7F3G8C7D8H8F1H2H1B5G3H4B7B2B3A6A1B8E1",
+    ),
+    (
+        "6 empties, Black has a single move",
+        "C4C3D3E3F2D6E2D2E6G1B2F6D1E1G7C5G2A1E7F8F3G5B5C1E8G4F1H6H1B6C7G3H3H2B1F5B7A5G6H5G8A7B4C2F4C6H7A3H4C8D7H8B3A2",
+    ),
+    // Forced passes and a finished game.
+    (
+        "White has no legal move",
+        "D3C3E6D6B2F4D7E3E2D2E1C4B5B3B4D8C7B8C6A1G4C1C8F3D1A2A3E7F7G3E8F8H3B7F5G5A8A5G8B6F2H4A6A4H5H6F6F1H7G7C5A7G6H2C2H8B1",
+    ),
+    (
+        "Black has no legal move",
+        "C4C5B6E3D6B4F5E6C3D7A5A4F2D3C8F6A3G6D2E2F4G2F7E8H7C2D1E7B1A7F8G7F3G8C7D8H8F1H2H1B5G3H4B7B2B3A6A1B8E1G4G1C6A8A2C1",
+    ),
+    (
+        "Game over, board full",
+        "C4C3D3E3F2D6E2D2E6G1B2F6D1E1G7C5G2A1E7F8F3G5B5C1E8G4F1H6H1B6C7G3H3H2B1F5B7A5G6H5G8A7B4C2F4C6H7A3H4C8D7H8B3A2F7A8B8A6A4D8",
+    ),
+];
+
+/// Returns a fixed set of representative positions for benchmarking and regression tests.
+///
+/// The set covers the opening, midgame and endgame, including positions where one
+/// player must pass. The order and contents are stable across runs.
+pub fn benchmark_positions() -> Vec<Bitboard> {
+    BENCHMARK_POSITIONS
+        .iter()
+        .map(|(description, transcript)| {
+            let moves = parse_transcript(transcript)
+                .unwrap_or_else(|e| panic!("Invalid benchmark transcript ({description}): {e}"));
+            let mut game = Game::default();
+            game.apply_moves(&moves)
+                .unwrap_or_else(|e| panic!("Illegal benchmark transcript ({description}): {e:?}"));
+            game.board_state().clone()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_reversi_core::Player;
+
+    #[test]
+    fn test_benchmark_positions_are_legal() {
+        let positions = benchmark_positions();
+        assert_eq!(positions.len(), 20);
+
+        for board in &positions {
+            let (black, white) = board.bits();
+            assert_eq!(black & white, 0, "Black and white discs must not overlap");
+
+            let (black_count, white_count) = board.count_stones();
+            let total = black_count + white_count;
+            assert!((4..=64).contains(&total));
+        }
+    }
+
+    #[test]
+    fn test_benchmark_positions_cover_forced_pass() {
+        let positions = benchmark_positions();
+
+        // At least one position where exactly one side has no legal move.
+        assert!(positions.iter().any(|board| {
+            !board.is_game_over()
+                && (board.valid_moves(Player::Black).is_empty()
+                    || board.valid_moves(Player::White).is_empty())
+        }));
+
+        // And at least one finished game.
+        assert!(positions.iter().any(|board| board.is_game_over()));
+    }
+}
@@ -0,0 +1,56 @@
+use temp_reversi_ai::{evaluation::EvaluationFunction, strategy::negamax::NegamaxStrategy};
+use temp_reversi_core::{utils::parse_transcript, Game, Position};
+
+/// Replays `transcript` from the initial position and scores every legal move.
+///
+/// # Arguments
+/// * `transcript` - The moves played so far, e.g. `"f5d6c3"`. Empty for the initial position.
+/// * `evaluator` - The evaluation function used by the search.
+/// * `depth` - The search depth.
+///
+/// # Returns
+/// - `Ok(Vec<(Position, i32)>)` with the legal moves and their search scores, best first.
+/// - `Err(String)` if the transcript cannot be parsed or replayed.
+pub fn analyze_position<E>(
+    transcript: &str,
+    evaluator: E,
+    depth: u32,
+) -> Result<Vec<(Position, i32)>, String>
+where
+    E: EvaluationFunction + Send + Sync,
+{
+    let moves = parse_transcript(transcript)?;
+    let mut game = Game::default();
+    game.apply_moves(&moves)
+        .map_err(|(index, err)| format!("Move {}: {}", index, err))?;
+
+    let mut strategy = NegamaxStrategy::new(evaluator, depth);
+    strategy.shuffle_moves = false;
+    Ok(strategy.evaluate_moves(&game))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_reversi_ai::evaluation::PositionalEvaluator;
+
+    #[test]
+    fn test_analyze_initial_position() {
+        let scores = analyze_position("", PositionalEvaluator, 3).unwrap();
+
+        let mut moves: Vec<Position> = scores.iter().map(|&(mv, _)| mv).collect();
+        moves.sort_by_key(|mv| mv.to_u8());
+        let mut expected = Game::default().valid_moves();
+        expected.sort_by_key(|mv| mv.to_u8());
+        assert_eq!(moves, expected);
+
+        assert!(scores.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        // The initial position is symmetric, so all moves score the same.
+        assert!(scores.iter().all(|&(_, score)| score == scores[0].1));
+    }
+
+    #[test]
+    fn test_analyze_invalid_transcript() {
+        assert!(analyze_position("a1", PositionalEvaluator, 1).is_err());
+    }
+}
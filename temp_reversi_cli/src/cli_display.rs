@@ -1,4 +1,4 @@
-use temp_reversi_core::{Game, Player};
+use temp_reversi_core::{Game, GameResult, Player};
 
 pub fn cli_display(game: &Game) {
     if game.is_game_over() {
@@ -9,10 +9,10 @@ pub fn cli_display(game: &Game) {
             "Final Score - Black: {}, White: {}",
             final_black_score, final_white_score
         );
-        match game.winner().unwrap() {
-            Some(Player::Black) => println!("Winner: Black"),
-            Some(Player::White) => println!("Winner: White"),
-            None => println!("It's a draw!"),
+        match game.result().unwrap() {
+            GameResult::BlackWins(margin) => println!("Winner: Black (+{})", margin),
+            GameResult::WhiteWins(margin) => println!("Winner: White (+{})", margin),
+            GameResult::Draw => println!("It's a draw!"),
         }
     } else {
         println!("Board:\n{}", game.board_state());
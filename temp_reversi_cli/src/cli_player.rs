@@ -1,10 +1,15 @@
 use std::str::FromStr;
-use temp_reversi_core::{Game, MoveDecider, Position};
+use temp_reversi_core::{Game, LegalMoves, MoveDecider, Position};
 
 pub struct CliPlayer;
 
 impl MoveDecider for CliPlayer {
     fn select_move(&mut self, game: &Game) -> Option<Position> {
+        let valid_moves = match game.legal_moves() {
+            LegalMoves::Moves(moves) => moves,
+            LegalMoves::MustPass | LegalMoves::GameOver => return None,
+        };
+
         println!("Enter your move (e.g., A1):");
         let mut position = None;
         loop {
@@ -16,7 +21,7 @@ impl MoveDecider for CliPlayer {
 
             match Position::from_str(input) {
                 Ok(p) => {
-                    if !game.valid_moves().iter().any(|m| *m == p) {
+                    if !valid_moves.contains(&p) {
                         println!("Invalid position.");
                         continue;
                     }
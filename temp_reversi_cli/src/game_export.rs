@@ -0,0 +1,79 @@
+use std::{fs, path::Path};
+
+use serde::Serialize;
+use temp_reversi_core::{utils::to_transcript, Game};
+
+/// A played game as written to JSON.
+#[derive(Debug, Serialize)]
+pub struct GameExport {
+    /// The moves in order, e.g. `["f5", "d6"]`.
+    pub moves: Vec<String>,
+    /// Final number of black discs.
+    pub black_score: usize,
+    /// Final number of white discs.
+    pub white_score: usize,
+    /// The result, e.g. `"Black wins by 4"`, or `None` if the game is not over.
+    pub result: Option<String>,
+}
+
+impl GameExport {
+    /// Collects the moves, score, and result of `game`.
+    pub fn from_game(game: &Game) -> Self {
+        let (black_score, white_score) = game.current_score();
+        Self {
+            moves: game
+                .move_history()
+                .iter()
+                .map(|&position| to_transcript(&[position]))
+                .collect(),
+            black_score,
+            white_score,
+            result: game.result().map(|result| result.to_string()),
+        }
+    }
+}
+
+/// Serializes `game` to a pretty-printed JSON string.
+pub fn game_to_json(game: &Game) -> Result<String, String> {
+    serde_json::to_string_pretty(&GameExport::from_game(game)).map_err(|e| e.to_string())
+}
+
+/// Writes `game` as JSON to `path`, creating the parent directory if needed.
+pub fn save_game_json(game: &Game, path: &str) -> Result<(), String> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, game_to_json(game)?).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_to_json() {
+        // Play a full game by always choosing the first valid move.
+        let mut game = Game::default();
+        while !game.is_game_over() {
+            game.apply_move(game.valid_moves()[0]).unwrap();
+        }
+
+        let json: serde_json::Value = serde_json::from_str(&game_to_json(&game).unwrap()).unwrap();
+        let moves = json["moves"].as_array().unwrap();
+        assert_eq!(moves.len(), game.history_len());
+        assert_eq!(moves[0], to_transcript(&game.move_history()[..1]));
+
+        let (black_score, white_score) = game.current_score();
+        assert_eq!(json["black_score"], black_score);
+        assert_eq!(json["white_score"], white_score);
+        assert_eq!(json["result"], game.result().unwrap().to_string());
+    }
+
+    #[test]
+    fn test_unfinished_game_has_no_result() {
+        let json: serde_json::Value =
+            serde_json::from_str(&game_to_json(&Game::default()).unwrap()).unwrap();
+        assert!(json["moves"].as_array().unwrap().is_empty());
+        assert!(json["result"].is_null());
+    }
+}
@@ -1,5 +1,11 @@
+mod analysis;
 mod cli_display;
 mod cli_player;
+mod game_export;
+mod utils;
 
+pub use analysis::*;
 pub use cli_display::*;
 pub use cli_player::*;
+pub use game_export::*;
+pub use utils::*;
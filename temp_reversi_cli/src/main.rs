@@ -1,32 +1,242 @@
+use std::time::Duration;
+
 use temp_reversi_ai::{
     evaluation::PatternEvaluator,
+    learning::{shuffle_dataset, TrainingConfig, TrainingPipeline},
     patterns::get_predefined_patterns,
-    strategy::{negamax::NegamaxStrategy, Strategy},
+    strategy::{endgame::EndgameSolver, negamax::NegamaxStrategy, Strategy},
+    test_match::run_model_tournament,
+};
+use temp_reversi_cli::{
+    analyze_position, cli_display, save_game_json, CliPlayer, GenerationReporter,
 };
-use temp_reversi_cli::{cli_display, CliPlayer};
 use temp_reversi_core::{run_game, Game, MoveDecider, Position};
 
 /// A wrapper to use NegamaxStrategy with MoveDecider trait.
+///
+/// Switches to the exact endgame solver once few enough empty squares remain.
 struct NegamaxMoveDecider {
     strategy: NegamaxStrategy<PatternEvaluator>,
+    endgame_solver: EndgameSolver,
 }
 
 impl NegamaxMoveDecider {
     pub fn new(depth: u32) -> Self {
         let evaluator = PatternEvaluator::new(get_predefined_patterns());
         let strategy = NegamaxStrategy::new(evaluator, depth);
-        Self { strategy }
+        Self {
+            strategy,
+            endgame_solver: EndgameSolver::default(),
+        }
     }
 }
 
 impl MoveDecider for NegamaxMoveDecider {
     fn select_move(&mut self, game: &Game) -> Option<Position> {
-        self.strategy.evaluate_and_decide(game)
+        if self.endgame_solver.is_applicable(game.board_state()) {
+            let selected = self.endgame_solver.evaluate_and_decide(game);
+            println!("{}", self.endgame_solver.last_stats());
+            return selected;
+        }
+
+        let selected = self.strategy.evaluate_and_decide(game);
+        println!("{}", self.strategy.last_stats());
+        selected
+    }
+}
+
+/// Default search depth of the `analyze` subcommand.
+const DEFAULT_ANALYZE_DEPTH: u32 = 5;
+
+/// Prints the search score of every legal move after `transcript`.
+///
+/// Usage: `analyze <transcript> [depth] [--model-path <path>]`
+///
+/// Without `--model-path`, the predefined patterns with untrained scores are used.
+fn analyze(args: &[String]) -> Result<(), String> {
+    let usage = "Usage: analyze <transcript> [depth] [--model-path <path>]";
+    let (args, model_path) = split_trailing_flag(args, "--model-path").ok_or(usage)?;
+    let transcript = args.first().map(String::as_str).unwrap_or("");
+    let depth = match args.get(1) {
+        Some(depth) => depth
+            .parse()
+            .map_err(|_| format!("Invalid depth: {}", depth))?,
+        None => DEFAULT_ANALYZE_DEPTH,
+    };
+
+    let evaluator = match model_path {
+        Some(path) => PatternEvaluator::load_bin(path)
+            .map_err(|e| format!("Failed to load model {}: {}", path, e))?,
+        None => PatternEvaluator::new(get_predefined_patterns()),
+    };
+    let scores = analyze_position(transcript, evaluator, depth)?;
+    if scores.is_empty() {
+        println!("No legal moves.");
+        return Ok(());
+    }
+
+    println!("Depth {}:", depth);
+    for (position, score) in &scores {
+        println!("{} {:>8}", position, score);
     }
+    println!("Recommended move: {}", scores[0].0);
+    Ok(())
+}
+
+/// Plays a game against the AI and optionally saves it as JSON.
+///
+/// Usage: `play [--output <path>]`
+fn play(args: &[String]) -> Result<(), String> {
+    let output = match args {
+        [] => None,
+        [flag, path] if flag == "--output" => Some(path.as_str()),
+        _ => return Err("Usage: play [--output <path>]".to_string()),
+    };
+
+    let mut save_result = Ok(());
+    let display = |game: &Game| {
+        cli_display(game);
+        match output {
+            Some(path) if game.is_game_over() => save_result = save_game_json(game, path),
+            _ => {}
+        }
+    };
+
+    let ai_player = NegamaxMoveDecider::new(5); // Depth of 3 for Black
+    run_game(ai_player, CliPlayer {}, display)?;
+    save_result?;
+    if let Some(path) = output {
+        println!("Game saved to {}", path);
+    }
+    Ok(())
+}
+
+/// Splits an optional trailing `<flag> <value>` pair off the arguments.
+///
+/// Returns the arguments before the flag and the flag's value, or `None` if the flag
+/// is not followed by exactly one value.
+fn split_trailing_flag<'a>(
+    args: &'a [String],
+    flag: &str,
+) -> Option<(&'a [String], Option<&'a str>)> {
+    match args.iter().position(|arg| arg == flag) {
+        Some(index) => match &args[index..] {
+            [_, value] => Some((&args[..index], Some(value.as_str()))),
+            _ => None,
+        },
+        None => Some((args, None)),
+    }
+}
+
+/// Parses the value of a `--seed` flag.
+fn parse_seed(seed: Option<&str>) -> Result<Option<u64>, String> {
+    seed.map(|seed| seed.parse().map_err(|_| format!("Invalid seed: {}", seed)))
+        .transpose()
+}
+
+/// Generates self-play games and saves them as a dataset, showing progress on stderr.
+///
+/// Usage: `generate <num_games> <dataset_path> [--seed <seed>]`
+fn generate(args: &[String]) -> Result<(), String> {
+    let usage = "Usage: generate <num_games> <dataset_path> [--seed <seed>]";
+    let (args, seed) = split_trailing_flag(args, "--seed").ok_or(usage)?;
+    let [num_games, dataset_path] = args else {
+        return Err(usage.to_string());
+    };
+    let num_games = num_games
+        .parse()
+        .map_err(|_| format!("Invalid number of games: {}", num_games))?;
+    let seed = parse_seed(seed)?;
+
+    let config = TrainingConfig {
+        num_games,
+        batch_size: 1,
+        num_epochs: 0,
+        model_path: String::new(),
+        dataset_path: dataset_path.clone(),
+        seed,
+        augment_symmetries: false,
+        deduplicate_positions: false,
+        metrics_path: None,
+    };
+    TrainingPipeline::new(config)
+        .with_reporter(Box::new(GenerationReporter::new()))
+        .generate_self_play_data();
+    Ok(())
+}
+
+/// Shuffles the records of a dataset and saves them under a new base name.
+///
+/// Usage: `shuffle <dataset_base_path> <output_base_path> [--seed <seed>]`
+///
+/// Without `--seed`, a random seed is chosen. The seed is printed either way, so
+/// the permutation can be reproduced.
+fn shuffle(args: &[String]) -> Result<(), String> {
+    let usage = "Usage: shuffle <dataset_base_path> <output_base_path> [--seed <seed>]";
+    let (args, seed) = split_trailing_flag(args, "--seed").ok_or(usage)?;
+    let [dataset_base_path, output_base_path] = args else {
+        return Err(usage.to_string());
+    };
+    let seed = parse_seed(seed)?;
+
+    let seed = shuffle_dataset(dataset_base_path, output_base_path, seed)
+        .map_err(|e| format!("Failed to shuffle {}: {}", dataset_base_path, e))?;
+    println!(
+        "Shuffled {} into {} (seed {})",
+        dataset_base_path, output_base_path, seed
+    );
+    Ok(())
+}
+
+/// Maximum thinking time per move in a tournament game.
+const TOURNAMENT_MOVE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Seed of the random openings of the `tournament` subcommand.
+const TOURNAMENT_SEED: u64 = 0;
+
+/// Plays a round-robin tournament between model files and prints the cross-table.
+///
+/// Usage: `tournament <games_per_pair> <depth> <model_path> <model_path>...`
+fn tournament(args: &[String]) -> Result<(), String> {
+    let usage = "Usage: tournament <games_per_pair> <depth> <model_path> <model_path>...";
+    let [games_per_pair, depth, model_paths @ ..] = args else {
+        return Err(usage.to_string());
+    };
+    if model_paths.len() < 2 {
+        return Err(usage.to_string());
+    }
+    let games_per_pair = games_per_pair
+        .parse()
+        .map_err(|_| format!("Invalid number of games: {}", games_per_pair))?;
+    let depth = depth
+        .parse()
+        .map_err(|_| format!("Invalid depth: {}", depth))?;
+
+    run_model_tournament(
+        model_paths,
+        depth,
+        games_per_pair,
+        TOURNAMENT_MOVE_TIMEOUT,
+        TOURNAMENT_SEED,
+    )?;
+    Ok(())
 }
 
 /// Entry point for the CLI-based Reversi game.
+///
+/// `play [--output <path>]`, the default, plays a game against the AI.
+/// `analyze <transcript> [depth] [--model-path <path>]` evaluates a position instead,
+/// `generate <num_games> <dataset_path> [--seed <seed>]` generates self-play data,
+/// `shuffle <dataset_base_path> <output_base_path> [--seed <seed>]` shuffles it, and
+/// `tournament <games_per_pair> <depth> <model_path>...` compares trained models.
 fn main() -> Result<(), String> {
-    let ai_player = NegamaxMoveDecider::new(5); // Depth of 3 for Black
-    run_game(ai_player, CliPlayer {}, cli_display)
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("analyze") => analyze(&args[1..]),
+        Some("play") => play(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        Some("shuffle") => shuffle(&args[1..]),
+        Some("tournament") => tournament(&args[1..]),
+        _ => play(&args),
+    }
 }
@@ -0,0 +1,49 @@
+use indicatif::{ProgressBar, ProgressStyle};
+use temp_reversi_ai::learning::ProgressReporter;
+
+/// Progress bar for self-play generation showing throughput and remaining time.
+///
+/// The rate and ETA are estimated by `indicatif` from a rolling average of recent
+/// steps, so they adapt when game lengths vary over a run.
+pub struct GenerationReporter {
+    bar: ProgressBar,
+}
+
+impl GenerationReporter {
+    /// Creates a reporter that draws to stderr.
+    pub fn new() -> Self {
+        let bar = ProgressBar::new(0);
+        bar.set_style(
+            ProgressStyle::with_template(
+                "[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} games ({per_sec}, ETA {eta})",
+            )
+            .unwrap()
+            .progress_chars("=> "),
+        );
+        Self { bar }
+    }
+}
+
+impl Default for GenerationReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for GenerationReporter {
+    fn set_total(&self, total: usize) {
+        self.bar.set_length(total as u64);
+    }
+
+    fn increment(&self) {
+        self.bar.inc(1);
+    }
+
+    fn message(&self, message: &str) {
+        self.bar.println(message);
+    }
+
+    fn finish(&self) {
+        self.bar.finish();
+    }
+}
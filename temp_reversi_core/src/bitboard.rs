@@ -1,8 +1,43 @@
-use std::fmt;
+use std::{fmt, sync::OnceLock};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    line::*,
+    player::*,
+    position::*,
+    utils::{transform_mask, SYMMETRY_COUNT},
+};
+
+/// Seed used to generate the Zobrist keys, fixed so hashes are reproducible across runs.
+const ZOBRIST_SEED: u64 = 0x7E4D_5EED_2B0A_4D17;
+
+/// Random keys for Zobrist hashing.
+struct ZobristTable {
+    /// One key per square for black stones.
+    black: [u64; 64],
+    /// One key per square for white stones.
+    white: [u64; 64],
+    /// Key XORed in when White is to move.
+    white_to_move: u64,
+}
 
-use crate::{player::*, position::*};
+impl ZobristTable {
+    /// Returns the shared table, generating it on first use.
+    fn get() -> &'static ZobristTable {
+        static TABLE: OnceLock<ZobristTable> = OnceLock::new();
+        TABLE.get_or_init(|| {
+            let mut rng = StdRng::seed_from_u64(ZOBRIST_SEED);
+            ZobristTable {
+                black: std::array::from_fn(|_| rng.gen()),
+                white: std::array::from_fn(|_| rng.gen()),
+                white_to_move: rng.gen(),
+            }
+        })
+    }
+}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Bitboard {
     black: u64, // Bitboard for black stones
     white: u64, // Bitboard for white stones
@@ -31,6 +66,9 @@ impl Bitboard {
         (-7, 0x00fefefefefefefe), // Up-right
     ];
 
+    /// Number of distinct game phases returned by [`Bitboard::phase`].
+    pub const PHASE_COUNT: usize = 60;
+
     /// Creates a new Bitboard with the specified black and white stone positions.
     pub fn new(black: u64, white: u64) -> Self {
         Self { black, white }
@@ -50,6 +88,92 @@ impl Bitboard {
         self.bitmask_to_positions(bitmask)
     }
 
+    /// Returns the number of valid moves for the specified player without allocating.
+    ///
+    /// # Arguments
+    /// * `player` - The current player (Black or White).
+    pub fn valid_moves_count(&self, player: Player) -> u32 {
+        self.valid_moves_bitmask(player).count_ones()
+    }
+
+    /// Returns the discs of the specified player that are adjacent to an empty square.
+    ///
+    /// # Arguments
+    /// * `player` - The player whose frontier discs are returned.
+    pub fn frontier_discs(&self, player: Player) -> u64 {
+        let player_bits = match player {
+            Player::Black => self.black,
+            Player::White => self.white,
+        };
+        let empty = !(self.black | self.white);
+
+        let next_to_empty = Self::DIRECTIONS
+            .iter()
+            .fold(0u64, |acc, &(shift_amount, mask)| {
+                acc | Self::safe_shift(empty, shift_amount, mask)
+            });
+        player_bits & next_to_empty
+    }
+
+    /// Returns the number of empty regions with an odd number of squares.
+    ///
+    /// Empty squares are grouped into regions connected horizontally and vertically.
+    /// In the endgame, the player who moves last in a region usually gains from it,
+    /// so odd-sized regions are a parity signal for endgame evaluation.
+    pub fn empty_region_parity(&self) -> i32 {
+        let mut empty = !(self.black | self.white);
+        let mut odd_regions = 0;
+
+        while empty != 0 {
+            // Flood-fill the region containing the lowest empty square
+            let mut region = empty & empty.wrapping_neg();
+            loop {
+                let grown = Self::DIRECTIONS[..4]
+                    .iter()
+                    .fold(region, |acc, &(shift_amount, mask)| {
+                        acc | Self::safe_shift(region, shift_amount, mask)
+                    })
+                    & empty;
+                if grown == region {
+                    break;
+                }
+                region = grown;
+            }
+
+            if region.count_ones() % 2 == 1 {
+                odd_regions += 1;
+            }
+            empty &= !region;
+        }
+
+        odd_regions
+    }
+
+    /// Returns the base-3 index of the discs on a line.
+    ///
+    /// Each square contributes 0 if empty, 1 for black and 2 for white. The squares are
+    /// taken in increasing bit order, the first being the least significant digit, as
+    /// in the pattern state indices. The index is within `0..3^8`.
+    ///
+    /// # Arguments
+    /// * `line` - The rank, file or diagonal to encode.
+    pub fn line_index(&self, line: Line) -> u16 {
+        let mut squares = line.mask();
+        let mut index = 0;
+        let mut weight = 1;
+        while squares != 0 {
+            let bit = squares & squares.wrapping_neg();
+            if self.black & bit != 0 {
+                index += weight;
+            } else if self.white & bit != 0 {
+                index += 2 * weight;
+            }
+            weight *= 3;
+            squares &= squares - 1;
+        }
+        index
+    }
+
     /// Counts the number of stones for both black and white players.
     ///
     /// # Returns
@@ -61,9 +185,22 @@ impl Bitboard {
         )
     }
 
+    /// Returns the game phase, defined as the number of moves played so far.
+    ///
+    /// The phase is `disc_count - 4`, clamped to `0..Self::PHASE_COUNT`, so the
+    /// opening position is phase 0 and the last move is played in phase 59.
+    /// In terms of empty squares, the phase is `60 - empty_count`: 60 empties map
+    /// to phase 0, 1 empty maps to phase 59, and a full board is clamped to 59.
+    pub fn phase(&self) -> usize {
+        let (black_count, white_count) = self.count_stones();
+        (black_count + white_count)
+            .saturating_sub(4)
+            .min(Self::PHASE_COUNT - 1)
+    }
+
     /// Checks if the game is over. The game ends if neither player has any valid moves.
     pub fn is_game_over(&self) -> bool {
-        self.valid_moves(Player::Black).is_empty() && self.valid_moves(Player::White).is_empty()
+        self.valid_moves_bitmask(Player::Black) == 0 && self.valid_moves_bitmask(Player::White) == 0
     }
 
     /// Applies a move to the board for the specified player.
@@ -75,6 +212,23 @@ impl Bitboard {
     /// # Returns
     /// `Ok(())` if the move is valid and applied successfully, otherwise an error message.
     pub fn apply_move(&mut self, position: Position, player: Player) -> Result<(), &'static str> {
+        self.apply_move_with_flips(position, player).map(|_| ())
+    }
+
+    /// Applies a move to the board and returns the flipped stones.
+    ///
+    /// # Arguments
+    /// * `position` - The position to place the stone.
+    /// * `player` - The current player making the move.
+    ///
+    /// # Returns
+    /// `Ok(flips)` with the bitmask of flipped stones if the move is valid and applied
+    /// successfully, otherwise an error message.
+    pub fn apply_move_with_flips(
+        &mut self,
+        position: Position,
+        player: Player,
+    ) -> Result<u64, &'static str> {
         let move_bit = position.to_bit();
 
         // Check if the position is already occupied.
@@ -99,7 +253,115 @@ impl Bitboard {
         *player_bits |= move_bit | flips;
         *opponent_bits &= !flips;
 
-        Ok(())
+        Ok(flips)
+    }
+
+    /// Returns the canonical representative of this board under the 8 board symmetries.
+    ///
+    /// All rotated and mirrored variants of a board share the same canonical form.
+    pub fn canonical(&self) -> Bitboard {
+        self.canonical_with_transform().0
+    }
+
+    /// Returns the canonical board together with the symmetry that produces it.
+    ///
+    /// The canonical board is the variant with the lexicographically smallest
+    /// `(black, white)` pair.
+    ///
+    /// # Returns
+    /// A tuple of the canonical board and the symmetry index to pass to
+    /// [`utils::transform_mask`](crate::utils::transform_mask) /
+    /// [`utils::inverse_transform_mask`](crate::utils::inverse_transform_mask) to map
+    /// positions between the two orientations.
+    pub fn canonical_with_transform(&self) -> (Bitboard, u8) {
+        let ((black, white), transform) = (0..SYMMETRY_COUNT)
+            .map(|t| {
+                (
+                    (transform_mask(self.black, t), transform_mask(self.white, t)),
+                    t,
+                )
+            })
+            .min()
+            .unwrap();
+        (Bitboard::new(black, white), transform)
+    }
+
+    /// Returns the stones that would be flipped if `player` played at `position`.
+    ///
+    /// # Returns
+    /// A bitmask of stones to be flipped, or 0 if the square is occupied or the move flips nothing.
+    pub fn flips(&self, position: Position, player: Player) -> u64 {
+        let move_bit = position.to_bit();
+        if (self.black | self.white) & move_bit != 0 {
+            return 0;
+        }
+
+        let (player_bits, opponent_bits) = match player {
+            Player::Black => (self.black, self.white),
+            Player::White => (self.white, self.black),
+        };
+        Self::get_flips_bits(move_bit, player_bits, opponent_bits)
+    }
+
+    /// Computes the Zobrist hash of the board with `player` to move.
+    ///
+    /// The keys are generated once from a fixed seed, so the hash is stable across runs.
+    pub fn zobrist_hash(&self, player: Player) -> u64 {
+        let table = ZobristTable::get();
+        let mut hash = 0;
+
+        let mut bits = self.black;
+        while bits != 0 {
+            hash ^= table.black[bits.trailing_zeros() as usize];
+            bits &= bits - 1;
+        }
+
+        let mut bits = self.white;
+        while bits != 0 {
+            hash ^= table.white[bits.trailing_zeros() as usize];
+            bits &= bits - 1;
+        }
+
+        if player == Player::White {
+            hash ^= table.white_to_move;
+        }
+
+        hash
+    }
+
+    /// Derives the hash of a child position from its parent's hash.
+    ///
+    /// The cost is proportional to the number of flipped stones rather than the number of
+    /// stones on the board. The side to move is switched to the opponent; if the opponent
+    /// has to pass, apply [`Bitboard::zobrist_pass`] to the result.
+    ///
+    /// # Arguments
+    /// * `hash` - Hash of the parent position with `player` to move.
+    /// * `mv` - The move played.
+    /// * `flips` - The stones flipped by the move (see [`Bitboard::flips`]).
+    /// * `player` - The player making the move.
+    pub fn zobrist_update(hash: u64, mv: Position, flips: u64, player: Player) -> u64 {
+        let table = ZobristTable::get();
+        let (own_keys, opponent_keys) = match player {
+            Player::Black => (&table.black, &table.white),
+            Player::White => (&table.white, &table.black),
+        };
+
+        let mut hash = hash ^ own_keys[mv.to_bit().trailing_zeros() as usize];
+
+        let mut bits = flips;
+        while bits != 0 {
+            let square = bits.trailing_zeros() as usize;
+            hash ^= own_keys[square] ^ opponent_keys[square];
+            bits &= bits - 1;
+        }
+
+        Self::zobrist_pass(hash)
+    }
+
+    /// Switches the side to move in a Zobrist hash, as happens on a pass.
+    pub fn zobrist_pass(hash: u64) -> u64 {
+        hash ^ ZobristTable::get().white_to_move
     }
 
     /// Safely shifts bits in a specified direction, applying a mask to prevent invalid shifts.
@@ -625,6 +887,113 @@ mod tests {
         assert!(board.apply_move(position, Player::Black).is_err());
     }
 
+    #[test]
+    fn test_line_index() {
+        // Black on A1 and D1, white on B1: 1 + 2 * 3 + 1 * 27.
+        let board = Bitboard::new(Position::A1 | Position::D1, Position::B1.to_bit());
+        assert_eq!(board.line_index(Line::Rank(0)), 34);
+        assert_eq!(board.line_index(Line::Rank(1)), 0);
+
+        // A1 is also the first square of file A and of the main diagonal.
+        assert_eq!(board.line_index(Line::File(0)), 1);
+        assert_eq!(board.line_index(Line::File(1)), 2);
+        assert_eq!(board.line_index(Line::Diagonal), 1);
+
+        // H1 is the first square of the anti-diagonal and A8 the last.
+        let board = Bitboard::new(Position::A8.to_bit(), Position::H1.to_bit());
+        assert_eq!(board.line_index(Line::AntiDiagonal), 2 + 3_u16.pow(7));
+
+        // A full white rank has the largest index.
+        let board = Bitboard::new(0, 0xff << 56);
+        assert_eq!(board.line_index(Line::Rank(7)), 3_u16.pow(8) - 1);
+
+        // The start position has discs on ranks 4 and 5 only.
+        let board = Bitboard::default();
+        assert_eq!(board.line_index(Line::Rank(3)), 2 * 27 + 81);
+        assert_eq!(board.line_index(Line::Rank(0)), 0);
+    }
+
+    #[test]
+    fn test_empty_region_parity() {
+        // A single empty corner is one odd region.
+        let board = Bitboard::new(!Position::A1.to_bit(), 0);
+        assert_eq!(board.empty_region_parity(), 1);
+
+        // Two pairs of empty squares in opposite corners are two even regions.
+        let empty = Position::A1 | Position::B1 | Position::H8 | Position::H7;
+        let board = Bitboard::new(!empty & 0xffffffff00000000, !empty & 0x00000000ffffffff);
+        assert_eq!(board.empty_region_parity(), 0);
+
+        // Diagonal neighbors are separate regions.
+        let empty = Position::A1 | Position::B2;
+        let board = Bitboard::new(!empty, 0);
+        assert_eq!(board.empty_region_parity(), 2);
+
+        // The 60 empty squares of the start position form a single even region.
+        assert_eq!(Bitboard::default().empty_region_parity(), 0);
+    }
+
+    #[test]
+    fn test_frontier_discs() {
+        // All four center discs of the start position touch an empty square.
+        let board = Bitboard::default();
+        assert_eq!(
+            board.frontier_discs(Player::Black),
+            Position::D5 | Position::E4
+        );
+        assert_eq!(
+            board.frontier_discs(Player::White),
+            Position::D4 | Position::E5
+        );
+
+        // Only the center of a 3x3 cluster is surrounded by discs.
+        let cluster = 0x0000001c1c1c0000;
+        let board = Bitboard::new(cluster, 0);
+        assert_eq!(
+            board.frontier_discs(Player::Black),
+            cluster & !Position::D4.to_bit()
+        );
+        assert_eq!(board.frontier_discs(Player::White), 0);
+
+        // A full board has no frontier.
+        let board = Bitboard::new(u64::MAX, 0);
+        assert_eq!(board.frontier_discs(Player::Black), 0);
+    }
+
+    #[test]
+    fn test_apply_move_with_flips() {
+        // Standard opening move: Black F5 flips E5.
+        let mut board = Bitboard::default();
+        let flips = board
+            .apply_move_with_flips(Position::F5, Player::Black)
+            .unwrap();
+        assert_eq!(flips, Position::E5.to_bit());
+
+        // The flips match get_flips_bits along a random game.
+        let mut rng = thread_rng();
+        let mut board = Bitboard::default();
+        let mut player = Player::Black;
+        while !board.is_game_over() {
+            let valid_moves = board.valid_moves(player);
+            if let Some(&mv) = valid_moves.choose(&mut rng) {
+                let (player_bits, opponent_bits) = match player {
+                    Player::Black => (board.black, board.white),
+                    Player::White => (board.white, board.black),
+                };
+                let expected = Bitboard::get_flips_bits(mv.to_bit(), player_bits, opponent_bits);
+                assert_eq!(board.apply_move_with_flips(mv, player), Ok(expected));
+            }
+            player = player.opponent();
+        }
+
+        // Invalid moves leave the board unchanged.
+        let mut board = Bitboard::default();
+        assert!(board
+            .apply_move_with_flips(Position::A1, Player::Black)
+            .is_err());
+        assert_eq!(board, Bitboard::default());
+    }
+
     #[test]
     fn test_get_flips_bits() {
         let black = Position::D5 | Position::E4;
@@ -715,6 +1084,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_canonical() {
+        let mut board = Bitboard::default();
+        board.apply_move(Position::F5, Player::Black).unwrap();
+        board.apply_move(Position::D6, Player::White).unwrap();
+        board.apply_move(Position::C3, Player::Black).unwrap();
+        let canonical = board.canonical();
+
+        for t in 0..SYMMETRY_COUNT {
+            let variant = Bitboard::new(
+                transform_mask(board.black, t),
+                transform_mask(board.white, t),
+            );
+            assert_eq!(variant.canonical(), canonical);
+
+            let (variant_canonical, transform) = variant.canonical_with_transform();
+            assert_eq!(
+                Bitboard::new(
+                    transform_mask(variant.black, transform),
+                    transform_mask(variant.white, transform)
+                ),
+                variant_canonical
+            );
+        }
+    }
+
+    #[test]
+    fn test_valid_moves_count() {
+        let mut rng = thread_rng();
+        let mut board = Bitboard::default();
+        let mut player = Player::Black;
+
+        while !board.is_game_over() {
+            for p in [Player::Black, Player::White] {
+                assert_eq!(
+                    board.valid_moves_count(p) as usize,
+                    board.valid_moves(p).len()
+                );
+            }
+
+            let valid_moves = board.valid_moves(player);
+            if let Some(&mv) = valid_moves.choose(&mut rng) {
+                board.apply_move(mv, player).unwrap();
+            }
+            player = player.opponent();
+        }
+    }
+
+    #[test]
+    fn test_zobrist_incremental_matches_full() {
+        let mut rng = thread_rng();
+        let mut board = Bitboard::default();
+        let mut player = Player::Black;
+        let mut hash = board.zobrist_hash(player);
+
+        while !board.is_game_over() {
+            let valid_moves = board.valid_moves(player);
+            if valid_moves.is_empty() {
+                player = player.opponent();
+                hash = Bitboard::zobrist_pass(hash);
+                assert_eq!(hash, board.zobrist_hash(player));
+                continue;
+            }
+
+            let mv = *valid_moves.choose(&mut rng).unwrap();
+            let flips = board.flips(mv, player);
+            hash = Bitboard::zobrist_update(hash, mv, flips, player);
+            board.apply_move(mv, player).unwrap();
+            player = player.opponent();
+
+            assert_eq!(hash, board.zobrist_hash(player));
+        }
+    }
+
+    #[test]
+    fn test_zobrist_side_to_move() {
+        let board = Bitboard::default();
+        assert_ne!(
+            board.zobrist_hash(Player::Black),
+            board.zobrist_hash(Player::White)
+        );
+    }
+
     #[test]
     fn test_random_simulation() {
         let mut board = Bitboard::default();
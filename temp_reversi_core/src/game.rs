@@ -1,7 +1,92 @@
+use std::fmt;
+
 use crate::bitboard::Bitboard;
 use crate::player::Player;
 use crate::position::Position;
 
+/// Errors that can occur when applying moves to a `Game`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    /// The position is not a valid move for the current player.
+    InvalidMove(Position),
+    /// The game is already over, so no further moves can be applied.
+    GameOver,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::InvalidMove(position) => write!(f, "Invalid move: {}", position),
+            MoveError::GameOver => write!(f, "Game is already over"),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// The result of a finished game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    /// Black won by the given disc differential.
+    BlackWins(usize),
+    /// White won by the given disc differential.
+    WhiteWins(usize),
+    /// Both players have the same number of discs.
+    Draw,
+}
+
+impl GameResult {
+    /// Returns the winning player, or `None` for a draw.
+    pub fn winner(&self) -> Option<Player> {
+        match self {
+            GameResult::BlackWins(_) => Some(Player::Black),
+            GameResult::WhiteWins(_) => Some(Player::White),
+            GameResult::Draw => None,
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameResult::BlackWins(margin) => write!(f, "Black wins by {}", margin),
+            GameResult::WhiteWins(margin) => write!(f, "White wins by {}", margin),
+            GameResult::Draw => write!(f, "Draw"),
+        }
+    }
+}
+
+/// The moves available to the player to move, as returned by [`Game::legal_moves`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LegalMoves {
+    /// The player to move can play any of the given positions.
+    Moves(Vec<Position>),
+    /// The player to move has no valid moves and must pass.
+    MustPass,
+    /// Neither player can move.
+    GameOver,
+}
+
+/// A snapshot of the game state together with the move played from it.
+#[derive(Debug, Clone)]
+struct HistoryEntry {
+    /// Board state.
+    board: Bitboard,
+    /// Player to move in this state.
+    current_player: Player,
+    /// The move that connects this state to the neighbouring one.
+    position: Position,
+}
+
+/// Undo and redo stacks of a `Game`.
+#[derive(Debug, Clone, Default)]
+struct GameHistory {
+    /// States before each applied move, most recent last.
+    undo_stack: Vec<HistoryEntry>,
+    /// States after each undone move, most recently undone last.
+    redo_stack: Vec<HistoryEntry>,
+}
+
 /// Struct to manage the overall state of an Othello game.
 #[derive(Debug)]
 pub struct Game {
@@ -9,6 +94,8 @@ pub struct Game {
     board: Bitboard,
     /// Current player (Black or White).
     current_player: Player,
+    /// Move history used for undo and redo.
+    history: GameHistory,
 }
 
 impl Default for Game {
@@ -17,6 +104,7 @@ impl Default for Game {
         Self {
             board: Default::default(),
             current_player: Player::Black,
+            history: GameHistory::default(),
         }
     }
 }
@@ -31,7 +119,52 @@ impl Game {
         Self {
             board,
             current_player,
+            history: GameHistory::default(),
+        }
+    }
+
+    /// Replays a move sequence from the initial position.
+    ///
+    /// Passes are inserted automatically when a player has no legal moves.
+    ///
+    /// # Arguments
+    /// * `moves` - The moves as board indices (0-63), as stored in game records.
+    ///
+    /// # Returns
+    /// - `Ok(Game)` with all moves applied.
+    /// - `Err(String)` describing the first move that could not be applied and its ply index.
+    pub fn from_moves(moves: &[u8]) -> Result<Game, String> {
+        Self::at_ply(moves, moves.len())
+    }
+
+    /// Replays the first `ply` moves of a move sequence from the initial position.
+    ///
+    /// # Arguments
+    /// * `moves` - The moves as board indices (0-63), as stored in game records.
+    /// * `ply` - The number of moves to replay.
+    ///
+    /// # Returns
+    /// - `Ok(Game)` with the first `ply` moves applied.
+    /// - `Err(String)` if `ply` exceeds the number of moves or a move cannot be applied.
+    pub fn at_ply(moves: &[u8], ply: usize) -> Result<Game, String> {
+        if ply > moves.len() {
+            return Err(format!(
+                "Ply {} is out of range for {} moves",
+                ply,
+                moves.len()
+            ));
         }
+
+        let positions = moves[..ply]
+            .iter()
+            .enumerate()
+            .map(|(index, &mv)| Position::from_u8(mv).map_err(|e| format!("Ply {}: {}", index, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut game = Game::default();
+        game.apply_moves(&positions)
+            .map_err(|(index, err)| format!("Ply {}: {}", index, err))?;
+        Ok(game)
     }
 
     /// Returns the current player.
@@ -47,6 +180,23 @@ impl Game {
         self.board.valid_moves(self.current_player)
     }
 
+    /// Gets the legal moves for the current player, taking passes into account.
+    ///
+    /// # Returns
+    /// - `LegalMoves::Moves` with the valid moves if the current player can move.
+    /// - `LegalMoves::MustPass` if the current player has no moves but the opponent does.
+    /// - `LegalMoves::GameOver` if neither player can move.
+    pub fn legal_moves(&self) -> LegalMoves {
+        let moves = self.valid_moves();
+        if !moves.is_empty() {
+            LegalMoves::Moves(moves)
+        } else if self.is_game_over() {
+            LegalMoves::GameOver
+        } else {
+            LegalMoves::MustPass
+        }
+    }
+
     /// Checks if a move at the specified position is valid.
     ///
     /// # Arguments
@@ -71,7 +221,15 @@ impl Game {
             return Err("Invalid move");
         }
 
+        let previous = HistoryEntry {
+            board: self.board.clone(),
+            current_player: self.current_player,
+            position,
+        };
+
         self.board.apply_move(position, self.current_player)?;
+        self.history.undo_stack.push(previous);
+        self.history.redo_stack.clear();
         self.switch_turn();
 
         if self.valid_moves().is_empty() {
@@ -81,6 +239,110 @@ impl Game {
         Ok(())
     }
 
+    /// Passes the turn when the current player has no valid moves.
+    ///
+    /// `apply_move` already passes automatically, so this is only needed for games
+    /// constructed in a position where the player to move must pass.
+    ///
+    /// The pass is not recorded as an undoable entry: such a position is never
+    /// reached through the history, so undoing the following move returns to the
+    /// position after the pass. Like a move, the pass clears the redo stack.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the turn was passed.
+    /// - `Err(&str)` if the current player has a valid move or the game is over.
+    pub fn pass(&mut self) -> Result<(), &'static str> {
+        if self.legal_moves() != LegalMoves::MustPass {
+            return Err("Pass is not allowed");
+        }
+        self.history.redo_stack.clear();
+        self.switch_turn();
+        Ok(())
+    }
+
+    /// Applies a sequence of moves in order, passing automatically when forced.
+    ///
+    /// Application stops at the first move that cannot be applied; moves before it
+    /// remain applied.
+    ///
+    /// # Arguments
+    /// * `moves` - The positions to play, in order.
+    ///
+    /// # Returns
+    /// - `Ok(())` if all moves were applied.
+    /// - `Err((index, MoveError))` with the index of the first move that failed.
+    pub fn apply_moves(&mut self, moves: &[Position]) -> Result<(), (usize, MoveError)> {
+        for (index, &position) in moves.iter().enumerate() {
+            match self.legal_moves() {
+                LegalMoves::GameOver => return Err((index, MoveError::GameOver)),
+                // The current player may have no moves if the game was constructed mid-pass.
+                LegalMoves::MustPass => self.pass().expect("The current player must pass"),
+                LegalMoves::Moves(_) => {}
+            }
+
+            self.apply_move(position)
+                .map_err(|_| (index, MoveError::InvalidMove(position)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Undoes the last applied move, restoring the board and the player to move.
+    ///
+    /// Forced passes are undone together with the move that caused them.
+    ///
+    /// # Returns
+    /// - `Some(Position)` with the undone move.
+    /// - `None` if there is no move to undo.
+    pub fn undo_move(&mut self) -> Option<Position> {
+        let entry = self.history.undo_stack.pop()?;
+        self.history.redo_stack.push(HistoryEntry {
+            board: std::mem::replace(&mut self.board, entry.board),
+            current_player: std::mem::replace(&mut self.current_player, entry.current_player),
+            position: entry.position,
+        });
+        Some(entry.position)
+    }
+
+    /// Redoes the last undone move.
+    ///
+    /// # Returns
+    /// - `Some(Position)` with the redone move.
+    /// - `None` if there is no move to redo.
+    pub fn redo_move(&mut self) -> Option<Position> {
+        let entry = self.history.redo_stack.pop()?;
+        self.history.undo_stack.push(HistoryEntry {
+            board: std::mem::replace(&mut self.board, entry.board),
+            current_player: std::mem::replace(&mut self.current_player, entry.current_player),
+            position: entry.position,
+        });
+        Some(entry.position)
+    }
+
+    /// Returns `true` if there is a move that can be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.history.undo_stack.is_empty()
+    }
+
+    /// Returns `true` if there is a move that can be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.history.redo_stack.is_empty()
+    }
+
+    /// Returns the number of moves applied since the game was created.
+    pub fn history_len(&self) -> usize {
+        self.history.undo_stack.len()
+    }
+
+    /// Returns the moves applied since the game was created, in order.
+    pub fn move_history(&self) -> Vec<Position> {
+        self.history
+            .undo_stack
+            .iter()
+            .map(|entry| entry.position)
+            .collect()
+    }
+
     /// Checks if the game is over.
     ///
     /// # Returns
@@ -110,6 +372,25 @@ impl Game {
         }
     }
 
+    /// Returns the result of the game.
+    ///
+    /// # Returns
+    /// - `Some(GameResult)` with the winner and the disc differential once the game is over.
+    /// - `None` if the game is not yet over.
+    pub fn result(&self) -> Option<GameResult> {
+        if !self.is_game_over() {
+            return None;
+        }
+
+        let (black_count, white_count) = self.board.count_stones();
+        let result = match black_count.cmp(&white_count) {
+            std::cmp::Ordering::Greater => GameResult::BlackWins(black_count - white_count),
+            std::cmp::Ordering::Less => GameResult::WhiteWins(white_count - black_count),
+            std::cmp::Ordering::Equal => GameResult::Draw,
+        };
+        Some(result)
+    }
+
     /// Gets the current score of the game.
     ///
     /// # Returns
@@ -118,11 +399,28 @@ impl Game {
         self.board.count_stones()
     }
 
+    /// Gets the current game phase.
+    ///
+    /// # Returns
+    /// The number of moves played so far (see [`Bitboard::phase`]).
+    pub fn phase(&self) -> usize {
+        self.board.phase()
+    }
+
     /// Returns the current state of the board.
     pub fn board_state(&self) -> &Bitboard {
         &self.board
     }
 
+    /// Returns a key identifying the position in a transposition table.
+    ///
+    /// The key is the Zobrist hash of the board with the side to move, so the same
+    /// stones with a different player to move are distinct positions. It is cheaper
+    /// than hashing the whole game, which also carries the move history.
+    pub fn tt_key(&self) -> u64 {
+        self.board.zobrist_hash(self.current_player)
+    }
+
     /// Switches the turn to the other player. (Internal use only)
     fn switch_turn(&mut self) {
         self.current_player = self.current_player.opponent();
@@ -133,6 +431,7 @@ impl Game {
 mod tests {
     use super::*;
     use crate::position::*;
+    use crate::utils::parse_transcript;
 
     #[test]
     fn test_game_initialization() {
@@ -162,6 +461,43 @@ mod tests {
         assert_eq!(valid_moves.len(), 4);
     }
 
+    #[test]
+    fn test_legal_moves() {
+        // The initial position has four moves for Black.
+        let mut game = Game::default();
+        assert_eq!(game.legal_moves(), LegalMoves::Moves(game.valid_moves()));
+        assert!(game.pass().is_err());
+
+        // White cannot flank A1, but Black can play C1.
+        let board = Bitboard::new(Position::A1.to_bit(), Position::B1.to_bit());
+        let mut game = Game::new(board, Player::White);
+        assert_eq!(game.legal_moves(), LegalMoves::MustPass);
+        assert!(game.pass().is_ok());
+        assert_eq!(game.current_player(), Player::Black);
+        assert_eq!(game.legal_moves(), LegalMoves::Moves(vec![Position::C1]));
+
+        // A board with stones of only one color is terminal.
+        let board = Bitboard::new(Position::A1 | Position::B1, 0);
+        let mut game = Game::new(board, Player::Black);
+        assert_eq!(game.legal_moves(), LegalMoves::GameOver);
+        assert!(game.pass().is_err());
+    }
+
+    #[test]
+    fn test_pass_is_not_undoable() {
+        let board = Bitboard::new(Position::A1.to_bit(), Position::B1.to_bit());
+        let mut game = Game::new(board, Player::White);
+        game.pass().unwrap();
+        assert!(!game.can_undo());
+        assert!(!game.can_redo());
+
+        // Undoing the move after the pass returns to the position after the pass.
+        game.apply_move(Position::C1).unwrap();
+        assert_eq!(game.undo_move(), Some(Position::C1));
+        assert_eq!(game.current_player(), Player::Black);
+        assert_eq!(game.undo_move(), None);
+    }
+
     #[test]
     fn test_apply_move_and_turn_switch() {
         // Test if a move is applied correctly and turn switches.
@@ -177,6 +513,180 @@ mod tests {
         assert_eq!(white_count, 1);
     }
 
+    #[test]
+    fn test_apply_moves() {
+        // Apply a legal opening sequence in one call.
+        let mut game = Game::default();
+        let moves = [
+            Position::F5,
+            Position::D6,
+            Position::C3,
+            Position::D3,
+            Position::C4,
+        ];
+        assert!(game.apply_moves(&moves).is_ok());
+        assert_eq!(game.current_player(), Player::White);
+
+        let (black_count, white_count) = game.current_score();
+        assert_eq!(black_count + white_count, 9);
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut game = Game::default();
+        assert!(!game.can_undo());
+        assert!(!game.can_redo());
+
+        game.apply_moves(&[Position::F5, Position::D6]).unwrap();
+        assert_eq!(game.history_len(), 2);
+
+        // Undo the last move.
+        assert_eq!(game.undo_move(), Some(Position::D6));
+        assert_eq!(game.current_player(), Player::White);
+        assert_eq!(game.current_score(), (4, 1));
+        assert!(game.can_redo());
+
+        // Undo back to the initial position.
+        assert_eq!(game.undo_move(), Some(Position::F5));
+        assert_eq!(game.board_state().bits(), Bitboard::default().bits());
+        assert_eq!(game.undo_move(), None);
+
+        // Redo both moves.
+        assert_eq!(game.redo_move(), Some(Position::F5));
+        assert_eq!(game.redo_move(), Some(Position::D6));
+        assert_eq!(game.redo_move(), None);
+        assert_eq!(game.current_player(), Player::Black);
+        assert_eq!(game.current_score(), (3, 3));
+
+        // Applying a new move clears the redo stack.
+        game.undo_move();
+        game.apply_move(Position::F6).unwrap();
+        assert!(!game.can_redo());
+        assert_eq!(game.history_len(), 2);
+        assert_eq!(game.move_history(), vec![Position::F5, Position::F6]);
+    }
+
+    #[test]
+    fn test_undo_forced_pass() {
+        // After the last move of this transcript White has no legal move, so Black moves again.
+        let transcript = "D3C3E6D6B2F4D7E3E2D2E1C4B5B3B4D8C7B8C6A1G4C1C8F3D1A2A3E7F7G3E8F8H3B7F5G5A8A5G8B6F2H4A6A4H5H6F6F1H7G7C5A7G6H2C2H8B1";
+        let moves = parse_transcript(transcript).unwrap();
+
+        let mut game = Game::default();
+        game.apply_moves(&moves).unwrap();
+        assert_eq!(game.current_player(), Player::Black);
+
+        // Undoing restores Black as the player who made the last move.
+        let (last_black, last_white) = game.current_score();
+        assert_eq!(game.undo_move(), moves.last().copied());
+        assert_eq!(game.current_player(), Player::Black);
+
+        // Redoing restores the position after the pass.
+        assert_eq!(game.redo_move(), moves.last().copied());
+        assert_eq!(game.current_player(), Player::Black);
+        assert_eq!(game.current_score(), (last_black, last_white));
+    }
+
+    #[test]
+    fn test_from_moves() {
+        let moves: Vec<u8> = [Position::F5, Position::D6, Position::C3]
+            .iter()
+            .map(|p| p.to_u8())
+            .collect();
+
+        let game = Game::from_moves(&moves).unwrap();
+        assert_eq!(game.current_player(), Player::White);
+        assert_eq!(game.current_score(), (5, 2));
+
+        let game = Game::at_ply(&moves, 1).unwrap();
+        assert_eq!(game.current_player(), Player::White);
+        assert_eq!(game.current_score(), (4, 1));
+
+        assert!(Game::at_ply(&moves, 4).is_err());
+    }
+
+    #[test]
+    fn test_from_moves_with_forced_pass() {
+        // After the last move of this transcript White has no legal move, so Black moves again.
+        let transcript = "D3C3E6D6B2F4D7E3E2D2E1C4B5B3B4D8C7B8C6A1G4C1C8F3D1A2A3E7F7G3E8F8H3B7F5G5A8A5G8B6F2H4A6A4H5H6F6F1H7G7C5A7G6H2C2H8B1";
+        let moves: Vec<u8> = parse_transcript(transcript)
+            .unwrap()
+            .iter()
+            .map(|p| p.to_u8())
+            .collect();
+
+        let game = Game::from_moves(&moves).unwrap();
+        assert_eq!(game.current_player(), Player::Black);
+
+        let before_pass = Game::at_ply(&moves, moves.len() - 1).unwrap();
+        assert_eq!(before_pass.current_player(), Player::Black);
+    }
+
+    #[test]
+    fn test_from_moves_illegal_move() {
+        let moves = [Position::F5.to_u8(), Position::A1.to_u8()];
+        let err = Game::from_moves(&moves).unwrap_err();
+        assert!(err.starts_with("Ply 1"), "{}", err);
+
+        let err = Game::from_moves(&[64]).unwrap_err();
+        assert!(err.starts_with("Ply 0"), "{}", err);
+    }
+
+    #[test]
+    fn test_tt_key() {
+        let mut game = Game::default();
+        let black_to_move = game.tt_key();
+        let white_to_move = Game::new(Bitboard::default(), Player::White).tt_key();
+        assert_ne!(black_to_move, white_to_move);
+
+        // The history is not part of the key.
+        game.apply_move(Position::F5).unwrap();
+        let replayed = Game::new(game.board_state().clone(), game.current_player());
+        assert_eq!(game.tt_key(), replayed.tt_key());
+    }
+
+    #[test]
+    fn test_phase() {
+        // The opening position is phase 0.
+        let mut game = Game::default();
+        assert_eq!(game.phase(), 0);
+
+        // Each move adds one disc, advancing the phase by one.
+        let moves = [
+            Position::F5,
+            Position::D6,
+            Position::C3,
+            Position::D3,
+            Position::C4,
+        ];
+        game.apply_moves(&moves).unwrap();
+        assert_eq!(game.phase(), 5);
+
+        // Near the end, the phase is 60 minus the number of empty squares.
+        let near_end = Game::new(Bitboard::new(u64::MAX >> 2, 0), Player::White);
+        assert_eq!(near_end.phase(), 58);
+
+        // A full board is clamped to the last phase.
+        let full = Game::new(Bitboard::new(u64::MAX, 0), Player::White);
+        assert_eq!(full.phase(), Bitboard::PHASE_COUNT - 1);
+    }
+
+    #[test]
+    fn test_apply_moves_illegal_move() {
+        // The third move is illegal, so application stops there.
+        let mut game = Game::default();
+        let moves = [Position::F5, Position::D6, Position::A1, Position::C3];
+        assert_eq!(
+            game.apply_moves(&moves),
+            Err((2, MoveError::InvalidMove(Position::A1)))
+        );
+
+        // The moves before the illegal one remain applied.
+        let (black_count, white_count) = game.current_score();
+        assert_eq!(black_count + white_count, 6);
+        assert_eq!(game.current_player(), Player::Black);
+    }
+
     #[test]
     fn test_game_over_and_winner() {
         // Test game-over logic and determining the winner.
@@ -194,4 +704,33 @@ mod tests {
             _ => panic!("Expected Black to win"),
         }
     }
+
+    #[test]
+    fn test_result() {
+        // Black wins: 40 black discs, 24 white discs.
+        let game = Game::new(
+            Bitboard::new(0xffffffffff000000, 0x0000000000ffffff),
+            Player::Black,
+        );
+        assert_eq!(game.result(), Some(GameResult::BlackWins(16)));
+        assert_eq!(game.result().unwrap().winner(), Some(Player::Black));
+
+        // White wins: 24 black discs, 40 white discs.
+        let game = Game::new(
+            Bitboard::new(0x0000000000ffffff, 0xffffffffff000000),
+            Player::Black,
+        );
+        assert_eq!(game.result(), Some(GameResult::WhiteWins(16)));
+
+        // Draw: 32 discs each.
+        let game = Game::new(
+            Bitboard::new(0xffffffff00000000, 0x00000000ffffffff),
+            Player::Black,
+        );
+        assert_eq!(game.result(), Some(GameResult::Draw));
+        assert_eq!(game.result().unwrap().winner(), None);
+
+        // The game is not over at the start.
+        assert_eq!(Game::default().result(), None);
+    }
 }
@@ -1,5 +1,6 @@
 mod bitboard;
 mod game;
+mod line;
 mod player;
 mod position;
 mod run_game;
@@ -7,6 +8,7 @@ pub mod utils;
 
 pub use bitboard::*;
 pub use game::*;
+pub use line::*;
 pub use player::*;
 pub use position::*;
 pub use run_game::*;
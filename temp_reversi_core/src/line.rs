@@ -0,0 +1,33 @@
+/// An 8-square line of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Line {
+    /// A row, from rank 1 (`Rank(0)`) to rank 8 (`Rank(7)`).
+    Rank(u8),
+    /// A column, from file A (`File(0)`) to file H (`File(7)`).
+    File(u8),
+    /// The diagonal from A1 to H8.
+    Diagonal,
+    /// The diagonal from H1 to A8.
+    AntiDiagonal,
+}
+
+impl Line {
+    /// Returns the squares of the line as a bitmask.
+    ///
+    /// # Panics
+    /// Panics if the index of a rank or file is not within 0..8.
+    pub fn mask(&self) -> u64 {
+        match *self {
+            Line::Rank(rank) => {
+                assert!(rank < 8, "rank must be within 0..8");
+                0xff << (rank * 8)
+            }
+            Line::File(file) => {
+                assert!(file < 8, "file must be within 0..8");
+                0x0101010101010101 << file
+            }
+            Line::Diagonal => 0x8040201008040201,
+            Line::AntiDiagonal => 0x0102040810204080,
+        }
+    }
+}
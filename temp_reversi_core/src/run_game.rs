@@ -1,15 +1,108 @@
-use crate::{Game, Player, Position};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{Game, GameResult, LegalMoves, Player, Position};
 
 pub trait MoveDecider {
     fn select_move(&mut self, game: &Game) -> Option<Position>;
+
+    /// Returns `true` if the player resigns instead of moving in the given position.
+    ///
+    /// Called before `select_move` on every turn. The default never resigns.
+    fn resign(&self, _game: &Game) -> bool {
+        false
+    }
+}
+
+impl<D: MoveDecider + ?Sized> MoveDecider for &mut D {
+    fn select_move(&mut self, game: &Game) -> Option<Position> {
+        (**self).select_move(game)
+    }
+
+    fn resign(&self, game: &Game) -> bool {
+        (**self).resign(game)
+    }
+}
+
+/// How a game run by [`run_game`] or [`run_game_timed`] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameOutcome {
+    /// The game was played to the end.
+    Finished(GameResult),
+    /// The given player resigned.
+    Resigned(Player),
+    /// The given player exceeded the time limit for a move.
+    TimedOut(Player),
+}
+
+impl GameOutcome {
+    /// Returns the winning player, or `None` for a draw.
+    pub fn winner(&self) -> Option<Player> {
+        match self {
+            GameOutcome::Finished(result) => result.winner(),
+            GameOutcome::Resigned(player) | GameOutcome::TimedOut(player) => {
+                Some(player.opponent())
+            }
+        }
+    }
+}
+
+impl fmt::Display for GameOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameOutcome::Finished(result) => write!(f, "{}", result),
+            GameOutcome::Resigned(player) => write!(f, "{:?} resigned", player),
+            GameOutcome::TimedOut(player) => write!(f, "{:?} lost on time", player),
+        }
+    }
 }
 
 /// Main game loop for Reversi, allowing for human or AI players.
+///
+/// # Returns
+/// - `Ok(GameOutcome)` describing how the game ended.
+/// - `Err(String)` if a decider returns an invalid move.
 pub fn run_game<D1, D2>(
+    black_decider: D1,
+    white_decider: D2,
+    display: impl FnMut(&Game),
+) -> Result<GameOutcome, String>
+where
+    D1: MoveDecider,
+    D2: MoveDecider,
+{
+    run_game_impl(black_decider, white_decider, display, None)
+}
+
+/// Main game loop with a time limit per move.
+///
+/// A decider that takes longer than `move_time_limit` to select a move loses the game.
+/// The move is not interrupted; the time is checked once it returns.
+///
+/// # Returns
+/// - `Ok(GameOutcome)` describing how the game ended.
+/// - `Err(String)` if a decider returns an invalid move.
+pub fn run_game_timed<D1, D2>(
+    black_decider: D1,
+    white_decider: D2,
+    display: impl FnMut(&Game),
+    move_time_limit: Duration,
+) -> Result<GameOutcome, String>
+where
+    D1: MoveDecider,
+    D2: MoveDecider,
+{
+    run_game_impl(black_decider, white_decider, display, Some(move_time_limit))
+}
+
+fn run_game_impl<D1, D2>(
     mut black_decider: D1,
     mut white_decider: D2,
     mut display: impl FnMut(&Game),
-) -> Result<(), String>
+    move_time_limit: Option<Duration>,
+) -> Result<GameOutcome, String>
 where
     D1: MoveDecider,
     D2: MoveDecider,
@@ -19,32 +112,120 @@ where
     loop {
         display(&game);
 
-        // Determine the move (either by human input or AI)
         let current_player = game.current_player();
+        match game.legal_moves() {
+            LegalMoves::GameOver => {
+                // Only reachable if no moves were available from the start.
+                return Ok(GameOutcome::Finished(game.result().unwrap()));
+            }
+            LegalMoves::MustPass => {
+                println!("No valid moves for {:?}. Skipping turn.", current_player);
+                game.pass()?;
+                continue;
+            }
+            LegalMoves::Moves(_) => {}
+        }
+
+        let resigned = match current_player {
+            Player::Black => black_decider.resign(&game),
+            Player::White => white_decider.resign(&game),
+        };
+        if resigned {
+            return Ok(GameOutcome::Resigned(current_player));
+        }
+
+        // Determine the move (either by human input or AI)
+        let start = Instant::now();
         let position = match current_player {
             Player::Black => black_decider.select_move(&game),
             Player::White => white_decider.select_move(&game),
         };
+        if move_time_limit.is_some_and(|limit| start.elapsed() > limit) {
+            return Ok(GameOutcome::TimedOut(current_player));
+        }
 
-        if let Some(position) = position {
-            if game.is_valid_move(position) {
-                game.apply_move(position)?;
-            } else {
-                return Err(format!("Invalid move: {:?}", position));
-            }
-        } else {
-            println!(
-                "No valid moves for {:?}. Skipping turn.",
-                game.current_player()
-            );
+        match position {
+            Some(position) if game.is_valid_move(position) => game.apply_move(position)?,
+            Some(position) => return Err(format!("Invalid move: {:?}", position)),
+            None => println!("No move selected by {:?}.", current_player),
         }
 
         // Check if the game is over
-        if game.is_game_over() {
+        if let Some(result) = game.result() {
             display(&game);
-            break;
+            return Ok(GameOutcome::Finished(result));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays the first valid move and optionally resigns once `resign_at_ply` moves were played.
+    struct FirstMoveDecider {
+        resign_at_ply: Option<usize>,
+        delay: Duration,
+    }
+
+    impl FirstMoveDecider {
+        fn new() -> Self {
+            Self {
+                resign_at_ply: None,
+                delay: Duration::ZERO,
+            }
+        }
+    }
+
+    impl MoveDecider for FirstMoveDecider {
+        fn select_move(&mut self, game: &Game) -> Option<Position> {
+            std::thread::sleep(self.delay);
+            game.valid_moves().first().copied()
+        }
+
+        fn resign(&self, game: &Game) -> bool {
+            self.resign_at_ply
+                .is_some_and(|ply| game.history_len() >= ply)
         }
     }
 
-    Ok(())
+    #[test]
+    fn test_run_game_finished() {
+        let outcome = run_game(FirstMoveDecider::new(), FirstMoveDecider::new(), |_| {}).unwrap();
+        assert!(matches!(outcome, GameOutcome::Finished(_)));
+    }
+
+    #[test]
+    fn test_run_game_resignation() {
+        let mut white = FirstMoveDecider::new();
+        white.resign_at_ply = Some(5);
+
+        let mut plies = 0;
+        let outcome = run_game(FirstMoveDecider::new(), &mut white, |game: &Game| {
+            plies = game.history_len()
+        })
+        .unwrap();
+
+        // White is to move after 5 plies and resigns.
+        assert_eq!(outcome, GameOutcome::Resigned(Player::White));
+        assert_eq!(outcome.winner(), Some(Player::Black));
+        assert_eq!(plies, 5);
+    }
+
+    #[test]
+    fn test_run_game_timed_timeout() {
+        let mut black = FirstMoveDecider::new();
+        black.delay = Duration::from_millis(20);
+
+        let outcome = run_game_timed(
+            black,
+            FirstMoveDecider::new(),
+            |_| {},
+            Duration::from_millis(5),
+        )
+        .unwrap();
+
+        assert_eq!(outcome, GameOutcome::TimedOut(Player::Black));
+        assert_eq!(outcome.winner(), Some(Player::White));
+    }
 }
@@ -1,3 +1,9 @@
+pub mod othello_format;
+
+use rayon::prelude::*;
+
+use crate::{Bitboard, Player, Position};
+
 /// Rotates a 64-bit bitmask representing an 8x8 board 90 degrees clockwise.
 ///
 /// # Arguments
@@ -83,6 +89,166 @@ pub fn rotate_mask_270_ccw(mask: u64) -> u64 {
     rotate_mask_90_cw(mask) // 270 degrees counterclockwise is the same as 90 degrees clockwise
 }
 
+/// Number of symmetry transforms of the board (4 rotations, each optionally mirrored).
+pub const SYMMETRY_COUNT: u8 = 8;
+
+/// Mirrors a 64-bit bitmask representing an 8x8 board horizontally (column A <-> column H).
+///
+/// # Arguments
+/// * `mask` - A 64-bit integer representing the bitmask of the board.
+///
+/// # Returns
+/// A new 64-bit integer where the columns are reversed.
+pub fn mirror_mask_horizontal(mask: u64) -> u64 {
+    mask.swap_bytes().reverse_bits()
+}
+
+/// Applies one of the 8 board symmetries to a bitmask.
+///
+/// # Arguments
+/// * `mask` - A 64-bit integer representing the bitmask of the board.
+/// * `transform` - Symmetry index in `0..SYMMETRY_COUNT`. Indices 4-7 mirror the board
+///   horizontally first; then the board is rotated `transform % 4` times 90 degrees clockwise.
+///
+/// # Returns
+/// The transformed bitmask.
+pub fn transform_mask(mask: u64, transform: u8) -> u64 {
+    let mask = if transform >= 4 {
+        mirror_mask_horizontal(mask)
+    } else {
+        mask
+    };
+    match transform % 4 {
+        1 => rotate_mask_90_cw(mask),
+        2 => rotate_mask_180(mask),
+        3 => rotate_mask_270_cw(mask),
+        _ => mask,
+    }
+}
+
+/// Undoes a symmetry applied with [`transform_mask`].
+///
+/// # Arguments
+/// * `mask` - A 64-bit integer representing the transformed bitmask.
+/// * `transform` - Symmetry index in `0..SYMMETRY_COUNT`.
+///
+/// # Returns
+/// The original bitmask.
+pub fn inverse_transform_mask(mask: u64, transform: u8) -> u64 {
+    let mask = match transform % 4 {
+        1 => rotate_mask_90_ccw(mask),
+        2 => rotate_mask_180(mask),
+        3 => rotate_mask_270_ccw(mask),
+        _ => mask,
+    };
+    if transform >= 4 {
+        mirror_mask_horizontal(mask)
+    } else {
+        mask
+    }
+}
+
+/// Counts the leaf nodes of the game tree to the given depth (perft).
+///
+/// Convention: a forced pass consumes one ply, like a move, and a finished game
+/// counts as a single leaf even if `depth` plies have not been played.
+///
+/// # Arguments
+/// * `board` - The board to start from.
+/// * `player` - The player to move.
+/// * `depth` - Number of plies to expand.
+///
+/// # Returns
+/// The number of leaf nodes.
+pub fn perft(board: &Bitboard, player: Player, depth: usize) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let valid_moves = board.valid_moves(player);
+    if valid_moves.is_empty() {
+        if board.valid_moves_count(player.opponent()) == 0 {
+            return 1; // Game over
+        }
+        return perft(board, player.opponent(), depth - 1); // Pass
+    }
+
+    valid_moves
+        .into_iter()
+        .map(|mv| {
+            let mut child = board.clone();
+            child.apply_move(mv, player).unwrap();
+            perft(&child, player.opponent(), depth - 1)
+        })
+        .sum()
+}
+
+/// Parallel version of [`perft`] that searches the root moves on the rayon thread pool.
+///
+/// # Arguments
+/// * `board` - The board to start from.
+/// * `player` - The player to move.
+/// * `depth` - Number of plies to expand.
+///
+/// # Returns
+/// The number of leaf nodes, identical to [`perft`].
+pub fn perft_parallel(board: &Bitboard, player: Player, depth: usize) -> u64 {
+    let valid_moves = board.valid_moves(player);
+    if depth == 0 || valid_moves.is_empty() {
+        return perft(board, player, depth);
+    }
+
+    valid_moves
+        .into_par_iter()
+        .map(|mv| {
+            let mut child = board.clone();
+            child.apply_move(mv, player).unwrap();
+            perft(&child, player.opponent(), depth - 1)
+        })
+        .sum()
+}
+
+/// Parses a transcript of concatenated move coordinates, such as `"f5d6c3"`.
+///
+/// Coordinates are case-insensitive. Passes are not written in transcripts.
+///
+/// # Arguments
+/// * `s` - The transcript to parse.
+///
+/// # Returns
+/// - `Ok(Vec<Position>)` with the moves in order.
+/// - `Err(String)` if the transcript has an odd length or contains an invalid coordinate.
+pub fn parse_transcript(s: &str) -> Result<Vec<Position>, String> {
+    let chars: Vec<char> = s.chars().collect();
+    if !chars.len().is_multiple_of(2) {
+        return Err(format!(
+            "Transcript length must be even, got {} characters",
+            chars.len()
+        ));
+    }
+
+    chars
+        .chunks(2)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let coordinate: String = chunk.iter().collect();
+            coordinate
+                .parse()
+                .map_err(|e| format!("Move {} ('{}'): {}", index, coordinate, e))
+        })
+        .collect()
+}
+
+/// Formats moves as a transcript of concatenated lowercase coordinates, such as `"f5d6c3"`.
+///
+/// This is the inverse of [`parse_transcript`].
+pub fn to_transcript(moves: &[Position]) -> String {
+    moves
+        .iter()
+        .map(|position| position.to_string().to_ascii_lowercase())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,4 +315,85 @@ mod tests {
         // Center vertical line -> Becomes center horizontal line
         assert_eq!(rotate_mask_270_ccw(0x1010101010101010), 0x000000FF00000000);
     }
+
+    #[test]
+    fn test_mirror_mask_horizontal() {
+        // Column A -> Column H
+        assert_eq!(
+            mirror_mask_horizontal(0x0101010101010101),
+            0x8080808080808080
+        );
+
+        // A single bit at (row=1, col=2) -> (row=1, col=5)
+        assert_eq!(mirror_mask_horizontal(1 << 10), 1 << 13);
+    }
+
+    #[test]
+    fn test_inverse_transform_mask() {
+        let mask = 0x0000_0000_0000_0702 | (1 << 63);
+        for transform in 0..SYMMETRY_COUNT {
+            assert_eq!(
+                inverse_transform_mask(transform_mask(mask, transform), transform),
+                mask
+            );
+        }
+    }
+
+    #[test]
+    fn test_perft_start_position() {
+        let board = Bitboard::default();
+        let expected = [1, 4, 12, 56, 244, 1396];
+
+        for (depth, &nodes) in expected.iter().enumerate() {
+            assert_eq!(perft(&board, Player::Black, depth), nodes);
+            assert_eq!(perft_parallel(&board, Player::Black, depth), nodes);
+        }
+    }
+
+    #[test]
+    fn test_perft_pass_and_game_over() {
+        // Black has no move, White can play C1 and then the game is over.
+        let black = 1 << 1; // B1
+        let white = 1; // A1
+        let board = Bitboard::new(black, white);
+        assert_eq!(board.valid_moves_count(Player::Black), 0);
+
+        // Depth 1: the pass; depth 2: White's only move; depth 3: the finished game.
+        assert_eq!(perft(&board, Player::Black, 1), 1);
+        assert_eq!(perft(&board, Player::Black, 2), 1);
+        assert_eq!(perft(&board, Player::Black, 3), 1);
+    }
+
+    #[test]
+    fn test_parse_transcript() {
+        // Opening of the tiger variation.
+        let moves = parse_transcript("f5d6c3d3c4").unwrap();
+        assert_eq!(
+            moves,
+            vec![
+                Position::F5,
+                Position::D6,
+                Position::C3,
+                Position::D3,
+                Position::C4
+            ]
+        );
+        assert_eq!(to_transcript(&moves), "f5d6c3d3c4");
+        assert_eq!(parse_transcript("F5D6C3D3C4").unwrap(), moves);
+
+        let mut game = crate::Game::default();
+        game.apply_moves(&moves).unwrap();
+        assert_eq!(game.current_player(), Player::White);
+
+        assert_eq!(parse_transcript("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_parse_transcript_errors() {
+        let err = parse_transcript("f5d").unwrap_err();
+        assert!(err.contains("even"), "{}", err);
+
+        let err = parse_transcript("f5z9").unwrap_err();
+        assert!(err.starts_with("Move 1"), "{}", err);
+    }
 }
@@ -0,0 +1,181 @@
+use crate::{Bitboard, Game, GameResult, Player, Position};
+
+/// Token used for a pass.
+const PASS: &str = "PASS";
+
+/// Writes a game as an Othello format record, an SGF-like text format.
+///
+/// The first node holds the metadata and each following node holds one move:
+///
+/// ```text
+/// (;GM[Othello]PB[Alice]PW[Bob]RE[B+4];B[F5];W[D6];...;W[PASS];B[H8])
+/// ```
+///
+/// `RE` is `B+n` or `W+n` for a win by `n` discs, `0` for a draw and `?` for an
+/// unfinished game. Forced passes, which [`Game`] applies implicitly, are written
+/// as `PASS` moves.
+///
+/// # Arguments
+/// * `game` - The game to write. It must have started from the standard initial position.
+/// * `black_name` - The name of the black player.
+/// * `white_name` - The name of the white player.
+///
+/// # Returns
+/// - `Ok(String)` with the record.
+/// - `Err(String)` if the move history cannot be replayed from the initial position.
+pub fn write_game(game: &Game, black_name: &str, white_name: &str) -> Result<String, String> {
+    let result = match game.result() {
+        Some(GameResult::BlackWins(margin)) => format!("B+{}", margin),
+        Some(GameResult::WhiteWins(margin)) => format!("W+{}", margin),
+        Some(GameResult::Draw) => "0".to_string(),
+        None => "?".to_string(),
+    };
+    let mut record = format!(
+        "(;GM[Othello]PB[{}]PW[{}]RE[{}]",
+        escape(black_name),
+        escape(white_name),
+        result
+    );
+
+    let mut board = Bitboard::default();
+    let mut player = Player::Black;
+    for (index, &position) in game.move_history().iter().enumerate() {
+        if board.valid_moves_count(player) == 0 {
+            record.push_str(&format!(";{}[{}]", color_tag(player), PASS));
+            player = player.opponent();
+        }
+        board
+            .apply_move(position, player)
+            .map_err(|e| format!("Move {} ({}): {}", index, position, e))?;
+        record.push_str(&format!(";{}[{}]", color_tag(player), position));
+        player = player.opponent();
+    }
+
+    record.push(')');
+    Ok(record)
+}
+
+/// Parses the moves of an Othello format record.
+///
+/// Metadata is skipped. The moves are replayed from the initial position, so each
+/// move must be legal, played by the player to move, and a `PASS` is only accepted
+/// when the player to move has no legal move.
+///
+/// # Arguments
+/// * `s` - The record to parse.
+///
+/// # Returns
+/// - `Ok(Vec<Position>)` with the moves in order, without the passes.
+/// - `Err(String)` if the record is malformed or contains an illegal move or pass.
+pub fn parse_moves(s: &str) -> Result<Vec<Position>, String> {
+    let body = s
+        .trim()
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .ok_or("Record must be enclosed in parentheses")?;
+
+    let mut nodes = body.split(';').map(str::trim);
+    if nodes.next() != Some("") {
+        return Err("Record must start with ';'".to_string());
+    }
+    let header = nodes.next().ok_or("Record has no metadata node")?;
+    if !header.contains("GM[Othello]") {
+        return Err("Record is not an Othello game".to_string());
+    }
+
+    let mut board = Bitboard::default();
+    let mut player = Player::Black;
+    let mut moves = Vec::new();
+    for (index, node) in nodes.enumerate() {
+        let (tag, value) = node
+            .strip_suffix(']')
+            .and_then(|node| node.split_once('['))
+            .ok_or_else(|| format!("Move {}: malformed node '{}'", index, node))?;
+        if tag != color_tag(player) {
+            return Err(format!(
+                "Move {}: expected {} to move, got '{}'",
+                index,
+                color_tag(player),
+                tag
+            ));
+        }
+
+        if value.eq_ignore_ascii_case(PASS) {
+            if board.valid_moves_count(player) != 0 {
+                return Err(format!("Move {}: pass with legal moves available", index));
+            }
+        } else {
+            let position: Position = value
+                .parse()
+                .map_err(|e| format!("Move {} ('{}'): {}", index, value, e))?;
+            board
+                .apply_move(position, player)
+                .map_err(|e| format!("Move {} ({}): {}", index, position, e))?;
+            moves.push(position);
+        }
+        player = player.opponent();
+    }
+
+    Ok(moves)
+}
+
+/// Returns the property tag for a player's moves.
+fn color_tag(player: Player) -> &'static str {
+    match player {
+        Player::Black => "B",
+        Player::White => "W",
+    }
+}
+
+/// Removes the characters that delimit properties and nodes from a player name.
+fn escape(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '[' | ']' | ';' | '(' | ')'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_transcript;
+
+    #[test]
+    fn test_round_trip_with_pass() {
+        // After the last move of this transcript White has no legal move, so Black moves again.
+        let transcript = "D3C3E6D6B2F4D7E3E2D2E1C4B5B3B4D8C7B8C6A1G4C1C8F3D1A2A3E7F7G3E8F8H3B7F5G5A8A5G8B6F2H4A6A4H5H6F6F1H7G7C5A7G6H2C2H8B1";
+        let mut game = Game::default();
+        game.apply_moves(&parse_transcript(transcript).unwrap())
+            .unwrap();
+        while !game.is_game_over() {
+            let position = game.valid_moves()[0];
+            game.apply_move(position).unwrap();
+        }
+
+        let record = write_game(&game, "Alice", "Bob").unwrap();
+        assert!(record.starts_with("(;GM[Othello]PB[Alice]PW[Bob]RE["));
+        assert!(record.contains(";B[B1];W[PASS];B["));
+        assert!(record.ends_with(')'));
+
+        assert_eq!(parse_moves(&record).unwrap(), game.move_history());
+    }
+
+    #[test]
+    fn test_result_and_unfinished_game() {
+        let mut game = Game::default();
+        game.apply_move(Position::F5).unwrap();
+        let record = write_game(&game, "A[1]", "B").unwrap();
+        assert_eq!(record, "(;GM[Othello]PB[A1]PW[B]RE[?];B[F5])");
+        assert_eq!(parse_moves(&record).unwrap(), vec![Position::F5]);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_records() {
+        // Not enclosed, wrong game, wrong player, illegal move and pass with moves available.
+        assert!(parse_moves(";GM[Othello];B[F5]").is_err());
+        assert!(parse_moves("(;GM[Go];B[F5])").is_err());
+        assert!(parse_moves("(;GM[Othello];W[F5])").is_err());
+        assert!(parse_moves("(;GM[Othello];B[A1])").is_err());
+        assert!(parse_moves("(;GM[Othello];B[PASS])").is_err());
+        assert!(parse_moves("(;GM[Othello];B[F5];W[D6]").is_err());
+    }
+}